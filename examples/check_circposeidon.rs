@@ -1,6 +1,7 @@
 use ark_bls12_381::Fr as F;
 use rand::thread_rng;
 use zk_callbacks::{
+    crypto::hash::HashDomain,
     generic::{
         object::ZKFields,
         user::{User, UserData},
@@ -31,11 +32,15 @@ fn main() {
         new_in_progress_callback_hash: F::from(0),
         old_in_progress_callback_hash: F::from(0),
         is_ingest_over: true,
+        scan_start: F::from(0),
+        last_full_scan: F::from(0),
+        secret: F::from(0),
     };
 
     let mut out = User::create(data.clone(), &mut rng);
     out.zk_fields = zk_fields.clone();
 
+    print!("{} ", HashDomain::UserCom.tag::<F>());
     for d in data.serialize_elements() {
         if d != F::from(0) {
             print!("{} ", d);
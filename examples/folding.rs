@@ -12,7 +12,7 @@ use folding_schemes::{
     transcript::poseidon::poseidon_canonical_config,
 };
 use rand::thread_rng;
-use std::time::SystemTime;
+use std::{sync::Arc, time::SystemTime};
 use zk_callbacks::{
     generic::{
         bulletin::{JoinableBulletin, PublicUserBul, UserBul},
@@ -96,8 +96,8 @@ fn main() {
         method_id: Id::from(0),
         expirable: false,
         expiration: Time::from(300),
-        method: cb_meth,
-        predicate: cb_pred,
+        method: Arc::new(cb_meth),
+        predicate: Arc::new(cb_pred),
     };
 
     // irrelevant callback type, we create it to test the checks
@@ -105,8 +105,8 @@ fn main() {
         method_id: Id::from(1),
         expirable: true,
         expiration: Time::from(1),
-        method: cb_meth,
-        predicate: cb_pred,
+        method: Arc::new(cb_meth),
+        predicate: Arc::new(cb_pred),
     };
 
     let mut store = St::new(&mut rng);
@@ -114,8 +114,11 @@ fn main() {
     let cb_methods = vec![cb.clone(), cb2.clone()];
 
     let interaction: Int1 = Interaction {
-        meth: (int_meth, int_meth_pred),
+        meth: (Arc::new(int_meth), Arc::new(int_meth_pred)),
         callbacks: [cb.clone()],
+        unchanged: None,
+        hidden_ids: None,
+        pseudonym_scope: None,
     };
 
     // generate keys for the method described initially
@@ -299,7 +302,8 @@ fn main() {
             cb_methods.clone(),
             &pkf,
             2,
-        );
+        )
+        .unwrap();
 
     println!("Rerandomization time: {:?}", start.elapsed().unwrap());
 
@@ -0,0 +1,257 @@
+use ark_bn254::{Bn254 as E, Fr as F};
+use ark_groth16::Groth16;
+use ark_r1cs_std::{eq::EqGadget, fields::fp::FpVar, prelude::Boolean};
+use ark_relations::r1cs::Result as ArkResult;
+use rand::thread_rng;
+use std::sync::Arc;
+use zk_callbacks::{
+    generic::{
+        interaction::{Callback, Interaction},
+        object::{Id, Time},
+        service::ServiceProvider,
+        user::{User, UserVar},
+    },
+    impls::{
+        centralized::crypto::{FakeSigPrivkey, FakeSigPubkey, NoSigOTP},
+        dummy::DummyStore,
+        hash::Poseidon,
+    },
+    zk_object,
+};
+
+// A forum where a moderator may later vote on a post, without the post (and the service storing
+// it) revealing whether the ticket calls the upvote or the downvote method.
+
+#[zk_object(F)]
+#[derive(Default)]
+pub struct ForumUser {
+    pub karma: F,
+}
+
+type U = User<F, ForumUser>;
+type UV = UserVar<F, ForumUser>;
+
+// The crypto we are using for authenticity and argument hiding (see the simple example).
+type Cr = NoSigOTP<F>;
+
+type CB = Callback<F, ForumUser, F, FpVar<F>>;
+
+// Posting issues a single callback.
+type Post = Interaction<F, ForumUser, (), (), (), (), F, FpVar<F>, 1>;
+
+// Posting leaves the user unchanged.
+fn post(tu: &U, _pub_args: (), _priv_args: ()) -> U {
+    tu.clone()
+}
+
+fn post_pred<'a>(
+    tu_old: &'a UV,
+    tu_new: &'a UV,
+    _pub_args: (),
+    _priv_args: (),
+) -> ArkResult<Boolean<F>> {
+    tu_old.data.karma.is_eq(&tu_new.data.karma)
+}
+
+// Every vote changes the karma of the user by the argument (the moderator picks the weight).
+fn vote(tu: &U, args: F) -> U {
+    let mut out = tu.clone();
+    out.data.karma += args;
+    out
+}
+
+fn vote_pred(tu_old: &UV, args: FpVar<F>) -> ArkResult<UV> {
+    let mut tu_new = tu_old.clone();
+    tu_new.data.karma += args;
+    Ok(tu_new)
+}
+
+fn main() {
+    let mut rng = thread_rng();
+
+    // The expiration of a ticket is revealed, so all the callbacks share the same expiration.
+    let upvote: CB = Callback {
+        method_id: Id::from(0),
+        expirable: false,
+        expiration: Time::from(300),
+        method: Arc::new(vote),
+        predicate: Arc::new(vote_pred),
+    };
+    let downvote: CB = Callback {
+        method_id: Id::from(1),
+        ..upvote.clone()
+    };
+    // A ban is not one of the callbacks a post may issue.
+    let ban: CB = Callback {
+        method_id: Id::from(2),
+        ..upvote.clone()
+    };
+
+    let allowed_ids = [upvote.method_id, downvote.method_id];
+    let allowed = vec![upvote.clone(), downvote.clone()];
+
+    // SERVER SETUP
+
+    // The keys only depend on the allowed ids, so any of the allowed callbacks may be issued with
+    // them.
+    let interaction: Post = Interaction {
+        meth: (Arc::new(post), Arc::new(post_pred)),
+        callbacks: [upvote.clone()],
+        unchanged: None,
+        hidden_ids: None,
+        pseudonym_scope: None,
+    }
+    .hiding_ids(&allowed_ids);
+
+    let (pk, vk) = interaction.generate_keys::<Poseidon<2>, Groth16<E>, Cr, DummyStore>(
+        &mut rng,
+        Some(()),
+        (),
+        false,
+    );
+
+    println!(
+        "[SERVER] Generated keys for posts hiding ids {:?}",
+        allowed_ids
+    );
+
+    // USER
+
+    let mut u = User::create(ForumUser { karma: F::from(0) }, &mut rng);
+
+    // The user posts, issuing a downvote ticket instead of the upvote.
+    let downvote_post = Interaction {
+        callbacks: [downvote.clone()],
+        ..interaction.clone()
+    };
+
+    let exec_method = u
+        .exec_method_create_cb::<Poseidon<2>, (), (), (), (), F, FpVar<F>, Cr, Groth16<E>, DummyStore, 1>(
+            &mut rng,
+            downvote_post,
+            [FakeSigPubkey::pk()],
+            Time::from(0),
+            &DummyStore,
+            true,
+            &pk,
+            (),
+            (),
+        )
+        .unwrap();
+
+    // The service is handed the callback with a blinded method id, while the user keeps the
+    // original.
+    let kept = u.callback_view::<F, Cr>().unwrap().get(0).unwrap().clone();
+    let handed = &exec_method.cb_tik_list[0].0;
+    assert_eq!(kept.cb_entry.cb_method_id, downvote.method_id);
+    assert!(!allowed_ids.contains(&handed.cb_entry.cb_method_id));
+    assert_eq!(
+        kept.blind_method_id::<Poseidon<2>>()
+            .commit::<Poseidon<2>>(),
+        exec_method.cb_com_list[0]
+    );
+    println!(
+        "[USER] Posted! The service sees the method id {}",
+        handed.cb_entry.cb_method_id
+    );
+
+    // SERVER
+
+    let approved =
+        <DummyStore as ServiceProvider<F, F, FpVar<F>, Cr>>::approve_interaction_hidden_ids::<
+            ForumUser,
+            Groth16<E>,
+            (),
+            DummyStore,
+            Poseidon<2>,
+            1,
+        >(
+            &DummyStore,
+            &exec_method,
+            FakeSigPrivkey::sk(),
+            (),
+            &DummyStore,
+            allowed.clone(),
+            1,
+            Time::from(0),
+            (),
+            true,
+            &vk,
+        );
+    assert!(approved);
+    println!("[SERVER] Approved post: {:?}", approved);
+
+    // USER (MISBEHAVING)
+
+    // The user cannot prove a post which issues a ban, as the id is not allowed.
+    let ban_post = Interaction {
+        callbacks: [ban.clone()],
+        ..interaction.clone()
+    };
+
+    let out = u.dry_run_interact::<Poseidon<2>, (), (), (), (), F, FpVar<F>, Cr, DummyStore, 1>(
+        &mut rng,
+        ban_post.clone(),
+        [FakeSigPubkey::pk()],
+        Time::from(0),
+        ((), ()),
+        true,
+        (),
+        (),
+        false,
+    );
+    assert!(out.is_err());
+    println!(
+        "[USER] Could not prove a post issuing a ban: {:?}",
+        out.err()
+    );
+
+    // Nor can it pass off a proof made with keys allowing bans.
+    let rogue_post = ban_post.hiding_ids(&[upvote.method_id, downvote.method_id, ban.method_id]);
+    let (rogue_pk, _) = rogue_post.generate_keys::<Poseidon<2>, Groth16<E>, Cr, DummyStore>(
+        &mut rng,
+        Some(()),
+        (),
+        false,
+    );
+
+    let rogue_exec = u
+        .exec_method_create_cb::<Poseidon<2>, (), (), (), (), F, FpVar<F>, Cr, Groth16<E>, DummyStore, 1>(
+            &mut rng,
+            rogue_post,
+            [FakeSigPubkey::pk()],
+            Time::from(0),
+            &DummyStore,
+            true,
+            &rogue_pk,
+            (),
+            (),
+        )
+        .unwrap();
+
+    // SERVER
+
+    let approved =
+        <DummyStore as ServiceProvider<F, F, FpVar<F>, Cr>>::approve_interaction_hidden_ids::<
+            ForumUser,
+            Groth16<E>,
+            (),
+            DummyStore,
+            Poseidon<2>,
+            1,
+        >(
+            &DummyStore,
+            &rogue_exec,
+            FakeSigPrivkey::sk(),
+            (),
+            &DummyStore,
+            allowed,
+            1,
+            Time::from(0),
+            (),
+            true,
+            &vk,
+        );
+    assert!(!approved);
+    println!("[SERVER] Approved post issuing a ban: {:?}", approved);
+}
@@ -4,7 +4,7 @@ use ark_r1cs_std::{eq::EqGadget, fields::fp::FpVar, prelude::Boolean};
 use ark_relations::r1cs::{Result as ArkResult, ToConstraintField};
 use ark_snark::SNARK;
 use rand::thread_rng;
-use std::time::SystemTime;
+use std::{sync::Arc, time::SystemTime};
 use zk_callbacks::{
     generic::{
         bulletin::{CallbackBul, JoinableBulletin, UserBul},
@@ -138,8 +138,8 @@ fn main() {
         method_id: Id::from(0),
         expirable: false,
         expiration: Time::from(300),
-        method: cb_meth,
-        predicate: cb_pred,
+        method: Arc::new(cb_meth),
+        predicate: Arc::new(cb_pred),
     };
 
     // irrelevant callback type, we create it to test the checks
@@ -147,8 +147,8 @@ fn main() {
         method_id: Id::from(1),
         expirable: true,
         expiration: Time::from(1),
-        method: cb_meth,
-        predicate: cb_pred,
+        method: Arc::new(cb_meth),
+        predicate: Arc::new(cb_pred),
     };
 
     println!("[SERVER] INIT...");
@@ -168,8 +168,11 @@ fn main() {
 
     // The first type of allowed interaction: a standard interaction
     let interaction: Int1 = Interaction {
-        meth: (int_meth, int_meth_pred),
+        meth: (Arc::new(int_meth), Arc::new(int_meth_pred)),
         callbacks: [cb.clone()],
+        unchanged: None,
+        hidden_ids: None,
+        pseudonym_scope: None,
     };
 
     // Generate keys for interaction 1, callback interaction, and proving a specific statement
@@ -221,7 +224,12 @@ fn main() {
         (),
         Groth16<E>,
         GRSchnorrObjStore,
-    >(&mut rng, some_pred, Some(store.obj_bul.get_pubkey()), ());
+    >(
+        &mut rng,
+        Arc::new(some_pred),
+        Some(store.obj_bul.get_pubkey()),
+        (),
+    );
 
     println!(
         "\t (time) Generated proof keys: {:?}",
@@ -257,7 +265,7 @@ fn main() {
     let proof = u
         .prove_statement_and_in::<Poseidon<2>, (), (), (), (), Groth16<E>, GRSchnorrObjStore>(
             &mut rng,
-            some_pred, // Specifically, this statement here (see some_pred above)
+            Arc::new(some_pred), // Specifically, this statement here (see some_pred above)
             &pki,
             (
                 store
@@ -0,0 +1,139 @@
+use crate::{
+    crypto::hash::{FieldHash, HashDomain, hash_with_domain, hash_with_domain_in_zk},
+    generic::object::{ComRand, ComRandVar, Ser, SerVar},
+};
+use ark_ff::{PrimeField, ToConstraintField};
+use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget, fields::fp::FpVar};
+use ark_relations::r1cs::SynthesisError;
+use std::marker::PhantomData;
+
+/// Trait for commitment schemes for user objects, which can also be represented in zero
+/// knowledge.
+///
+/// A user object is committed to as a vector of field elements (the serialized user data,
+/// followed by the serialized [`ZKFields`](`crate::generic::object::ZKFields`)), along with the
+/// commitment randomness of the user. Note that the serialized bookkeeping fields already contain
+/// the commitment randomness, so schemes which only need a message (such as hash based schemes)
+/// may ignore `rand`.
+///
+/// The default scheme for users is [`HashCom`], which produces the same commitment as
+/// [`User::commit`](`crate::generic::user::User::commit`).
+///
+/// Commitment schemes are not pluggable everywhere: bulletins, interactions and scans always
+/// commit with [`HashCom`]. Other schemes may only be used to commit to a user through
+/// [`User::commit_with`](`crate::generic::user::User::commit_with`), and to prove statements
+/// about such commitments through
+/// [`User::prove_committed_statement`](`crate::generic::user::User::prove_committed_statement`).
+/// In particular, a service can not apply a [`HomomorphicCommitment`] update to the commitment of
+/// a user in a bulletin.
+pub trait UserCommitment<F: PrimeField>: Send + Sync {
+    /// Public parameters for the commitment scheme.
+    type Params: Clone;
+    /// Public parameters in zero knowledge.
+    type ParamsVar: AllocVar<Self::Params, F> + Clone;
+    /// The commitment type.
+    type Com: Clone + Default + std::fmt::Debug + PartialEq + ToConstraintField<F>;
+    /// The commitment type in zero knowledge.
+    type ComVar: AllocVar<Self::Com, F> + EqGadget<F> + Clone;
+
+    /// Commit to a vector of field elements with randomness `rand`.
+    fn commit(params: &Self::Params, data: &[Ser<F>], rand: ComRand<F>) -> Self::Com;
+
+    /// Commit to a vector of field elements with randomness `rand` in zero knowledge.
+    fn commit_in_zk(
+        params: &Self::ParamsVar,
+        data: &[SerVar<F>],
+        rand: &ComRandVar<F>,
+    ) -> Result<Self::ComVar, SynthesisError>;
+}
+
+/// An error indicating a commitment could not be updated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ComError {
+    /// The index of the updated element is out of range of the commitment parameters.
+    IndexOutOfRange {
+        /// The requested index.
+        index: usize,
+        /// The number of elements supported by the parameters.
+        len: usize,
+    },
+}
+
+impl std::fmt::Display for ComError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComError::IndexOutOfRange { index, len } => {
+                write!(f, "element index {} out of range ({} elements)", index, len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComError {}
+
+/// Trait for commitment schemes which allow for additive updates to committed elements.
+///
+/// Given a commitment to a vector `(m_0, ..., m_n)`, anyone with the public parameters may produce
+/// a commitment to `(m_0, ..., m_i + delta, ..., m_n)` with the same randomness, without knowing
+/// the opening. For example, a service may add karma to a commitment from
+/// [`User::commit_with`](`crate::generic::user::User::commit_with`) directly.
+///
+/// Updates may act on the elements as integers rather than as field elements (as for
+/// [`Pedersen`](`crate::impls::pedersen::Pedersen`) commitments), in which case they do not wrap
+/// around the field modulus.
+pub trait HomomorphicCommitment<F: PrimeField>: UserCommitment<F> {
+    /// Add `delta` to the element at position `index` of the committed vector.
+    ///
+    /// If the element goes past the field modulus, the result commits to a value which is not a
+    /// field element, and so can never be opened in-circuit.
+    ///
+    /// Returns an error if `index` is out of range of the parameters.
+    fn add_to(
+        params: &Self::Params,
+        com: &Self::Com,
+        index: usize,
+        delta: F,
+    ) -> Result<Self::Com, ComError>;
+
+    /// Subtract `delta` from the element at position `index` of the committed vector.
+    ///
+    /// If the element goes below zero, the result commits to a negative value, and so can never be
+    /// opened in-circuit. As the element is hidden, this can not be detected from the commitment;
+    /// callers must know `delta` is at most the committed element.
+    ///
+    /// Returns an error if `index` is out of range of the parameters.
+    fn sub_from(
+        params: &Self::Params,
+        com: &Self::Com,
+        index: usize,
+        delta: F,
+    ) -> Result<Self::Com, ComError>;
+}
+
+/// A commitment scheme from a field hash.
+///
+/// Commits by hashing the message in the [`HashDomain::UserCom`] domain. This is the commitment
+/// scheme used for all user objects by default.
+#[derive(Clone, Default, Debug)]
+pub struct HashCom<H> {
+    _phantom_hash: PhantomData<H>,
+}
+
+impl<F: PrimeField, H: FieldHash<F>> UserCommitment<F> for HashCom<H> {
+    type Params = ();
+    type ParamsVar = ();
+    type Com = F;
+    type ComVar = FpVar<F>;
+
+    fn commit(_params: &(), data: &[Ser<F>], _rand: ComRand<F>) -> F {
+        hash_with_domain::<F, H>(HashDomain::UserCom, data)
+    }
+
+    fn commit_in_zk(
+        _params: &(),
+        data: &[SerVar<F>],
+        _rand: &ComRandVar<F>,
+    ) -> Result<FpVar<F>, SynthesisError> {
+        hash_with_domain_in_zk::<F, H>(HashDomain::UserCom, data)
+    }
+}
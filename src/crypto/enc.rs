@@ -1,6 +1,10 @@
 use crate::crypto::rr::{RRSigner, RRVerifier};
 use ark_ff::{PrimeField, ToConstraintField};
-use ark_r1cs_std::{convert::ToConstraintFieldGadget, prelude::AllocVar};
+use ark_r1cs_std::{
+    convert::ToConstraintFieldGadget,
+    fields::fp::FpVar,
+    prelude::{AllocVar, Boolean},
+};
 use ark_relations::r1cs::SynthesisError;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::{CryptoRng, RngCore};
@@ -188,4 +192,24 @@ pub trait AECipherSigZK<F: PrimeField, Args: Clone>: Clone + std::fmt::Debug {
 
         (enc, sig)
     }
+
+    /// The ciphertext signed by a service to release a ticket, given the release tag of the ticket
+    /// (see [`release_tag`](crate::generic::callbacks::release_tag)).
+    ///
+    /// This should not be the encryption of any arguments, so that a release can not be replayed as
+    /// a call.
+    fn release_ct(tag: F) -> Self::Ct;
+
+    /// Whether a ticket posted with `ct` at `time` was released rather than called, given the
+    /// release tag of the ticket.
+    fn is_release(tag: F, ct: &Self::Ct, time: F) -> bool;
+
+    /// Whether a ticket posted with `ct` at `time` was released rather than called, in-circuit.
+    ///
+    /// See [`AECipherSigZK::is_release`].
+    fn is_release_in_zk(
+        tag: &FpVar<F>,
+        ct: &<Self::EncKey as CPACipher<F>>::CV,
+        time: &FpVar<F>,
+    ) -> Result<Boolean<F>, SynthesisError>;
 }
@@ -76,3 +76,94 @@ pub trait FieldHash<F: PrimeField>:
     HasherZK<F, C = F, M = Ser<F>, MV = SerVar<F>, CV = FpVar<F>> + Clone
 {
 }
+
+/// The version of the commitment scheme.
+///
+/// Every domain tag produced by [`HashDomain`] embeds this version, so commitments and hash chains
+/// produced under different versions of the scheme never collide.
+pub const COMMITMENT_SCHEME_VERSION: u32 = 1;
+
+/// The domains in which the framework uses a [`FieldHash`].
+///
+/// Each hash use is prefixed with a distinct domain tag (both natively and in-circuit), so that
+/// e.g. a user commitment and a callback commitment with the same serialized length can never be
+/// confused with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashDomain {
+    /// Commitments to user objects.
+    UserCom,
+    /// Commitments to callback tickets.
+    CallbackCom,
+    /// Links of the callback hash chain.
+    CallbackHashChain,
+    /// Derivation of nullifiers.
+    Nullifier,
+    /// Messages signed by a range signature store.
+    SigRange,
+    /// Expansion of public UOV matrices from a seed.
+    UOVExpand,
+    /// The digest signed by UOV, and the derivation of the vinegar variables for deterministic
+    /// UOV signing.
+    UOVSign,
+    /// Blinding of hidden callback method ids.
+    CallbackMethodId,
+    /// Hiding of user fields revealed in a statement proof.
+    Disclosure,
+    /// Derivation of scope pseudonyms.
+    Pseudonym,
+    /// Tags signed by a service to release a callback ticket.
+    Release,
+    /// Messages signed by a signature store to attest membership of an object commitment.
+    ObjectMembership,
+    /// Messages signed by a signature store to attest membership of a called ticket.
+    CallbackMembership,
+}
+
+impl HashDomain {
+    /// The numeric identifier of the domain.
+    pub fn id(&self) -> u32 {
+        match self {
+            HashDomain::UserCom => 1,
+            HashDomain::CallbackCom => 2,
+            HashDomain::CallbackHashChain => 3,
+            HashDomain::Nullifier => 4,
+            HashDomain::SigRange => 5,
+            HashDomain::UOVExpand => 6,
+            HashDomain::UOVSign => 7,
+            HashDomain::CallbackMethodId => 8,
+            HashDomain::Disclosure => 9,
+            HashDomain::Pseudonym => 10,
+            HashDomain::Release => 11,
+            HashDomain::ObjectMembership => 12,
+            HashDomain::CallbackMembership => 13,
+        }
+    }
+
+    /// The domain tag as a field element, which includes the commitment scheme version.
+    pub fn tag<F: PrimeField>(&self) -> Ser<F> {
+        F::from(((COMMITMENT_SCHEME_VERSION as u64) << 32) | self.id() as u64)
+    }
+
+    /// The domain tag as a constant in-circuit.
+    pub fn tag_var<F: PrimeField>(&self) -> SerVar<F> {
+        FpVar::Constant(self.tag())
+    }
+}
+
+/// Hash `data` within the domain `domain`.
+///
+/// This prefixes the message with the tag of the domain, and then hashes with `H`.
+pub fn hash_with_domain<F: PrimeField, H: FieldHash<F>>(domain: HashDomain, data: &[Ser<F>]) -> F {
+    H::hash(&[&[domain.tag::<F>()], data].concat())
+}
+
+/// Hash `data` within the domain `domain` in-circuit.
+///
+/// This is the in-circuit version of [`hash_with_domain`]; the domain tag is a constant, so it
+/// adds no witnesses.
+pub fn hash_with_domain_in_zk<F: PrimeField, H: FieldHash<F>>(
+    domain: HashDomain,
+    data: &[SerVar<F>],
+) -> Result<FpVar<F>, SynthesisError> {
+    H::hash_in_zk(&[&[domain.tag_var::<F>()], data].concat())
+}
@@ -0,0 +1,411 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use chacha20poly1305::{
+    XChaCha20Poly1305, XNonce,
+    aead::{Aead, KeyInit, Payload},
+};
+use rand::{CryptoRng, RngCore};
+use std::{fs, path::Path};
+use zeroize::Zeroizing;
+
+/// Magic bytes at the start of every keystore.
+pub const KEYSTORE_MAGIC: [u8; 8] = *b"ZKCBKEYS";
+
+/// The current version of the keystore format.
+pub const KEYSTORE_VERSION: u8 = 1;
+
+/// The largest memory cost accepted for a keystore, in KiB (1 GiB).
+pub const MAX_M_COST: u32 = 1 << 20;
+
+/// The largest number of passes accepted for a keystore.
+pub const MAX_T_COST: u32 = 64;
+
+/// The largest degree of parallelism accepted for a keystore.
+pub const MAX_P_COST: u32 = 64;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+const HEADER_LEN: usize = KEYSTORE_MAGIC.len() + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+
+/// An error indicating a keystore could not be created or opened.
+#[derive(Debug)]
+pub enum KeystoreError {
+    /// The keystore is truncated or does not start with [`KEYSTORE_MAGIC`].
+    Malformed,
+    /// The keystore was written with an unsupported version of the format.
+    UnsupportedVersion(u8),
+    /// The key derivation parameters are invalid, or exceed [`MAX_M_COST`], [`MAX_T_COST`] or
+    /// [`MAX_P_COST`].
+    InvalidParams,
+    /// Encryption of the key failed.
+    Encryption,
+    /// Decryption failed: either the password is wrong or the keystore was tampered with.
+    Decryption,
+    /// The decrypted key could not be (de)serialized.
+    Serialization(SerializationError),
+    /// Reading or writing the keystore file failed.
+    Io(std::io::Error),
+}
+
+impl From<SerializationError> for KeystoreError {
+    fn from(e: SerializationError) -> Self {
+        KeystoreError::Serialization(e)
+    }
+}
+
+impl From<std::io::Error> for KeystoreError {
+    fn from(e: std::io::Error) -> Self {
+        KeystoreError::Io(e)
+    }
+}
+
+/// Cost parameters for the Argon2id password hash used to derive the encryption key.
+///
+/// These are stored in the keystore header, so keystores may be opened regardless of the
+/// parameters used to create them. Since the header is read before the password is checked, the
+/// costs are bounded by [`MAX_M_COST`], [`MAX_T_COST`] and [`MAX_P_COST`], so an untrusted
+/// keystore cannot make opening it arbitrarily expensive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeystoreParams {
+    /// Memory cost, in KiB.
+    pub m_cost: u32,
+    /// Number of passes.
+    pub t_cost: u32,
+    /// Degree of parallelism.
+    pub p_cost: u32,
+}
+
+impl Default for KeystoreParams {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KeystoreParams {
+    fn derive_key(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+    ) -> Result<Zeroizing<[u8; KEY_LEN]>, KeystoreError> {
+        if self.m_cost > MAX_M_COST || self.t_cost > MAX_T_COST || self.p_cost > MAX_P_COST {
+            return Err(KeystoreError::InvalidParams);
+        }
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(KEY_LEN))
+            .map_err(|_| KeystoreError::InvalidParams)?;
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password, salt, &mut *key)
+            .map_err(|_| KeystoreError::InvalidParams)?;
+        Ok(key)
+    }
+}
+
+/// Encrypt a (private) key under a password.
+///
+/// The key is serialized with [`CanonicalSerialize`], and encrypted with XChaCha20-Poly1305 under
+/// a key derived from the password with Argon2id. The output has the format
+///
+/// ```text
+/// magic (8) || version (1) || m_cost (4) || t_cost (4) || p_cost (4) || salt (16) || nonce (24) || ciphertext
+/// ```
+///
+/// where integers are little endian, and the header is authenticated along with the ciphertext.
+///
+/// # Example
+/// ```rust
+/// # use zk_callbacks::crypto::keystore::{KeystoreError, KeystoreParams, decrypt_key, encrypt_key};
+/// # use zk_callbacks::impls::centralized::ds::sig::{Signature, jj_schnorr::JubjubSchnorr};
+/// # use ark_bls12_381::Fr;
+/// # use rand::thread_rng;
+/// type Privkey = <JubjubSchnorr as Signature<Fr>>::Privkey;
+///
+/// let mut rng = thread_rng();
+/// let sk = JubjubSchnorr::gen_key(&mut rng);
+///
+/// let ks = encrypt_key(&sk, b"hunter2", KeystoreParams::default(), &mut rng).unwrap();
+/// let opened: Privkey = decrypt_key(&ks, b"hunter2").unwrap();
+///
+/// assert_eq!(JubjubSchnorr::get_pubkey(&opened), JubjubSchnorr::get_pubkey(&sk));
+/// assert!(decrypt_key::<Privkey>(&ks, b"hunter3").is_err());
+///
+/// // The header is authenticated, and the costs in it are bounded.
+/// let mut tampered = ks.clone();
+/// tampered[9] ^= 1;
+/// assert!(decrypt_key::<Privkey>(&tampered, b"hunter2").is_err());
+/// tampered[9..13].copy_from_slice(&u32::MAX.to_le_bytes());
+/// assert!(matches!(
+///     decrypt_key::<Privkey>(&tampered, b"hunter2"),
+///     Err(KeystoreError::InvalidParams)
+/// ));
+/// ```
+pub fn encrypt_key<K: CanonicalSerialize>(
+    key: &K,
+    password: &[u8],
+    params: KeystoreParams,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<Vec<u8>, KeystoreError> {
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+
+    let mut out = Vec::with_capacity(HEADER_LEN);
+    out.extend_from_slice(&KEYSTORE_MAGIC);
+    out.push(KEYSTORE_VERSION);
+    out.extend_from_slice(&params.m_cost.to_le_bytes());
+    out.extend_from_slice(&params.t_cost.to_le_bytes());
+    out.extend_from_slice(&params.p_cost.to_le_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+
+    let mut plaintext = Zeroizing::new(Vec::new());
+    key.serialize_compressed(&mut *plaintext)?;
+
+    let enc_key = params.derive_key(password, &salt)?;
+    let cipher = XChaCha20Poly1305::new_from_slice(enc_key.as_slice())
+        .map_err(|_| KeystoreError::InvalidParams)?;
+    let ciphertext = cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext[..],
+                aad: &out,
+            },
+        )
+        .map_err(|_| KeystoreError::Encryption)?;
+
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a (private) key encrypted with [`encrypt_key`].
+///
+/// Fails with [`KeystoreError::Decryption`] if the password is incorrect or the keystore was
+/// modified, including its header. Fails with [`KeystoreError::InvalidParams`] before deriving
+/// any key if the cost parameters in the header exceed the maximum costs.
+pub fn decrypt_key<K: CanonicalDeserialize>(
+    keystore: &[u8],
+    password: &[u8],
+) -> Result<K, KeystoreError> {
+    if keystore.len() < HEADER_LEN || keystore[..KEYSTORE_MAGIC.len()] != KEYSTORE_MAGIC {
+        return Err(KeystoreError::Malformed);
+    }
+    let (header, ciphertext) = keystore.split_at(HEADER_LEN);
+
+    let version = header[KEYSTORE_MAGIC.len()];
+    if version != KEYSTORE_VERSION {
+        return Err(KeystoreError::UnsupportedVersion(version));
+    }
+
+    let read_u32 = |i: usize| {
+        let start = KEYSTORE_MAGIC.len() + 1 + 4 * i;
+        u32::from_le_bytes(header[start..start + 4].try_into().unwrap())
+    };
+    let params = KeystoreParams {
+        m_cost: read_u32(0),
+        t_cost: read_u32(1),
+        p_cost: read_u32(2),
+    };
+    let salt = &header[HEADER_LEN - NONCE_LEN - SALT_LEN..HEADER_LEN - NONCE_LEN];
+    let nonce = &header[HEADER_LEN - NONCE_LEN..];
+
+    let enc_key = params.derive_key(password, salt)?;
+    let cipher = XChaCha20Poly1305::new_from_slice(enc_key.as_slice())
+        .map_err(|_| KeystoreError::InvalidParams)?;
+    // The whole header is authenticated as associated data.
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| KeystoreError::Decryption)?,
+    );
+
+    Ok(K::deserialize_compressed(&plaintext[..])?)
+}
+
+/// Encrypt a (private) key under a password, and write the keystore to a file.
+///
+/// See [`encrypt_key`] for the format of the file.
+pub fn save_key<K: CanonicalSerialize>(
+    path: impl AsRef<Path>,
+    key: &K,
+    password: &[u8],
+    params: KeystoreParams,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<(), KeystoreError> {
+    fs::write(path, encrypt_key(key, password, params, rng)?)?;
+    Ok(())
+}
+
+/// Read a keystore file written with [`save_key`], and decrypt the key with a password.
+pub fn load_key<K: CanonicalDeserialize>(
+    path: impl AsRef<Path>,
+    password: &[u8],
+) -> Result<K, KeystoreError> {
+    decrypt_key(&fs::read(path)?, password)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    // Cheap parameters, so the tests do not spend their time in Argon2.
+    const PARAMS: KeystoreParams = KeystoreParams {
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    const M_COST_AT: usize = KEYSTORE_MAGIC.len() + 1;
+
+    fn keystore() -> (Fr, Vec<u8>) {
+        let mut rng = thread_rng();
+        let key = Fr::rand(&mut rng);
+        let ks = encrypt_key(&key, b"hunter2", PARAMS, &mut rng).unwrap();
+        (key, ks)
+    }
+
+    // Tests that a keystore opens with the right password only
+    #[test]
+    fn keystore_wrong_password() {
+        let (key, ks) = keystore();
+        assert_eq!(decrypt_key::<Fr>(&ks, b"hunter2").unwrap(), key);
+        assert!(matches!(
+            decrypt_key::<Fr>(&ks, b"hunter3"),
+            Err(KeystoreError::Decryption)
+        ));
+        assert!(matches!(
+            decrypt_key::<Fr>(&ks, b""),
+            Err(KeystoreError::Decryption)
+        ));
+    }
+
+    // Tests that modifying any byte of the ciphertext or the authenticated header is detected
+    #[test]
+    fn keystore_tampered() {
+        let (_, ks) = keystore();
+        for i in [
+            HEADER_LEN,
+            ks.len() - 1,
+            HEADER_LEN - 1,
+            HEADER_LEN - NONCE_LEN - 1,
+        ] {
+            let mut tampered = ks.clone();
+            tampered[i] ^= 1;
+            assert!(matches!(
+                decrypt_key::<Fr>(&tampered, b"hunter2"),
+                Err(KeystoreError::Decryption)
+            ));
+        }
+
+        // A lower (in bounds) cost parameter is authenticated as well.
+        let mut tampered = ks.clone();
+        tampered[M_COST_AT..M_COST_AT + 4].copy_from_slice(&128u32.to_le_bytes());
+        assert!(matches!(
+            decrypt_key::<Fr>(&tampered, b"hunter2"),
+            Err(KeystoreError::Decryption)
+        ));
+
+        let mut tampered = ks.clone();
+        tampered[0] ^= 1;
+        assert!(matches!(
+            decrypt_key::<Fr>(&tampered, b"hunter2"),
+            Err(KeystoreError::Malformed)
+        ));
+
+        let mut tampered = ks;
+        tampered[KEYSTORE_MAGIC.len()] = KEYSTORE_VERSION + 1;
+        assert!(matches!(
+            decrypt_key::<Fr>(&tampered, b"hunter2"),
+            Err(KeystoreError::UnsupportedVersion(v)) if v == KEYSTORE_VERSION + 1
+        ));
+    }
+
+    // Tests that out of bounds cost parameters are rejected, both when creating and opening
+    #[test]
+    fn keystore_cost_bounds() {
+        let mut rng = thread_rng();
+        let key = Fr::rand(&mut rng);
+        for params in [
+            KeystoreParams {
+                m_cost: MAX_M_COST + 1,
+                ..PARAMS
+            },
+            KeystoreParams {
+                t_cost: MAX_T_COST + 1,
+                ..PARAMS
+            },
+            KeystoreParams {
+                p_cost: MAX_P_COST + 1,
+                ..PARAMS
+            },
+            KeystoreParams {
+                t_cost: 0,
+                ..PARAMS
+            },
+        ] {
+            assert!(matches!(
+                encrypt_key(&key, b"hunter2", params, &mut rng),
+                Err(KeystoreError::InvalidParams)
+            ));
+        }
+
+        let (_, ks) = keystore();
+        for (i, max) in [MAX_M_COST, MAX_T_COST, MAX_P_COST].into_iter().enumerate() {
+            let at = M_COST_AT + 4 * i;
+            let mut tampered = ks.clone();
+            tampered[at..at + 4].copy_from_slice(&(max + 1).to_le_bytes());
+            assert!(matches!(
+                decrypt_key::<Fr>(&tampered, b"hunter2"),
+                Err(KeystoreError::InvalidParams)
+            ));
+        }
+    }
+
+    // Tests that a truncated keystore file is rejected
+    #[test]
+    fn keystore_truncated() {
+        let mut rng = thread_rng();
+        let key = Fr::rand(&mut rng);
+        let path = std::env::temp_dir().join(format!("zkcb-keystore-{}", rng.next_u64()));
+        save_key(&path, &key, b"hunter2", PARAMS, &mut rng).unwrap();
+        assert_eq!(load_key::<Fr>(&path, b"hunter2").unwrap(), key);
+
+        let ks = fs::read(&path).unwrap();
+        fs::write(&path, &ks[..ks.len() - 1]).unwrap();
+        assert!(matches!(
+            load_key::<Fr>(&path, b"hunter2"),
+            Err(KeystoreError::Decryption)
+        ));
+        fs::write(&path, &ks[..HEADER_LEN - 1]).unwrap();
+        assert!(matches!(
+            load_key::<Fr>(&path, b"hunter2"),
+            Err(KeystoreError::Malformed)
+        ));
+        fs::write(&path, []).unwrap();
+        assert!(matches!(
+            load_key::<Fr>(&path, b"hunter2"),
+            Err(KeystoreError::Malformed)
+        ));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            load_key::<Fr>(&path, b"hunter2"),
+            Err(KeystoreError::Io(_))
+        ));
+    }
+}
@@ -4,11 +4,17 @@
 //! system. For example, zk-callbacks relies on rerandomizable public keys for callbacks, along
 //! with IND-CPA encryption (which can also be done in zero-knowledge).
 
+/// Traits for (vector) commitments to user objects in zero knowledge.
+pub mod com;
+
 /// Traits for IND-CPA encryption and authenticated encryption with signatures.
 pub mod enc;
 
 /// Traits for hashing in zero knowledge.
 pub mod hash;
 
+/// Password-encrypted storage for private keys.
+pub mod keystore;
+
 /// Traits for public key rerandomizable signatures.
 pub mod rr;
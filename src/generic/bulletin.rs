@@ -1,9 +1,11 @@
 use crate::{
     crypto::{
         enc::{AECipherSigZK, CPACipher},
+        hash::FieldHash,
         rr::RRVerifier,
     },
     generic::{
+        callbacks::{release_tag, release_time},
         object::{Com, ComVar, Nul},
         user::UserData,
     },
@@ -429,6 +431,33 @@ pub trait PublicUserBul<F: PrimeField + Absorb, U: UserData<F>> {
 ///         self.index += 1;
 ///         Ok(())
 ///      }
+///
+///      fn append_multi_value<
+///         PubArgs: ToConstraintField<Fr> + Clone,
+///         Snark: SNARK<Fr>,
+///         const NUMUSERS: usize,
+///         const NUMCBS: usize,
+///      >(
+///         &mut self,
+///         objects: [Com<Fr>; NUMUSERS],
+///         old_nuls: [Nul<Fr>; NUMUSERS],
+///         _cb_com_lists: [[Com<Fr>; NUMCBS]; NUMUSERS],
+///         _args: PubArgs,
+///         _proof: Snark::Proof,
+///         _memb_data: Option<[Self::MembershipPub; NUMUSERS]>,
+///         _verif_key: &Snark::VerifyingKey,
+///      ) -> Result<(), Self::Error> {
+///         // Check there is room for every object before appending any.
+///         if self.index + NUMUSERS > self.list.len() {
+///             return Err(());
+///         }
+///         for (object, old_nul) in objects.into_iter().zip(old_nuls) {
+///             self.list[self.index] = object;
+///             self.nuls.push(old_nul);
+///             self.index += 1;
+///         }
+///         Ok(())
+///      }
 /// }
 ///
 /// ```
@@ -530,6 +559,7 @@ pub trait UserBul<F: PrimeField + Absorb, U: UserData<F>>: PublicUserBul<F, U> {
     ///
     ///# Example
     /// ```rust
+    /// # use std::sync::Arc;
     /// # use zk_callbacks::zk_object;
     /// # use zk_callbacks::generic::user::User;
     /// # use rand::thread_rng;
@@ -603,13 +633,16 @@ pub trait UserBul<F: PrimeField + Absorb, U: UserData<F>>: PublicUserBul<F, U> {
     ///         method_id: Id::from(0),
     ///         expirable: false,
     ///         expiration: Time::from(10),
-    ///         method: callback,
-    ///         predicate: enforce_callback
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
     ///     };
     ///
     ///     let int = Interaction {
-    ///         meth: (method, predicate),
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
     ///         callbacks: [cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
     ///     };
     ///
     ///     let mut rng = thread_rng();
@@ -676,6 +709,132 @@ pub trait UserBul<F: PrimeField + Absorb, U: UserData<F>>: PublicUserBul<F, U> {
 
         Ok(())
     }
+
+    /// Append the new user objects of a multi-user interaction into the bulletin.
+    ///
+    /// This function should not do any checking. It must be atomic: if appending any of the
+    /// objects would fail, then none of them should be appended. In particular, this should not
+    /// simply call [`UserBul::append_value`] for each object in turn, unless appending can not
+    /// fail.
+    ///
+    /// See [`UserBul::append_value`] for a description of the arguments, which are given for each
+    /// user in order.
+    #[allow(clippy::too_many_arguments)]
+    fn append_multi_value<
+        PubArgs: ToConstraintField<F> + Clone,
+        Snark: SNARK<F>,
+        const NUMUSERS: usize,
+        const NUMCBS: usize,
+    >(
+        &mut self,
+        objects: [Com<F>; NUMUSERS],
+        old_nuls: [Nul<F>; NUMUSERS],
+        cb_com_lists: [[Com<F>; NUMCBS]; NUMUSERS],
+        args: PubArgs,
+        proof: Snark::Proof,
+        memb_data: Option<[Self::MembershipPub; NUMUSERS]>,
+        verif_key: &Snark::VerifyingKey,
+    ) -> Result<(), Self::Error>;
+
+    /// Given the new user objects and proof of a multi-user interaction, this verifies the proof
+    /// that the new user objects respect the interaction.
+    ///
+    /// This checks that none of the nullifiers have been previously seen, and that they are
+    /// pairwise distinct, so that a user can not take part twice in the same interaction. The
+    /// proof is verified with respect to the `verif_key` generated by
+    /// [`MultiInteraction::generate_keys`](super::interaction::MultiInteraction::generate_keys).
+    ///
+    /// See [`UserBul::verify_interaction`] for a description of the arguments, which are given
+    /// for each user in order.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_multi_interaction<
+        PubArgs: ToConstraintField<F>,
+        Snark: SNARK<F>,
+        const NUMUSERS: usize,
+        const NUMCBS: usize,
+    >(
+        &self,
+        objects: [Com<F>; NUMUSERS],
+        old_nuls: [Nul<F>; NUMUSERS],
+        args: PubArgs,
+        cb_com_lists: [[Com<F>; NUMCBS]; NUMUSERS],
+        proof: Snark::Proof,
+        memb_data: Option<[Self::MembershipPub; NUMUSERS]>,
+        verif_key: &Snark::VerifyingKey,
+    ) -> bool {
+        for (i, nul) in old_nuls.iter().enumerate() {
+            if !self.has_never_received_nul(nul) || old_nuls[..i].contains(nul) {
+                return false;
+            }
+        }
+
+        let mut pub_inputs = objects.to_vec();
+        pub_inputs.extend(old_nuls);
+        pub_inputs.extend::<Vec<F>>(args.to_field_elements().unwrap());
+        for cb_com_list in cb_com_lists {
+            pub_inputs.extend(cb_com_list);
+        }
+        if let Some(data) = memb_data {
+            for a in data {
+                pub_inputs.extend::<Vec<F>>(a.to_field_elements().unwrap());
+            }
+        }
+
+        let out = Snark::verify(verif_key, &pub_inputs, &proof);
+
+        out.unwrap_or(false)
+    }
+
+    /// Verifies a multi-user interaction and appends all of the new objects to the bulletin.
+    ///
+    /// All nullifiers are checked before any object is appended, so either the whole interaction
+    /// is accepted, or the bulletin is left unchanged (provided
+    /// [`UserBul::append_multi_value`] appends atomically).
+    ///
+    /// See [`User::interact_multi`](super::user::User::interact_multi) for an example.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_multi_interact_and_append<
+        PubArgs: ToConstraintField<F> + Clone,
+        Snark: SNARK<F>,
+        const NUMUSERS: usize,
+        const NUMCBS: usize,
+    >(
+        &mut self,
+        objects: [Com<F>; NUMUSERS],
+        old_nuls: [Nul<F>; NUMUSERS],
+        args: PubArgs,
+        cb_com_lists: [[Com<F>; NUMCBS]; NUMUSERS],
+        proof: Snark::Proof,
+        memb_data: Option<[Self::MembershipPub; NUMUSERS]>,
+        verif_key: &Snark::VerifyingKey,
+    ) -> Result<(), BulError<Self::Error>> {
+        let out = self.verify_multi_interaction::<PubArgs, Snark, NUMUSERS, NUMCBS>(
+            objects,
+            old_nuls,
+            args.clone(),
+            cb_com_lists,
+            proof.clone(),
+            memb_data.clone(),
+            verif_key,
+        );
+
+        if !out {
+            return Err(BulError::VerifyError);
+        }
+
+        self.append_multi_value::<PubArgs, Snark, NUMUSERS, NUMCBS>(
+            objects,
+            old_nuls,
+            cb_com_lists,
+            args,
+            proof,
+            memb_data,
+            verif_key,
+        )
+        .map_err(BulError::AppendError)?;
+
+        Ok(())
+    }
 }
 
 /// Methods which users can perform by viewing a public callback bulletin.
@@ -1002,6 +1161,26 @@ pub trait PublicCallbackBul<F: PrimeField, CBArgs: Clone, Crypto: AECipherSigZK<
         Self::NonMembershipWitness,
     );
 
+    /// Get membership data for a ticket whose membership is never checked.
+    ///
+    /// When scanning, tickets which expired before the user's last full scan are dropped without
+    /// checking the bulletin (see [`scan_method`](super::scan::scan_method)). The public data must
+    /// still be valid, as it is a public input to the scan, but the witnesses may be anything.
+    ///
+    /// By default this calls [`get_membership_data`](PublicCallbackBul::get_membership_data);
+    /// bulletins may override it to avoid looking up the ticket.
+    fn get_membership_data_unchecked(
+        &self,
+        tik: Crypto::SigPK,
+    ) -> (
+        Self::MembershipPub,
+        Self::MembershipWitness,
+        Self::NonMembershipPub,
+        Self::NonMembershipWitness,
+    ) {
+        self.get_membership_data(tik)
+    }
+
     /// Prove membership of a (ticket, arguments, time) tuple in the callback bulletin in-circuit.
     ///
     /// Given a ticket and membership witness and public data, return `true` if the ticket data is in
@@ -1036,13 +1215,48 @@ pub trait PublicCallbackBul<F: PrimeField, CBArgs: Clone, Crypto: AECipherSigZK<
         ),
         ewitness: (Self::MembershipWitnessVar, Self::NonMembershipWitnessVar),
         epub: (Self::MembershipPubVar, Self::NonMembershipPubVar),
+    ) -> Result<Boolean<F>, SynthesisError> {
+        Self::conditional_enforce_memb_nmemb(tikvar, ewitness, epub, &Boolean::TRUE)
+    }
+
+    /// Enforces a ticket is either a member of the bulletin or not if `should_enforce` is true.
+    /// Returns true if the ticket is a member, and false if not.
+    ///
+    /// If `should_enforce` is false, nothing is enforced and the result is meaningless.
+    fn conditional_enforce_memb_nmemb(
+        tikvar: (
+            Crypto::SigPKV,
+            <Crypto::EncKey as CPACipher<F>>::CV,
+            TimeVar<F>,
+        ),
+        ewitness: (Self::MembershipWitnessVar, Self::NonMembershipWitnessVar),
+        epub: (Self::MembershipPubVar, Self::NonMembershipPubVar),
+        should_enforce: &Boolean<F>,
     ) -> Result<Boolean<F>, SynthesisError> {
         let b2 = Self::enforce_nonmembership_of(tikvar.0.clone(), ewitness.1, epub.1)?;
         let b1 = Self::enforce_membership_of(tikvar, ewitness.0, epub.0)?;
         let o = b1.is_neq(&b2)?;
-        o.enforce_equal(&Boolean::TRUE)?;
+        o.conditional_enforce_equal(&Boolean::TRUE, should_enforce)?;
         Ok(b1)
     }
+
+    /// The number of calls made on a multi-use ticket, given its tickets in order.
+    ///
+    /// Calls are made on the tickets in order, so the tickets in the bulletin must be a prefix of
+    /// `tiks`, and this is the length of that prefix. Returns `None` if a ticket was called
+    /// before one of the tickets preceding it. See
+    /// [`MultiUseTicket`](super::callbacks::MultiUseTicket).
+    fn num_calls(&self, tiks: &[Crypto::SigPK]) -> Option<usize> {
+        let called: Vec<bool> = tiks
+            .iter()
+            .map(|tik| self.verify_in(tik.clone()).is_some())
+            .collect();
+        let n = called.iter().take_while(|c| **c).count();
+        match called[n..].contains(&true) {
+            true => None,
+            false => Some(n),
+        }
+    }
 }
 
 /// A callback bulletin.
@@ -1256,6 +1470,72 @@ pub trait CallbackBul<F: PrimeField, CBArgs: Clone, Crypto: AECipherSigZK<F, CBA
 
         Ok(())
     }
+
+    /// Verify a released ticket, and then append it at the [`release_time`].
+    ///
+    /// The signature must be on the [`release_tag`] of the ticket, as produced by
+    /// [`ServiceProvider::release`](`super::service::ServiceProvider::release`).
+    fn verify_release_and_append<H: FieldHash<F>>(
+        &mut self,
+        tik: Crypto::SigPK,
+        signature: Crypto::Sig,
+    ) -> Result<(), BulError<Self::Error>> {
+        let enc_args = Crypto::release_ct(release_tag::<F, H>(&tik));
+        self.verify_call_and_append(tik, enc_args, signature, release_time())
+    }
+
+    /// Verify a call on a multi-use ticket with tickets `tiks`, and then append it.
+    ///
+    /// This is the same as [`verify_call_and_append`](`CallbackBul::verify_call_and_append`),
+    /// except that `tik` must also be the next unused ticket of `tiks` (see
+    /// [`PublicCallbackBul::num_calls`]), so that the tickets of a multi-use ticket are called in
+    /// order.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use ark_bn254::Fr;
+    /// # use rand::thread_rng;
+    /// # use zk_callbacks::generic::bulletin::{CallbackBul, PublicCallbackBul};
+    /// # use zk_callbacks::generic::object::Time;
+    /// # use zk_callbacks::impls::centralized::crypto::{FakeSigPubkey, NoSigOTP};
+    /// # use zk_callbacks::impls::centralized::ds::sigstore::GRSchnorrCallbackStore;
+    /// type CBul = GRSchnorrCallbackStore<Fr>;
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut cbul = CBul::new(&mut rng);
+    ///
+    /// // A ticket which may be called three times.
+    /// let tiks: Vec<FakeSigPubkey<Fr>> = (1..4).map(|i| FakeSigPubkey::new(Fr::from(i))).collect();
+    ///
+    /// // The third ticket may not be called before the second.
+    /// assert!(<CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_call_next_and_append(&mut cbul, &tiks, tiks[2].clone(), Fr::from(5), (), Time::from(0)).is_err());
+    ///
+    /// <CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_call_next_and_append(&mut cbul, &tiks, tiks[0].clone(), Fr::from(5), (), Time::from(0)).unwrap();
+    /// assert!(<CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_call_next_and_append(&mut cbul, &tiks, tiks[2].clone(), Fr::from(5), (), Time::from(1)).is_err());
+    /// <CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_call_next_and_append(&mut cbul, &tiks, tiks[1].clone(), Fr::from(3), (), Time::from(1)).unwrap();
+    /// assert_eq!(<CBul as PublicCallbackBul<Fr, Fr, NoSigOTP<Fr>>>::num_calls(&cbul, &tiks), Some(2));
+    ///
+    /// // A bulletin where the third ticket was called before the second is detected.
+    /// let mut skipped = CBul::new(&mut rng);
+    /// <CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_call_and_append(&mut skipped, tiks[0].clone(), Fr::from(5), (), Time::from(0)).unwrap();
+    /// <CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_call_and_append(&mut skipped, tiks[2].clone(), Fr::from(5), (), Time::from(1)).unwrap();
+    /// assert_eq!(<CBul as PublicCallbackBul<Fr, Fr, NoSigOTP<Fr>>>::num_calls(&skipped, &tiks), None);
+    /// ```
+    fn verify_call_next_and_append(
+        &mut self,
+        tiks: &[Crypto::SigPK],
+        tik: Crypto::SigPK,
+        enc_args: Crypto::Ct,
+        signature: Crypto::Sig,
+        time: Time<F>,
+    ) -> Result<(), BulError<Self::Error>> {
+        let next = self.num_calls(tiks).and_then(|n| tiks.get(n));
+        if next != Some(&tik) {
+            return Err(BulError::VerifyError);
+        }
+
+        self.verify_call_and_append(tik, enc_args, signature, time)
+    }
 }
 
 /// A bulletin where a user can also join.
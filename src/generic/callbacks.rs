@@ -1,11 +1,11 @@
 use crate::{
     crypto::{
         enc::{AECipherSigZK, CPACipher},
-        hash::FieldHash,
+        hash::{FieldHash, HashDomain, hash_with_domain, hash_with_domain_in_zk},
         rr::RRVerifier,
     },
     generic::{
-        interaction::Interaction,
+        interaction::Callback,
         object::{
             CBHash, CBHashVar, Com, ComRand, ComRandVar, ComVar, Id, IdVar, Ser, SerVar, Time,
             TimeVar,
@@ -18,7 +18,10 @@ use ark_ff::{PrimeField, ToConstraintField};
 use ark_r1cs_std::{
     alloc::{AllocVar, AllocationMode},
     boolean::Boolean,
+    cmp::CmpGadget,
     convert::ToConstraintFieldGadget,
+    fields::fp::FpVar,
+    uint::UInt,
 };
 use ark_relations::{
     ns,
@@ -41,8 +44,9 @@ pub struct CallbackTicket<F: PrimeField + Absorb, Args: Clone, Crypto: AECipherS
     /// sign arguments, so users are ensured that arguments are authentic.
     pub tik: Crypto::SigPK,
     /// The ID for the callback. This is what determines what function is called. If the function
-    /// must remain private as well, then the function ID must be passed in as an argument in zero
-    /// knowledge. This determines what function can be called by this ticket.
+    /// must remain private as well, the ID may be blinded before handing the ticket to the service
+    /// (see [`Interaction::hiding_ids`](super::interaction::Interaction::hiding_ids)). This
+    /// determines what function can be called by this ticket.
     pub cb_method_id: Id<F>,
     /// Does this ticket expire.
     pub expirable: bool,
@@ -83,6 +87,15 @@ impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
         ]
         .concat()
     }
+
+    /// Whether the ticket expires before some time.
+    ///
+    /// Once a user has completed a scan which started at some time, any ticket which expired
+    /// before that time and remains in the user's callback list was never called in time, so it
+    /// may be dropped on the next scan without checking the bulletin.
+    pub fn expired_before(&self, time: Time<F>) -> bool {
+        self.expirable && self.expiration < time
+    }
 }
 
 impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
@@ -99,6 +112,15 @@ impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
         ]
         .concat())
     }
+
+    /// Whether the ticket expires before some time, in-circuit.
+    ///
+    /// See [`CallbackTicket::expired_before`].
+    pub fn expired_before(&self, time: &TimeVar<F>) -> Result<Boolean<F>, SynthesisError> {
+        let expiration = <UInt<64, u64, F>>::from_fp(&self.expiration)?.0;
+        let time = <UInt<64, u64, F>>::from_fp(time)?.0;
+        Ok(self.expirable.clone() & expiration.is_lt(&time)?)
+    }
 }
 
 impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
@@ -178,13 +200,13 @@ impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
 {
     /// Produce a commitment to the callback entry.
     ///
-    /// Uses the hash `H` to produce a commitment. Note that the nonce is already stored within the
-    /// callback entry.
+    /// Uses the hash `H` to produce a commitment in the [`HashDomain::CallbackCom`] domain. Note
+    /// that the nonce is already stored within the callback entry.
     pub fn commit<H: FieldHash<F>>(&self) -> Com<F> {
         let ser_fields = self.cb_entry.serialize();
         let com_rand_ser = self.com_rand.to_field_elements().unwrap();
         let full_dat = [ser_fields.as_slice(), com_rand_ser.as_slice()].concat();
-        H::hash(&full_dat)
+        hash_with_domain::<F, H>(HashDomain::CallbackCom, &full_dat)
     }
 
     /// Produce a commitment of `cb_var` in-circuit.
@@ -195,8 +217,70 @@ impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
         let com_rand_ser = cb_var.com_rand.to_constraint_field()?;
 
         let full_dat = [ser_fields.as_slice(), com_rand_ser.as_slice()].concat();
-        H::hash_in_zk(&full_dat)
+        hash_with_domain_in_zk::<F, H>(HashDomain::CallbackCom, &full_dat)
+    }
+
+    /// Blind the method id of the callback, so that it may be handed to a service without
+    /// revealing which method the ticket calls.
+    ///
+    /// The method id is replaced with its hash (in the [`HashDomain::CallbackMethodId`] domain)
+    /// along with the commitment randomness, and the commitment randomness is cleared. A service
+    /// may then check the blinded callback against its commitment as usual. See
+    /// [`Interaction::hiding_ids`](super::interaction::Interaction::hiding_ids).
+    pub fn blind_method_id<H: FieldHash<F>>(&self) -> Self {
+        let mut blinded = self.clone();
+        blinded.cb_entry.cb_method_id = hash_with_domain::<F, H>(
+            HashDomain::CallbackMethodId,
+            &[self.cb_entry.cb_method_id, self.com_rand],
+        );
+        blinded.com_rand = F::zero();
+        blinded
     }
+
+    /// Blind the method id of `cb_var` in-circuit.
+    ///
+    /// See [`CallbackCom::blind_method_id`].
+    pub fn blind_method_id_in_zk<H: FieldHash<F>>(
+        cb_var: CallbackComVar<F, Args, Crypto>,
+    ) -> Result<CallbackComVar<F, Args, Crypto>, SynthesisError> {
+        let mut blinded = cb_var.clone();
+        blinded.cb_entry.cb_method_id = hash_with_domain_in_zk::<F, H>(
+            HashDomain::CallbackMethodId,
+            &[cb_var.cb_entry.cb_method_id, cb_var.com_rand],
+        )?;
+        blinded.com_rand = ComRandVar::Constant(F::zero());
+        Ok(blinded)
+    }
+}
+
+/// The callbacks handed to a service, along with their commitments, given the callbacks issued to
+/// a user.
+///
+/// Callbacks past `num_issued` are inert, and have the zero commitment. If `hide_ids` is set, the
+/// callbacks handed to the service have their method ids blinded.
+pub(crate) fn callbacks_for_service<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    CBArgs: Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    const NUMCBS: usize,
+>(
+    cb_tik_list: &CBList<F, Crypto, CBArgs, NUMCBS>,
+    num_issued: usize,
+    hide_ids: bool,
+) -> (CBList<F, Crypto, CBArgs, NUMCBS>, [Com<F>; NUMCBS]) {
+    let service_list: CBList<F, Crypto, CBArgs, NUMCBS> =
+        cb_tik_list.clone().map(|(cb, rand)| match hide_ids {
+            true => (cb.blind_method_id::<H>(), rand),
+            false => (cb, rand),
+        });
+
+    let coms = core::array::from_fn(|i| match i < num_issued {
+        true => service_list[i].0.commit::<H>(),
+        false => F::zero(),
+    });
+
+    (service_list, coms)
 }
 
 impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
@@ -226,6 +310,92 @@ impl<Args: Clone, Crypto: AECipherSigZK<F, Args>, F: PrimeField + Absorb>
     }
 }
 
+/// A callback ticket which may be called multiple times.
+///
+/// A ticket with `k` uses is issued as `k` consecutive single-use tickets for the same callback
+/// (see [`Callback::uses`](super::interaction::Callback::uses)). The `j`-th call is made on the
+/// `j`-th ticket, with its own arguments, so a ticket can never be called more than `k` times.
+/// The number of calls made so far is the number of tickets in the callback bulletin (see
+/// [`PublicCallbackBul::num_calls`](super::bulletin::PublicCallbackBul::num_calls)). Calls posted
+/// with [`CallbackBul::verify_call_next_and_append`](super::bulletin::CallbackBul::verify_call_next_and_append)
+/// are made in order, so scanning the consecutive tickets applies them in order.
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiUseTicket<F: PrimeField + Absorb, Args: Clone, Crypto: AECipherSigZK<F, Args>> {
+    /// The single-use tickets, in the order they are called, along with the randomness used to
+    /// rerandomize the service key for each ticket.
+    pub tickets: Vec<(CallbackCom<F, Args, Crypto>, Crypto::Rand)>,
+}
+
+impl<F: PrimeField + Absorb, Args: Clone, Crypto: AECipherSigZK<F, Args>>
+    MultiUseTicket<F, Args, Crypto>
+{
+    /// Group consecutive issued tickets into a multi-use ticket.
+    ///
+    /// For example, the tickets issued for a callback repeated with
+    /// [`Callback::uses`](super::interaction::Callback::uses), as found in
+    /// [`ExecutedMethod::cb_tik_list`](super::user::ExecutedMethod::cb_tik_list).
+    pub fn new(tickets: &[(CallbackCom<F, Args, Crypto>, Crypto::Rand)]) -> Self {
+        Self {
+            tickets: tickets.to_vec(),
+        }
+    }
+
+    /// The maximum number of times the ticket may be called.
+    pub fn max_calls(&self) -> usize {
+        self.tickets.len()
+    }
+
+    /// The underlying random tickets, in the order they are called.
+    pub fn get_tickets(&self) -> Vec<Crypto::SigPK> {
+        self.tickets.iter().map(|(cb, _)| cb.get_ticket()).collect()
+    }
+}
+
+/// Whether a callback commitment is that of an inert ticket.
+///
+/// Inert tickets pad an interaction which issues fewer callbacks than it may (see
+/// [`User::interact_padded`](super::user::User::interact_padded)). They have the zero commitment,
+/// and are never added to the user's callback list, so they can never be called.
+pub fn is_inert_com<F: PrimeField>(com: &Com<F>) -> bool {
+    com.is_zero()
+}
+
+/// The time at which a released ticket is posted to the callback bulletin.
+///
+/// A service may release a ticket it will never call (see
+/// [`ServiceProvider::release`](super::service::ServiceProvider::release)). A released ticket is
+/// posted as if it were called at the end of time, and scanning treats it as a no-op, but still
+/// removes it from the user's callback list.
+///
+/// Note that the post time is not signed, so scanning tells released tickets apart by their signed
+/// [`release_tag`] instead (see [`AECipherSigZK::is_release`]).
+pub fn release_time<F: PrimeField>() -> Time<F> {
+    F::from(u64::MAX)
+}
+
+/// Whether a callback posted at `time` was released rather than called.
+pub fn is_release_time<F: PrimeField>(time: &Time<F>) -> bool {
+    *time == release_time()
+}
+
+/// The tag a service signs to release the ticket `tik`.
+///
+/// This is the hash of the ticket in the [`HashDomain::Release`] domain. The service signs
+/// [`AECipherSigZK::release_ct`] of the tag rather than encrypted arguments, so a release can not
+/// be replayed as a call on the ticket, nor a call as a release.
+pub fn release_tag<F: PrimeField, H: FieldHash<F>>(tik: &impl ToConstraintField<F>) -> F {
+    hash_with_domain::<F, H>(HashDomain::Release, &tik.to_field_elements().unwrap())
+}
+
+/// The release tag of `tik` in-circuit.
+///
+/// See [`release_tag`].
+pub fn release_tag_in_zk<F: PrimeField, H: FieldHash<F>>(
+    tik: &impl ToConstraintFieldGadget<F>,
+) -> Result<FpVar<F>, SynthesisError> {
+    hash_with_domain_in_zk::<F, H>(HashDomain::Release, &tik.to_constraint_field()?)
+}
+
 type CBList<F, Crypto, Args, const NUMCBS: usize> = [(
     CallbackCom<F, Args, Crypto>,
     <Crypto as AECipherSigZK<F, Args>>::Rand,
@@ -234,30 +404,15 @@ type CBList<F, Crypto, Args, const NUMCBS: usize> = [(
 pub(crate) fn create_defaults<
     F: PrimeField + Absorb,
     U: UserData<F>,
-    PubArgs: Clone,
-    PubArgsVar: AllocVar<PubArgs, F>,
-    PrivArgs: Clone,
-    PrivArgsVar: AllocVar<PrivArgs, F>,
     CBArgs: Clone,
     CBArgsVar: AllocVar<CBArgs, F>,
     Crypto: AECipherSigZK<F, CBArgs>,
     const NUMCBS: usize,
 >(
-    interaction: Interaction<
-        F,
-        U,
-        PubArgs,
-        PubArgsVar,
-        PrivArgs,
-        PrivArgsVar,
-        CBArgs,
-        CBArgsVar,
-        NUMCBS,
-    >,
+    callbacks: &[Callback<F, U, CBArgs, CBArgsVar>],
     pub_cur_time: Time<F>,
 ) -> [CallbackCom<F, CBArgs, Crypto>; NUMCBS] {
-    interaction
-        .callbacks
+    callbacks
         .iter()
         .map(|cb| {
             let ticket_value = Crypto::SigPK::default();
@@ -285,39 +440,40 @@ pub(crate) fn create_defaults<
 pub(crate) fn create_cbs_from_interaction<
     F: PrimeField + Absorb,
     U: UserData<F>,
-    PubArgs: Clone + std::fmt::Debug,
-    PubArgsVar: AllocVar<PubArgs, F>,
-    PrivArgs: Clone + std::fmt::Debug,
-    PrivArgsVar: AllocVar<PrivArgs, F>,
     CBArgs: Clone + std::fmt::Debug,
     CBArgsVar: AllocVar<CBArgs, F>,
     Crypto: AECipherSigZK<F, CBArgs>,
     const NUMCBS: usize,
 >(
     rng: &mut (impl CryptoRng + RngCore),
-    interaction: Interaction<
-        F,
-        U,
-        PubArgs,
-        PubArgsVar,
-        PrivArgs,
-        PrivArgsVar,
-        CBArgs,
-        CBArgsVar,
-        NUMCBS,
-    >,
+    callbacks: &[Callback<F, U, CBArgs, CBArgsVar>],
     rpk_identities: [Crypto::SigPK; NUMCBS],
     cur_time: Time<F>,
+    num_issued: usize,
 ) -> CBList<F, Crypto, CBArgs, NUMCBS>
 where
     Standard: Distribution<F>,
 {
-    interaction
-        .callbacks
+    callbacks
         .iter()
         .enumerate()
         .map(|(i, cb)| {
             let (rand, ticket_value) = rpk_identities[i].rerand(rng);
+
+            if i >= num_issued {
+                let inert = CallbackCom {
+                    cb_entry: CallbackTicket {
+                        tik: Crypto::SigPK::default(),
+                        cb_method_id: F::zero(),
+                        expirable: false,
+                        expiration: F::zero(),
+                        enc_key: Crypto::EncKey::default(),
+                    },
+                    com_rand: F::zero(),
+                };
+                return (inert, rand);
+            }
+
             let enc_key = Crypto::EncKey::keygen(rng);
             let com_rand = rng.r#gen::<F>();
 
@@ -350,7 +506,10 @@ pub(crate) fn add_ticket_to_hc<
     ticket: CallbackTicket<F, Args, Crypto>,
 ) -> CBHash<F> {
     let serialized_ticket = ticket.serialize();
-    H::hash(&[&[hash_chain], serialized_ticket.as_slice()].concat())
+    hash_with_domain::<F, H>(
+        HashDomain::CallbackHashChain,
+        &[&[hash_chain], serialized_ticket.as_slice()].concat(),
+    )
 }
 
 pub(crate) fn add_ticket_to_hc_zk<
@@ -367,7 +526,7 @@ pub(crate) fn add_ticket_to_hc_zk<
 
     let full_dat = [ser_hc.as_slice(), ser_ticket.as_slice()].concat();
 
-    *hash_chain = H::hash_in_zk(&full_dat)?;
+    *hash_chain = hash_with_domain_in_zk::<F, H>(HashDomain::CallbackHashChain, &full_dat)?;
 
     Ok(())
 }
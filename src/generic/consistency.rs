@@ -0,0 +1,410 @@
+use crate::generic::{
+    interaction::{Callback, Interaction},
+    object::Id,
+    user::{User, UserData, UserVar},
+};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{R1CSVar, alloc::AllocVar, boolean::Boolean, eq::EqGadget};
+use ark_relations::{
+    ns,
+    r1cs::{ConstraintSystem, ConstraintSystemRef, SynthesisError},
+};
+use rand::{
+    CryptoRng, RngCore,
+    distributions::{Distribution, Standard},
+};
+#[cfg(feature = "trace")]
+use {
+    ark_relations::r1cs::{ConstraintLayer, TracingMode},
+    tracing_subscriber::layer::SubscriberExt,
+};
+
+/// The way in which a native method and its in-circuit counterpart disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mismatch<F: PrimeField> {
+    /// The circuit could not be synthesized.
+    Synthesis(SynthesisError),
+    /// The predicate of an interaction rejected the output of the native method.
+    PredicateRejected,
+    /// The constraint system is unsatisfied, at the given constraint path (only known with the
+    /// `trace` feature).
+    Unsatisfied(Option<String>),
+    /// The serialized user has a different number of elements natively and in-circuit.
+    LengthMismatch {
+        /// The number of elements natively.
+        native: usize,
+        /// The number of elements in-circuit.
+        in_circuit: usize,
+    },
+    /// An element of the serialized user differs natively and in-circuit.
+    ///
+    /// The index is into the serialized user data, followed by the serialized
+    /// [`ZKFields`](crate::generic::object::ZKFields).
+    ValueMismatch {
+        /// The index of the element.
+        index: usize,
+        /// The native value.
+        native: F,
+        /// The in-circuit value.
+        in_circuit: F,
+    },
+}
+
+impl<F: PrimeField> From<SynthesisError> for Mismatch<F> {
+    fn from(e: SynthesisError) -> Self {
+        Mismatch::Synthesis(e)
+    }
+}
+
+impl<F: PrimeField> std::fmt::Display for Mismatch<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mismatch::Synthesis(e) => write!(f, "circuit synthesis failed: {e}"),
+            Mismatch::PredicateRejected => write!(f, "predicate rejected the native output"),
+            Mismatch::Unsatisfied(name) => write!(
+                f,
+                "constraint {} is unsatisfied",
+                name.as_deref().unwrap_or("<unknown>")
+            ),
+            Mismatch::LengthMismatch { native, in_circuit } => write!(
+                f,
+                "user serializes to {native} elements natively, but {in_circuit} in-circuit"
+            ),
+            Mismatch::ValueMismatch {
+                index,
+                native,
+                in_circuit,
+            } => write!(
+                f,
+                "element {index} of the user is {native} natively, but {in_circuit} in-circuit"
+            ),
+        }
+    }
+}
+
+/// An input on which a native method and its in-circuit counterpart disagree.
+#[derive(Clone, Debug)]
+pub struct Counterexample<F: PrimeField + Absorb, U: UserData<F>, Args> {
+    /// The trial on which the disagreement was found.
+    pub trial: usize,
+    /// For callbacks, the method id of the disagreeing callback.
+    pub callback: Option<Id<F>>,
+    /// The user the method was called on.
+    pub old: User<F, U>,
+    /// The output of the native method.
+    pub new: User<F, U>,
+    /// The arguments the method was called with.
+    pub args: Args,
+    /// How the native method and circuit disagree.
+    pub mismatch: Mismatch<F>,
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>, Args: std::fmt::Debug> std::fmt::Display
+    for Counterexample<F, U, Args>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "trial {}", self.trial)?;
+        if let Some(id) = self.callback {
+            write!(f, ", callback {id}")?;
+        }
+        write!(
+            f,
+            ": {} (old: {:?}, new: {:?}, args: {:?})",
+            self.mismatch, self.old.data, self.new.data, self.args
+        )
+    }
+}
+
+/// Runs a check on a fresh constraint system, tracing namespaces so that unsatisfied constraints
+/// are reported by their path.
+#[cfg(feature = "trace")]
+fn traced<F: PrimeField>(
+    check: impl FnOnce(ConstraintSystemRef<F>) -> Result<(), Mismatch<F>>,
+) -> Result<(), Mismatch<F>> {
+    let mut layer = ConstraintLayer::default();
+    layer.mode = TracingMode::OnlyConstraints;
+    let subscriber = tracing_subscriber::Registry::default().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        let cs = ConstraintSystem::<F>::new_ref();
+        check(cs.clone())?;
+        if !cs.is_satisfied()? {
+            return Err(Mismatch::Unsatisfied(cs.which_is_unsatisfied()?));
+        }
+        Ok(())
+    })
+}
+
+/// Runs a check on a fresh constraint system. Without the `trace` feature, unsatisfied
+/// constraints are not named.
+#[cfg(not(feature = "trace"))]
+fn traced<F: PrimeField>(
+    check: impl FnOnce(ConstraintSystemRef<F>) -> Result<(), Mismatch<F>>,
+) -> Result<(), Mismatch<F>> {
+    let cs = ConstraintSystem::<F>::new_ref();
+    check(cs.clone())?;
+    if !crate::generic::user::is_satisfied_untraced(&cs)? {
+        return Err(Mismatch::Unsatisfied(None));
+    }
+    Ok(())
+}
+
+/// Checks that an in-circuit user has the same serialization as a native user.
+fn compare_user<F: PrimeField + Absorb, U: UserData<F>>(
+    var: &UserVar<F, U>,
+    user: &User<F, U>,
+) -> Result<(), Mismatch<F>> {
+    let native = [user.data.serialize_elements(), user.zk_fields.serialize()].concat();
+    let in_circuit = [
+        U::serialize_in_zk(var.data.clone())?,
+        var.zk_fields.serialize()?,
+    ]
+    .concat();
+
+    if native.len() != in_circuit.len() {
+        return Err(Mismatch::LengthMismatch {
+            native: native.len(),
+            in_circuit: in_circuit.len(),
+        });
+    }
+
+    for (index, (n, v)) in native.iter().zip(&in_circuit).enumerate() {
+        let v = v.value()?;
+        if *n != v {
+            return Err(Mismatch::ValueMismatch {
+                index,
+                native: *n,
+                in_circuit: v,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check that the native method of an interaction agrees with its predicate.
+///
+/// For each of `trials` trials, this generates user data and arguments with `generate`, creates a
+/// fresh user, and runs the native method. It then allocates the old and new users in a
+/// constraint system, and checks that
+///* both users have the same values in-circuit as natively, and
+///* the predicate (along with any [`unchanged`](Interaction::unchanged) condition) accepts, and
+///  the constraint system is satisfied.
+///
+/// The first failing input is returned as a [`Counterexample`].
+///
+/// # Example
+/// ```rust
+/// # use zk_callbacks::zk_object;
+/// # use zk_callbacks::generic::consistency::{Mismatch, check_interaction};
+/// # use zk_callbacks::generic::interaction::Interaction;
+/// # use zk_callbacks::generic::user::{User, UserVar};
+/// # use ark_bn254::Fr;
+/// # use ark_r1cs_std::eq::EqGadget;
+/// # use ark_r1cs_std::fields::fp::FpVar;
+/// # use ark_ff::UniformRand;
+/// # use rand::thread_rng;
+/// #[zk_object(Fr)]
+/// #[derive(Default)]
+/// struct Data {
+///     pub karma: Fr,
+/// }
+///
+/// type Int = Interaction<Fr, Data, Fr, FpVar<Fr>, (), (), Fr, FpVar<Fr>, 0>;
+///
+/// // The native method adds the argument, but the predicate expects it to be doubled.
+/// let int: Int = Interaction::new(
+///     |old: &User<Fr, Data>, x: Fr, _priv: ()| {
+///         let mut new = old.clone();
+///         new.data.karma += x;
+///         new
+///     },
+///     |old: &UserVar<Fr, Data>, new: &UserVar<Fr, Data>, x: FpVar<Fr>, _priv: ()| {
+///         (&old.data.karma + &x + &x).is_eq(&new.data.karma)
+///     },
+///     [],
+/// );
+///
+/// let mut rng = thread_rng();
+/// let cex = check_interaction(
+///     &int,
+///     &mut rng,
+///     |rng| (Data { karma: Fr::rand(rng) }, Fr::rand(rng), ()),
+///     10,
+/// )
+/// .unwrap_err();
+///
+/// assert_eq!(cex.trial, 0);
+/// assert_eq!(cex.mismatch, Mismatch::PredicateRejected);
+/// ```
+pub fn check_interaction<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    R: CryptoRng + RngCore,
+    const NUMCBS: usize,
+>(
+    int: &Interaction<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, NUMCBS>,
+    rng: &mut R,
+    mut generate: impl FnMut(&mut R) -> (U, PubArgs, PrivArgs),
+    trials: usize,
+) -> Result<(), Counterexample<F, U, (PubArgs, PrivArgs)>>
+where
+    Standard: Distribution<F>,
+{
+    for trial in 0..trials {
+        let (data, pub_args, priv_args) = generate(rng);
+        let old = User::create(data, rng);
+        let new = (int.meth.0)(&old, pub_args.clone(), priv_args.clone());
+
+        let res = traced(|cs| {
+            let old_var = UserVar::new_witness(ns!(cs, "old_user"), || Ok(old.clone()))?;
+            let new_var = UserVar::new_witness(ns!(cs, "new_user"), || Ok(new.clone()))?;
+            compare_user(&old_var, &old)?;
+            compare_user(&new_var, &new)?;
+
+            let pub_args_var =
+                PubArgsVar::new_witness(ns!(cs, "pub_args"), || Ok(pub_args.clone()))?;
+            let priv_args_var =
+                PrivArgsVar::new_witness(ns!(cs, "priv_args"), || Ok(priv_args.clone()))?;
+
+            let mut b = (int.meth.1)(&old_var, &new_var, pub_args_var, priv_args_var)?;
+            if let Some(unchanged) = &int.unchanged {
+                b &= unchanged.enforce(&old_var, &new_var)?;
+            }
+
+            if !b.value()? {
+                return Err(Mismatch::PredicateRejected);
+            }
+            b.enforce_equal(&Boolean::TRUE)?;
+            Ok(())
+        });
+
+        if let Err(mismatch) = res {
+            return Err(Counterexample {
+                trial,
+                callback: None,
+                old,
+                new,
+                args: (pub_args, priv_args),
+                mismatch,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check that the native methods of a list of callbacks agree with their in-circuit methods.
+///
+/// For each of `trials` trials, this generates user data and arguments with `generate`, and
+/// creates a fresh user. For each callback, it runs the native method, and runs the in-circuit
+/// method on the allocated user and arguments. It then checks that the constraint system is
+/// satisfied, and that the in-circuit output has the same values as the native output.
+///
+/// The first failing input is returned as a [`Counterexample`].
+///
+/// # Example
+/// ```rust
+/// # use zk_callbacks::zk_object;
+/// # use zk_callbacks::generic::consistency::{Mismatch, check_callbacks};
+/// # use zk_callbacks::generic::interaction::Callback;
+/// # use zk_callbacks::generic::user::{User, UserVar};
+/// # use zk_callbacks::generic::object::Time;
+/// # use ark_bn254::Fr;
+/// # use ark_r1cs_std::fields::fp::FpVar;
+/// # use ark_relations::r1cs::SynthesisError;
+/// # use ark_ff::UniformRand;
+/// # use rand::{rngs::ThreadRng, thread_rng};
+/// #[zk_object(Fr)]
+/// #[derive(Default)]
+/// struct Data {
+///     pub karma: Fr,
+/// }
+///
+/// let good = Callback::new(
+///     Fr::from(0),
+///     false,
+///     Time::from(0),
+///     |old: &User<Fr, Data>, x: Fr| {
+///         let mut new = old.clone();
+///         new.data.karma -= x;
+///         new
+///     },
+///     |old: &UserVar<Fr, Data>, x: FpVar<Fr>| {
+///         let mut new = old.clone();
+///         new.data.karma = &old.data.karma - x;
+///         Ok::<_, SynthesisError>(new)
+///     },
+/// );
+///
+/// // The in-circuit method forgets to subtract.
+/// let bad = Callback::new(
+///     Fr::from(1),
+///     false,
+///     Time::from(0),
+///     |old: &User<Fr, Data>, x: Fr| {
+///         let mut new = old.clone();
+///         new.data.karma -= x;
+///         new
+///     },
+///     |old: &UserVar<Fr, Data>, _x: FpVar<Fr>| Ok::<_, SynthesisError>(old.clone()),
+/// );
+///
+/// let mut rng = thread_rng();
+/// let generate = |rng: &mut ThreadRng| (Data { karma: Fr::rand(rng) }, Fr::rand(rng));
+///
+/// assert!(check_callbacks(&[good.clone()], &mut rng, generate, 10).is_ok());
+///
+/// let cex = check_callbacks(&[good, bad], &mut rng, generate, 10).unwrap_err();
+/// assert_eq!(cex.callback, Some(Fr::from(1)));
+/// assert!(matches!(cex.mismatch, Mismatch::ValueMismatch { index: 0, .. }));
+/// ```
+pub fn check_callbacks<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    Args: Clone,
+    ArgsVar: AllocVar<Args, F>,
+    R: CryptoRng + RngCore,
+>(
+    callbacks: &[Callback<F, U, Args, ArgsVar>],
+    rng: &mut R,
+    mut generate: impl FnMut(&mut R) -> (U, Args),
+    trials: usize,
+) -> Result<(), Counterexample<F, U, Args>>
+where
+    Standard: Distribution<F>,
+{
+    for trial in 0..trials {
+        let (data, args) = generate(rng);
+        let old = User::create(data, rng);
+
+        for cb in callbacks {
+            let new = (cb.method)(&old, args.clone());
+
+            let res = traced(|cs| {
+                let old_var = UserVar::new_witness(ns!(cs, "old_user"), || Ok(old.clone()))?;
+                compare_user(&old_var, &old)?;
+
+                let args_var = ArgsVar::new_witness(ns!(cs, "args"), || Ok(args.clone()))?;
+                let new_var = (cb.predicate)(&old_var, args_var)?;
+                compare_user(&new_var, &new)
+            });
+
+            if let Err(mismatch) = res {
+                return Err(Counterexample {
+                    trial,
+                    callback: Some(cb.method_id),
+                    old,
+                    new,
+                    args,
+                    mismatch,
+                });
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use ark_relations::ns;
-use std::{borrow::Borrow, marker::PhantomData};
+use std::{borrow::Borrow, marker::PhantomData, sync::Arc};
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
 use ark_r1cs_std::{alloc::AllocationMode, prelude::Boolean, select::CondSelectGadget};
@@ -70,11 +70,11 @@ pub(crate) fn rerandomize_predicate<'a, 'b, H: FieldHash<F>, F: PrimeField + Abs
 
 // TODO: write docs.
 /// TODO.
-pub fn gen_fold_proof_snark_key<F: PrimeField + Absorb, H: FieldHash<F>, U: UserData<F> + Default, Snark: SNARK<F>, Bul: PublicUserBul<F, U>>(
+pub fn gen_fold_proof_snark_key<F: PrimeField + Absorb, H: FieldHash<F> + 'static, U: UserData<F> + Default + 'static, Snark: SNARK<F>, Bul: PublicUserBul<F, U>>(
     rng: &mut (impl CryptoRng + RngCore),
     memb_data: Option<Bul::MembershipPub>,
 ) -> (Snark::ProvingKey, Snark::VerifyingKey) where Standard: Distribution<F> {
-    generate_keys_for_statement_in::<F, H, U, Rerand<F>, RerandVar<F>, ComRand<F>, ComRandVar<F>, Snark, Bul>(rng, rerandomize_predicate::<H, F, U>, memb_data, Rerand { com: F::default(), nul: F::default() })
+    generate_keys_for_statement_in::<F, H, U, Rerand<F>, RerandVar<F>, ComRand<F>, ComRandVar<F>, Snark, Bul>(rng, Arc::new(rerandomize_predicate::<H, F, U>), memb_data, Rerand { com: F::default(), nul: F::default() })
 }
 
 /// This allows for users to perform a folding scan instead of scanning incremenetally.
@@ -1,27 +1,40 @@
 use crate::{
-    crypto::{enc::AECipherSigZK, hash::FieldHash},
+    crypto::{
+        com::UserCommitment,
+        enc::AECipherSigZK,
+        hash::{FieldHash, HashDomain, hash_with_domain, hash_with_domain_in_zk},
+    },
     generic::{
         bulletin::{PublicCallbackBul, PublicUserBul},
         callbacks::{CallbackCom, CallbackComVar, add_ticket_to_hc_zk, create_defaults},
-        object::{Com, ComVar, Id, Nul, NulVar, Time},
+        object::{
+            Com, ComVar, Id, Nul, NulVar, Pseudonym, PseudonymVar, Ser, SerVar, Time,
+            derive_nul_in_zk, derive_pseudonym, derive_pseudonym_in_zk,
+        },
         scan::{PubScanArgs, get_scan_interaction},
         user::{User, UserData, UserVar},
     },
     util::ArrayVar,
 };
 use ark_crypto_primitives::sponge::Absorb;
-use ark_ff::PrimeField;
-use ark_r1cs_std::{alloc::AllocVar, boolean::Boolean, eq::EqGadget, select::CondSelectGadget};
+use ark_ff::{PrimeField, ToConstraintField};
+use ark_r1cs_std::{
+    alloc::AllocVar, boolean::Boolean, eq::EqGadget, fields::fp::FpVar, select::CondSelectGadget,
+};
 use ark_relations::{
     ns,
-    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Result as ArkResult},
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Result as ArkResult, SynthesisError},
 };
 use ark_snark::SNARK;
-use core::marker::PhantomData;
+use core::{cmp::Ordering, marker::PhantomData};
 use rand::{
     CryptoRng, RngCore,
     distributions::{Distribution, Standard},
 };
+use std::{
+    ops::{BitAnd, BitOr, Not},
+    sync::Arc,
+};
 
 /// A predicate.
 ///
@@ -46,8 +59,15 @@ use rand::{
 ///     old.data.rep.is_eq(&new.data.rep)
 /// }
 /// ```
-pub type Predicate<F, UserVar, PubArgsVar, PrivArgsVar> =
-    fn(&UserVar, &UserVar, PubArgsVar, PrivArgsVar) -> ArkResult<Boolean<F>>;
+///
+/// Predicates may capture state, such as a threshold. Since the predicate is also run when
+/// generating keys, any captured state is fixed into the circuit, and so must be the same when
+/// proving and verifying.
+pub type Predicate<F, UserVar, PubArgsVar, PrivArgsVar> = Arc<
+    dyn for<'a> Fn(&'a UserVar, &'a UserVar, PubArgsVar, PrivArgsVar) -> ArkResult<Boolean<F>>
+        + Send
+        + Sync,
+>;
 
 /// A predicate on a single user.
 ///
@@ -73,8 +93,13 @@ pub type Predicate<F, UserVar, PubArgsVar, PrivArgsVar> =
 ///     old.data.rep.is_eq(&FpVar::Constant(Fr::from(3)))
 /// }
 /// ```
-pub type SingularPredicate<F, UserVar, PubUserCom, PubArgsVar, PrivArgsVar> =
-    fn(&UserVar, &PubUserCom, PubArgsVar, PrivArgsVar) -> ArkResult<Boolean<F>>;
+///
+/// As with a [`Predicate`], singular predicates may capture state.
+pub type SingularPredicate<F, UserVar, PubUserCom, PubArgsVar, PrivArgsVar> = Arc<
+    dyn for<'a> Fn(&'a UserVar, &'a PubUserCom, PubArgsVar, PrivArgsVar) -> ArkResult<Boolean<F>>
+        + Send
+        + Sync,
+>;
 /// A method.
 ///
 /// This is a function `f(U, A, B) -> U'` which modifies a user based on some private and public
@@ -101,7 +126,8 @@ pub type SingularPredicate<F, UserVar, PubUserCom, PubArgsVar, PrivArgsVar> =
 ///     a
 /// }
 ///```
-pub type Method<User, PubArgs, PrivArgs> = fn(&User, PubArgs, PrivArgs) -> User;
+pub type Method<User, PubArgs, PrivArgs> =
+    Arc<dyn Fn(&User, PubArgs, PrivArgs) -> User + Send + Sync>;
 /// A method without private arguments.
 ///
 /// This is a function `f(U, A) -> U'` which does not have any private arguments. For example, a
@@ -128,7 +154,7 @@ pub type Method<User, PubArgs, PrivArgs> = fn(&User, PubArgs, PrivArgs) -> User;
 ///     a
 /// }
 ///```
-pub type NoPrivMethod<User, Args> = fn(&User, Args) -> User;
+pub type NoPrivMethod<User, Args> = Arc<dyn Fn(&User, Args) -> User + Send + Sync>;
 /// An in-circuit method update.
 ///
 /// This is meant for interatively scanning callback methods to update the user variable. Note that
@@ -156,7 +182,612 @@ pub type NoPrivMethod<User, Args> = fn(&User, Args) -> User;
 ///     Ok(a)
 /// }
 ///```
-pub type NoPrivMethodVar<UserVar, ArgsVar> = fn(&UserVar, ArgsVar) -> ArkResult<UserVar>;
+pub type NoPrivMethodVar<UserVar, ArgsVar> =
+    Arc<dyn Fn(&UserVar, ArgsVar) -> ArkResult<UserVar> + Send + Sync>;
+
+/// A field of some user data, from which predicates may be built declaratively.
+///
+/// This consists of the name of the field, along with how to serialize the field from the user
+/// data, both natively and in-circuit. Field references are generated by the
+/// [`zk_object`](`crate::zk_object`) macro on a separate struct (see [`UserFields::Fields`]): for
+/// user data `Data` with a field `rep`, `DataFields::rep()` is the corresponding `UserField`.
+///
+/// See [`UserPredicate`] for building predicates from fields.
+pub struct UserField<F: PrimeField + Absorb, U: UserData<F>> {
+    /// The name of the field.
+    pub name: &'static str,
+    /// Serializes the field from the user data.
+    pub native: fn(&U) -> Vec<Ser<F>>,
+    /// Serializes the field from the in-circuit user data.
+    pub in_zk: fn(&U::UserDataVar) -> ArkResult<Vec<SerVar<F>>>,
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Clone for UserField<F, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Copy for UserField<F, U> {}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> UserField<F, U> {
+    /// Refer to the field of the old user, before a method is applied.
+    ///
+    /// For statements about a single user, this is the field of that user.
+    pub fn before(self) -> FieldOf<F, U> {
+        FieldOf {
+            field: self,
+            after: false,
+        }
+    }
+
+    /// Refer to the field of the new user, after a method is applied.
+    ///
+    /// For statements about a single user, this is the same as [`UserField::before`].
+    pub fn after(self) -> FieldOf<F, U> {
+        FieldOf {
+            field: self,
+            after: true,
+        }
+    }
+}
+
+/// User data whose fields may be referred to in predicates.
+///
+/// This is implemented by the [`zk_object`](`crate::zk_object`) macro.
+pub trait UserFields<F: PrimeField + Absorb>: UserData<F> {
+    /// The type holding a reference to each field of the user data.
+    ///
+    /// For user data `Data`, the macro generates a unit struct `DataFields` with one associated
+    /// function per field, so that the references never collide with methods of `Data`.
+    type Fields;
+
+    /// All fields of the user data.
+    fn fields() -> Vec<UserField<F, Self>>;
+}
+
+/// A field of either the old or the new user, on which comparisons may be made.
+///
+/// Comparisons treat the field as an integer, and so the field must serialize to a single field
+/// element of size at most `(p - 1) / 2` (as with field elements holding small values, `u8`, `bool`
+/// and [`Time`]). If not, no proof can be made, and the predicate evaluates to false natively.
+pub struct FieldOf<F: PrimeField + Absorb, U: UserData<F>> {
+    field: UserField<F, U>,
+    after: bool,
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Clone for FieldOf<F, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Copy for FieldOf<F, U> {}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> FieldOf<F, U> {
+    fn compare(self, ordering: Ordering, or_equal: bool, value: F) -> UserPredicate<F, U> {
+        UserPredicate(Cond::Cmp(self, ordering, or_equal, value))
+    }
+
+    /// The field is at least `value`.
+    pub fn ge(self, value: F) -> UserPredicate<F, U> {
+        self.compare(Ordering::Greater, true, value)
+    }
+
+    /// The field is greater than `value`.
+    pub fn gt(self, value: F) -> UserPredicate<F, U> {
+        self.compare(Ordering::Greater, false, value)
+    }
+
+    /// The field is at most `value`.
+    pub fn le(self, value: F) -> UserPredicate<F, U> {
+        self.compare(Ordering::Less, true, value)
+    }
+
+    /// The field is less than `value`.
+    pub fn lt(self, value: F) -> UserPredicate<F, U> {
+        self.compare(Ordering::Less, false, value)
+    }
+
+    /// The field lies in the range `[low, high]` (inclusive).
+    pub fn in_range(self, low: F, high: F) -> UserPredicate<F, U> {
+        self.ge(low) & self.le(high)
+    }
+
+    /// The field is equal to `value`.
+    pub fn equals(self, value: F) -> UserPredicate<F, U> {
+        UserPredicate(Cond::Equals(self, value))
+    }
+
+    fn native(&self, old: &User<F, U>, new: &User<F, U>) -> Vec<Ser<F>> {
+        (self.field.native)(if self.after { &new.data } else { &old.data })
+    }
+
+    fn in_zk(&self, old: &UserVar<F, U>, new: &UserVar<F, U>) -> ArkResult<Vec<SerVar<F>>> {
+        (self.field.in_zk)(if self.after { &new.data } else { &old.data })
+    }
+}
+
+#[derive(Clone)]
+enum Cond<F: PrimeField + Absorb, U: UserData<F>> {
+    Const(bool),
+    Cmp(FieldOf<F, U>, Ordering, bool, F),
+    Equals(FieldOf<F, U>, F),
+    Unchanged(UserField<F, U>),
+    ScannedSince(Time<F>),
+    And(Box<Cond<F, U>>, Box<Cond<F, U>>),
+    Or(Box<Cond<F, U>>, Box<Cond<F, U>>),
+    Not(Box<Cond<F, U>>),
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Cond<F, U> {
+    /// Evaluates the condition natively. Returns `None` if the condition cannot be proven at all,
+    /// mirroring an unsatisfiable circuit.
+    fn evaluate(&self, old: &User<F, U>, new: &User<F, U>) -> Option<bool> {
+        match self {
+            Cond::Const(b) => Some(*b),
+            Cond::Cmp(field, ordering, or_equal, value) => {
+                let [x] = field.native(old, new)[..] else {
+                    return None;
+                };
+                if x.into_bigint() > F::MODULUS_MINUS_ONE_DIV_TWO
+                    || value.into_bigint() > F::MODULUS_MINUS_ONE_DIV_TWO
+                {
+                    return None;
+                }
+                let res = x.into_bigint().cmp(&value.into_bigint());
+                Some(res == *ordering || (*or_equal && res == Ordering::Equal))
+            }
+            Cond::Equals(field, value) => {
+                let [x] = field.native(old, new)[..] else {
+                    return None;
+                };
+                Some(x == *value)
+            }
+            Cond::Unchanged(field) => Some((field.native)(&old.data) == (field.native)(&new.data)),
+            Cond::ScannedSince(time) => {
+                let last = old.zk_fields.last_full_scan;
+                if last.into_bigint() > F::MODULUS_MINUS_ONE_DIV_TWO
+                    || time.into_bigint() > F::MODULUS_MINUS_ONE_DIV_TWO
+                {
+                    return None;
+                }
+                Some(old.zk_fields.scanned_since(*time))
+            }
+            Cond::And(a, b) => Some(a.evaluate(old, new)? & b.evaluate(old, new)?),
+            Cond::Or(a, b) => Some(a.evaluate(old, new)? | b.evaluate(old, new)?),
+            Cond::Not(a) => Some(!a.evaluate(old, new)?),
+        }
+    }
+
+    fn enforce(&self, old: &UserVar<F, U>, new: &UserVar<F, U>) -> ArkResult<Boolean<F>> {
+        match self {
+            Cond::Const(b) => Ok(Boolean::constant(*b)),
+            Cond::Cmp(field, ordering, or_equal, value) => {
+                let ser = field.in_zk(old, new)?;
+                let [x] = &ser[..] else {
+                    return Err(SynthesisError::Unsatisfiable);
+                };
+                x.is_cmp(&FpVar::Constant(*value), *ordering, *or_equal)
+            }
+            Cond::Equals(field, value) => {
+                let ser = field.in_zk(old, new)?;
+                let [x] = &ser[..] else {
+                    return Err(SynthesisError::Unsatisfiable);
+                };
+                x.is_eq(&FpVar::Constant(*value))
+            }
+            Cond::Unchanged(field) => {
+                let a = (field.in_zk)(&old.data)?;
+                let b = (field.in_zk)(&new.data)?;
+                if a.len() != b.len() {
+                    return Ok(Boolean::FALSE);
+                }
+                let mut eq = Boolean::TRUE;
+                for (x, y) in a.iter().zip(&b) {
+                    eq &= x.is_eq(y)?;
+                }
+                Ok(eq)
+            }
+            Cond::ScannedSince(time) => old.zk_fields.scanned_since(&FpVar::Constant(*time)),
+            Cond::And(a, b) => Ok(a.enforce(old, new)? & b.enforce(old, new)?),
+            Cond::Or(a, b) => Ok(a.enforce(old, new)? | b.enforce(old, new)?),
+            Cond::Not(a) => Ok(!a.enforce(old, new)?),
+        }
+    }
+}
+
+/// A predicate on user data, built from [`UserField`]s with combinators.
+///
+/// Predicates are built from comparisons on fields (see [`FieldOf`]), along with
+/// [`UserPredicate::unchanged`] and [`UserPredicate::unchanged_except`], and combined with `&`,
+/// `|` and `!`. A predicate may then be used in an [`Interaction`] with
+/// [`UserPredicate::into_predicate`], or to prove a statement with
+/// [`UserPredicate::into_singular_predicate`].
+///
+/// Predicates may also be evaluated natively with [`UserPredicate::evaluate`], which mirrors the
+/// in-circuit result: it is true exactly when a proof of the predicate can be made. This is useful
+/// for testing predicates without generating keys or proofs.
+///
+/// # Example
+/// ```rust
+/// # use ark_bn254::Fr;
+/// # use ark_r1cs_std::prelude::{AllocVar, R1CSVar};
+/// # use ark_relations::r1cs::ConstraintSystem;
+/// # use rand::thread_rng;
+/// # use zk_callbacks::zk_object;
+/// # use zk_callbacks::generic::interaction::UserPredicate;
+/// # use zk_callbacks::generic::user::{User, UserVar};
+/// #[zk_object(Fr)]
+/// #[derive(Default)]
+/// struct Data {
+///     pub bad_rep: Fr,
+///     pub posts: Fr,
+/// }
+///
+/// // Reputation must be at most 40, and only the number of posts may change.
+/// let pred = DataFields::bad_rep().before().le(Fr::from(40))
+///     & UserPredicate::unchanged_except(&[DataFields::posts()]);
+///
+/// let mut rng = thread_rng();
+/// let old = User::create(Data { bad_rep: Fr::from(10), posts: Fr::from(0) }, &mut rng);
+/// let mut new = old.clone();
+/// new.data.posts = Fr::from(1);
+///
+/// assert!(pred.evaluate(&old, &new));
+///
+/// // The in-circuit result matches the native one.
+/// let cs = ConstraintSystem::<Fr>::new_ref();
+/// let old_var = UserVar::new_witness(cs.clone(), || Ok(&old)).unwrap();
+/// let new_var = UserVar::new_witness(cs.clone(), || Ok(&new)).unwrap();
+/// assert!(pred.enforce(&old_var, &new_var).unwrap().value().unwrap());
+/// assert!(cs.is_satisfied().unwrap());
+///
+/// new.data.bad_rep = Fr::from(50);
+/// assert!(!pred.evaluate(&old, &new));
+/// ```
+#[derive(Clone)]
+pub struct UserPredicate<F: PrimeField + Absorb, U: UserData<F>>(Cond<F, U>);
+
+impl<F: PrimeField + Absorb, U: UserData<F>> UserPredicate<F, U> {
+    /// A predicate which always has the value `b`.
+    pub fn constant(b: bool) -> Self {
+        Self(Cond::Const(b))
+    }
+
+    /// The field is the same in the old and new user.
+    pub fn unchanged(field: UserField<F, U>) -> Self {
+        Self(Cond::Unchanged(field))
+    }
+
+    /// The old user has scanned every callback called before `time`.
+    ///
+    /// This holds if the old user is not in the middle of a scan, and its last full scan started
+    /// at or after `time` (see [`ZKFields::last_full_scan`](super::object::ZKFields::last_full_scan)). A service
+    /// may require this before sensitive actions, so that users cannot act while avoiding
+    /// outstanding callbacks. To compare against a time passed in the public arguments instead,
+    /// use [`ZKFieldsVar::scanned_since`](super::object::ZKFieldsVar::scanned_since) within a
+    /// predicate.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use ark_bn254::Fr;
+    /// # use rand::thread_rng;
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::interaction::UserPredicate;
+    /// # use zk_callbacks::generic::object::Time;
+    /// # use zk_callbacks::generic::user::User;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub bad_rep: Fr,
+    /// }
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut u = User::create(Data { bad_rep: Fr::from(0) }, &mut rng);
+    /// u.zk_fields.last_full_scan = Time::from(20);
+    ///
+    /// assert!(UserPredicate::scanned_since(Time::from(10)).evaluate_single(&u));
+    /// assert!(!UserPredicate::scanned_since(Time::from(30)).evaluate_single(&u));
+    /// ```
+    pub fn scanned_since(time: Time<F>) -> Self {
+        Self(Cond::ScannedSince(time))
+    }
+
+    /// Every field of the user data is the same in the old and new user, except for the fields
+    /// in `except`.
+    pub fn unchanged_except(except: &[UserField<F, U>]) -> Self
+    where
+        U: UserFields<F>,
+    {
+        U::fields()
+            .into_iter()
+            .filter(|f| except.iter().all(|e| e.name != f.name))
+            .fold(Self::constant(true), |acc, f| acc & Self::unchanged(f))
+    }
+
+    /// Evaluate the predicate natively on an old and new user.
+    ///
+    /// This is true exactly when the predicate may be proven in-circuit.
+    pub fn evaluate(&self, old: &User<F, U>, new: &User<F, U>) -> bool {
+        self.0.evaluate(old, new) == Some(true)
+    }
+
+    /// Evaluate the predicate natively on a single user.
+    ///
+    /// See [`UserPredicate::evaluate`].
+    pub fn evaluate_single(&self, user: &User<F, U>) -> bool {
+        self.evaluate(user, user)
+    }
+
+    /// Compute the predicate in-circuit on an old and new user.
+    pub fn enforce(&self, old: &UserVar<F, U>, new: &UserVar<F, U>) -> ArkResult<Boolean<F>> {
+        self.0.enforce(old, new)
+    }
+
+    /// Turn this into a [`Predicate`] for use in an [`Interaction`], ignoring any arguments.
+    pub fn into_predicate<PubArgsVar, PrivArgsVar>(
+        self,
+    ) -> Predicate<F, UserVar<F, U>, PubArgsVar, PrivArgsVar>
+    where
+        U: 'static,
+    {
+        Arc::new(
+            move |old: &UserVar<F, U>, new: &UserVar<F, U>, _: PubArgsVar, _: PrivArgsVar| {
+                self.enforce(old, new)
+            },
+        )
+    }
+
+    /// Turn this into a [`SingularPredicate`] for proving statements, ignoring the commitment and
+    /// any arguments.
+    pub fn into_singular_predicate<PubUserCom, PubArgsVar, PrivArgsVar>(
+        self,
+    ) -> SingularPredicate<F, UserVar<F, U>, PubUserCom, PubArgsVar, PrivArgsVar>
+    where
+        U: 'static,
+    {
+        Arc::new(
+            move |user: &UserVar<F, U>, _: &PubUserCom, _: PubArgsVar, _: PrivArgsVar| {
+                self.enforce(user, user)
+            },
+        )
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> BitAnd for UserPredicate<F, U> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(Cond::And(Box::new(self.0), Box::new(rhs.0)))
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> BitOr for UserPredicate<F, U> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(Cond::Or(Box::new(self.0), Box::new(rhs.0)))
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Not for UserPredicate<F, U> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(Cond::Not(Box::new(self.0)))
+    }
+}
+
+/// User data which may be read back from its serialization, when revealed in a [`Disclosure`].
+///
+/// This is implemented for field elements (and so [`Time`]), `bool` and the unsigned integers.
+pub trait Revealable<F: PrimeField + Absorb>: UserData<F> {
+    /// Read a value from its serialization (as in [`UserData::serialize_elements`]). Returns
+    /// `None` if the elements are not the serialization of any value.
+    fn from_revealed(elems: &[Ser<F>]) -> Option<Self>;
+}
+
+type RevealNative<F, U> = Arc<dyn Fn(&User<F, U>) -> Vec<Ser<F>> + Send + Sync>;
+
+type RevealInZk<F, U> = Arc<dyn Fn(&UserVar<F, U>) -> ArkResult<Vec<SerVar<F>>> + Send + Sync>;
+
+#[derive(Clone, PartialEq, Eq)]
+enum DisclosedKind<F: PrimeField> {
+    Clear(&'static str),
+    Hashed(&'static str),
+    Pseudonym(Id<F>),
+}
+
+#[derive(Clone)]
+struct DisclosedField<F: PrimeField + Absorb, U: UserData<F>> {
+    kind: DisclosedKind<F>,
+    native: RevealNative<F, U>,
+    in_zk: RevealInZk<F, U>,
+}
+
+/// The values revealed by a [`Disclosure`], one entry for each disclosed field.
+///
+/// These are public inputs to the statement proof, following the public arguments. Typed values
+/// are read back with [`Disclosure::get`] and [`Disclosure::get_hash`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Revealed<F: PrimeField>(pub Vec<Vec<Ser<F>>>);
+
+impl<F: PrimeField> ToConstraintField<F> for Revealed<F> {
+    fn to_field_elements(&self) -> Option<Vec<F>> {
+        Some(self.0.concat())
+    }
+}
+
+/// A selection of user fields to reveal when proving a statement.
+///
+/// By default, a statement proof only shows that a predicate holds for the user. A disclosure
+/// additionally reveals some fields of the user, bound to the proof, such as a tier level shown to
+/// a partner service. Fields are revealed either in the clear with [`Disclosure::reveal`], or as a
+/// hash with [`Disclosure::reveal_hashed`], which only allows a verifier to check the field
+/// against a value they already know. A disclosure may also reveal the pseudonym of the user
+/// within a scope, with [`Disclosure::pseudonym`].
+///
+/// Disclosures are used with [`User::prove_disclosed_statement`] and
+/// [`User::prove_disclosed_statement_and_in`]. As the revealed fields are fixed into the circuit,
+/// the prover and verifier must use the same disclosure as when generating keys.
+#[derive(Clone)]
+pub struct Disclosure<F: PrimeField + Absorb, U: UserData<F>> {
+    fields: Vec<DisclosedField<F, U>>,
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Default for Disclosure<F, U> {
+    fn default() -> Self {
+        Self { fields: vec![] }
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F> + 'static> Disclosure<F, U> {
+    /// Create a disclosure revealing nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reveal a field in the clear.
+    pub fn reveal(mut self, field: UserField<F, U>) -> Self {
+        let (native, in_zk) = (field.native, field.in_zk);
+        self.fields.push(DisclosedField {
+            kind: DisclosedKind::Clear(field.name),
+            native: Arc::new(move |user: &User<F, U>| native(&user.data)),
+            in_zk: Arc::new(move |user: &UserVar<F, U>| in_zk(&user.data)),
+        });
+        self
+    }
+
+    /// Reveal the hash of a field (in the [`HashDomain::Disclosure`] domain) under `H`.
+    ///
+    /// A verifier may check the hash against a known value with [`Disclosure::hashed_value`]. As
+    /// the hash is unsalted, it does not hide fields with few possible values.
+    pub fn reveal_hashed<H: FieldHash<F>>(mut self, field: UserField<F, U>) -> Self {
+        let (native, in_zk) = (field.native, field.in_zk);
+        self.fields.push(DisclosedField {
+            kind: DisclosedKind::Hashed(field.name),
+            native: Arc::new(move |user: &User<F, U>| {
+                vec![hash_with_domain::<F, H>(
+                    HashDomain::Disclosure,
+                    &native(&user.data),
+                )]
+            }),
+            in_zk: Arc::new(move |user: &UserVar<F, U>| -> ArkResult<Vec<SerVar<F>>> {
+                Ok(vec![hash_with_domain_in_zk::<F, H>(
+                    HashDomain::Disclosure,
+                    &in_zk(&user.data)?,
+                )?])
+            }),
+        });
+        self
+    }
+
+    /// Reveal the pseudonym of the user within `scope`, derived under `H` (see
+    /// [`derive_pseudonym`]).
+    ///
+    /// The pseudonym is the same in every proof within the scope, so a service may use it as a
+    /// persistent identifier for the user, while proofs with different scopes remain unlinkable.
+    pub fn pseudonym<H: FieldHash<F>>(mut self, scope: Id<F>) -> Self {
+        self.fields.push(DisclosedField {
+            kind: DisclosedKind::Pseudonym(scope),
+            native: Arc::new(move |user: &User<F, U>| {
+                vec![derive_pseudonym::<F, H>(user.zk_fields.secret, scope)]
+            }),
+            in_zk: Arc::new(move |user: &UserVar<F, U>| -> ArkResult<Vec<SerVar<F>>> {
+                Ok(vec![derive_pseudonym_in_zk::<F, H>(
+                    &user.zk_fields.secret,
+                    &FpVar::Constant(scope),
+                )?])
+            }),
+        });
+        self
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Disclosure<F, U> {
+    /// The number of disclosed fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Whether nothing is disclosed.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The values revealed for a user.
+    pub fn values(&self, user: &User<F, U>) -> Revealed<F> {
+        Revealed(
+            self.fields
+                .iter()
+                .map(|field| (field.native)(user))
+                .collect(),
+        )
+    }
+
+    /// Allocate the revealed values as public inputs, and enforce that they are the values of the
+    /// user.
+    pub(crate) fn enforce(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        user: &UserVar<F, U>,
+        revealed: &Revealed<F>,
+    ) -> ArkResult<()> {
+        if revealed.0.len() != self.fields.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        for (field, values) in self.fields.iter().zip(&revealed.0) {
+            let ser = (field.in_zk)(user)?;
+            if ser.len() != values.len() {
+                return Err(SynthesisError::Unsatisfiable);
+            }
+            for (x, v) in ser.iter().zip(values) {
+                let v_var = FpVar::new_input(ns!(cs, "revealed"), || Ok(*v))?;
+                x.enforce_equal(&v_var)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn entry<'a>(&self, revealed: &'a Revealed<F>, kind: DisclosedKind<F>) -> Option<&'a [F]> {
+        let index = self.fields.iter().position(|f| f.kind == kind)?;
+        revealed.0.get(index).map(Vec::as_slice)
+    }
+
+    /// Read a field revealed in the clear from the revealed values.
+    ///
+    /// Returns `None` if the field is not revealed in the clear, or if the revealed value is not
+    /// a valid `T`.
+    pub fn get<T: Revealable<F>>(
+        &self,
+        revealed: &Revealed<F>,
+        field: UserField<F, U>,
+    ) -> Option<T> {
+        T::from_revealed(self.entry(revealed, DisclosedKind::Clear(field.name))?)
+    }
+
+    /// Read the hash of a field revealed with [`Disclosure::reveal_hashed`].
+    pub fn get_hash(&self, revealed: &Revealed<F>, field: UserField<F, U>) -> Option<F> {
+        let [hash] = self.entry(revealed, DisclosedKind::Hashed(field.name))? else {
+            return None;
+        };
+        Some(*hash)
+    }
+
+    /// Read the pseudonym of the user within `scope`, revealed with [`Disclosure::pseudonym`].
+    pub fn get_pseudonym(&self, revealed: &Revealed<F>, scope: Id<F>) -> Option<Pseudonym<F>> {
+        let [pseudonym] = self.entry(revealed, DisclosedKind::Pseudonym(scope))? else {
+            return None;
+        };
+        Some(*pseudonym)
+    }
+
+    /// The hash of a value, as revealed by [`Disclosure::reveal_hashed`] under `H`.
+    pub fn hashed_value<H: FieldHash<F>, T: UserData<F>>(value: &T) -> F {
+        hash_with_domain::<F, H>(HashDomain::Disclosure, &value.serialize_elements())
+    }
+}
 
 /// A callback. This consists of the data of the function along with expiry information.
 ///
@@ -169,6 +800,7 @@ pub type NoPrivMethodVar<UserVar, ArgsVar> = fn(&UserVar, ArgsVar) -> ArkResult<
 ///
 /// # Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use zk_callbacks::zk_object;
 /// # use zk_callbacks::generic::user::User;
 /// # use rand::thread_rng;
@@ -230,8 +862,8 @@ pub type NoPrivMethodVar<UserVar, ArgsVar> = fn(&UserVar, ArgsVar) -> ArkResult<
 ///         method_id: Id::from(0),
 ///         expirable: true,
 ///         expiration: Time::from(25),
-///         method: callback,
-///         predicate: enforce_callback
+///         method: Arc::new(callback),
+///         predicate: Arc::new(enforce_callback)
 ///     };
 /// }
 #[derive(Clone)]
@@ -256,6 +888,44 @@ impl<F: PrimeField + Absorb, U: UserData<F>, Args, ArgsVar: AllocVar<Args, F>> s
     }
 }
 
+impl<F: PrimeField + Absorb, U: UserData<F>, Args, ArgsVar: AllocVar<Args, F>>
+    Callback<F, U, Args, ArgsVar>
+{
+    /// Create a callback from an update method and its in-circuit counterpart.
+    ///
+    /// Both may be closures which capture state, such as the size of a penalty. Any captured state
+    /// is fixed into the scan circuit when generating keys, so the method and predicate must agree,
+    /// and must behave the same whenever they are called.
+    pub fn new(
+        method_id: Id<F>,
+        expirable: bool,
+        expiration: Time<F>,
+        method: impl Fn(&User<F, U>, Args) -> User<F, U> + Send + Sync + 'static,
+        predicate: impl Fn(&UserVar<F, U>, ArgsVar) -> ArkResult<UserVar<F, U>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            method_id,
+            expirable,
+            expiration,
+            method: Arc::new(method),
+            predicate: Arc::new(predicate),
+        }
+    }
+
+    /// Repeat the callback `K` times, to issue a ticket which may be called up to `K` times.
+    ///
+    /// Placing these `K` callbacks consecutively in an interaction issues `K` single-use tickets,
+    /// which a service may group into a
+    /// [`MultiUseTicket`](super::callbacks::MultiUseTicket) and call one after another, each with
+    /// its own arguments. As the tickets are consecutive, scanning applies the calls in order.
+    pub fn uses<const K: usize>(&self) -> [Self; K]
+    where
+        Self: Clone,
+    {
+        core::array::from_fn(|_| self.clone())
+    }
+}
+
 /// A constant size array of `N` callbacks, of type [`Callback`].
 pub type CallbackList<F, U, A, X, const N: usize> = [Callback<F, U, A, X>; N];
 
@@ -276,6 +946,7 @@ pub type MethProof<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> = (
 ///
 /// # Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use zk_callbacks::zk_object;
 /// # use zk_callbacks::generic::user::User;
 /// # use rand::thread_rng;
@@ -354,15 +1025,18 @@ pub type MethProof<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> = (
 ///         method_id: Id::from(0),
 ///         expirable: false,
 ///         expiration: Time::from(10),
-///         method: callback,
-///         predicate: enforce_callback
+///         method: Arc::new(callback),
+///         predicate: Arc::new(enforce_callback)
 ///     };
 ///
 ///     let cb_methods = vec![cb.clone()];
 ///
 ///     let int = Interaction {
-///         meth: (method, predicate),
+///         meth: (Arc::new(method), Arc::new(predicate)),
 ///         callbacks: [cb.clone()],
+///         unchanged: None,
+///         hidden_ids: None,
+///         pseudonym_scope: None,
 ///     };
 /// }
 #[derive(Clone)]
@@ -382,58 +1056,320 @@ pub struct Interaction<
     pub meth: MethProof<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar>,
     /// A list of callbacks.
     pub callbacks: CallbackList<F, U, CBArgs, CBArgsVar, NUMCBS>,
+    /// An optional condition enforcing that fields the method does not modify are unchanged.
+    ///
+    /// This is enforced alongside the predicate when proving the interaction. It is usually set
+    /// with [`Interaction::modifying`], which declares the fields the method may modify.
+    pub unchanged: Option<UserPredicate<F, U>>,
+    /// If set, the method ids of issued callbacks are hidden from the service, which only learns
+    /// that each is one of these ids.
+    ///
+    /// See [`Interaction::hiding_ids`].
+    pub hidden_ids: Option<Vec<Id<F>>>,
+    /// If set, the interaction outputs the pseudonym of the user within this scope.
+    ///
+    /// See [`Interaction::with_pseudonym`].
+    pub pseudonym_scope: Option<Id<F>>,
 }
 
 impl<
     F: PrimeField + Absorb,
-    U: UserData<F> + Default,
-    PubArgs: Clone + Default + std::fmt::Debug,
-    PubArgsVar: AllocVar<PubArgs, F> + Clone,
-    PrivArgs: Clone + Default + std::fmt::Debug,
-    PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
-    CBArgs: Clone + Default + std::fmt::Debug,
-    CBArgsVar: AllocVar<CBArgs, F> + Clone,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
     const NUMCBS: usize,
 > Interaction<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, NUMCBS>
-where
-    Standard: Distribution<F>,
 {
-    /// Generate proving and verification keys for an interaction.
-    ///
-    /// If the public membership data is a constant, it must be encoded into the key, and so it
-    /// must be set in `memb_data`. Otherwise, the `memb_data` should be set to `None`.
+    /// Create an interaction from a method, a predicate enforced on the method, and a list of
+    /// callbacks.
     ///
-    /// For `aux_data`, if the auxiliary public arguments to the interaction method is a constant,
-    /// this should be done in the `AllocVar` implementation of the auxiliary data type.
+    /// The method and predicate may be closures which capture state. This allows a single generic
+    /// interaction to be instantiated with different constants, as in the example below. Any
+    /// captured state is fixed into the circuit when generating keys, so each instantiation needs
+    /// its own keys.
     ///
     /// # Example
     /// ```rust
     /// # use zk_callbacks::zk_object;
-    /// # use zk_callbacks::generic::user::User;
-    /// # use rand::thread_rng;
-    /// # use ark_bn254::{Bn254 as E, Fr};
-    /// # use ark_r1cs_std::eq::EqGadget;
     /// # use zk_callbacks::generic::interaction::Interaction;
-    /// # use zk_callbacks::generic::interaction::Callback;
-    /// # use zk_callbacks::generic::object::Id;
-    /// # use zk_callbacks::generic::object::Time;
-    /// # use ark_relations::r1cs::SynthesisError;
-    /// # use zk_callbacks::generic::user::UserVar;
+    /// # use zk_callbacks::generic::user::{User, UserVar};
+    /// # use ark_bn254::Fr;
+    /// # use ark_r1cs_std::cmp::CmpGadget;
+    /// # use ark_r1cs_std::eq::EqGadget;
     /// # use ark_r1cs_std::fields::fp::FpVar;
-    /// # use ark_groth16::Groth16;
-    /// # use ark_r1cs_std::prelude::Boolean;
-    /// # use zk_callbacks::impls::hash::Poseidon;
-    /// # use zk_callbacks::impls::dummy::DummyStore;
-    /// # use zk_callbacks::impls::centralized::crypto::{FakeSigPubkey, NoSigOTP};
-    /// # type Groth = Groth16<E>;
-    ///#  #[zk_object(Fr)]
-    ///#  #[derive(Default)]
-    ///#  struct Data {
-    ///#      karma: Fr,
-    ///#      is_banned: bool,
-    ///#  }
-    ///#
-    ///#  fn method<'a>(old_user: &'a User<Fr, Data>, _pub: (), _priv: ()) -> User<Fr, Data> {
+    /// # use ark_r1cs_std::prelude::UInt8;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub bad_rep: u8,
+    /// }
+    ///
+    /// type Post = Interaction<Fr, Data, (), (), (), (), Fr, FpVar<Fr>, 0>;
+    ///
+    /// // Posting is allowed as long as the user's reputation is at most `threshold`.
+    /// fn post(threshold: u8) -> Post {
+    ///     Interaction::new(
+    ///         |old: &User<Fr, Data>, _pub: (), _priv: ()| old.clone(),
+    ///         move |old: &UserVar<Fr, Data>, new: &UserVar<Fr, Data>, _pub: (), _priv: ()| {
+    ///             let unchanged = old.data.bad_rep.is_eq(&new.data.bad_rep)?;
+    ///             let below = old.data.bad_rep.is_le(&UInt8::constant(threshold))?;
+    ///             Ok(unchanged & below)
+    ///         },
+    ///         [],
+    ///     )
+    /// }
+    ///
+    /// // Two forums with different moderation policies.
+    /// let strict = post(10);
+    /// let lenient = post(40);
+    /// ```
+    pub fn new(
+        method: impl Fn(&User<F, U>, PubArgs, PrivArgs) -> User<F, U> + Send + Sync + 'static,
+        predicate: impl for<'a> Fn(
+            &'a UserVar<F, U>,
+            &'a UserVar<F, U>,
+            PubArgsVar,
+            PrivArgsVar,
+        ) -> ArkResult<Boolean<F>>
+        + Send
+        + Sync
+        + 'static,
+        callbacks: CallbackList<F, U, CBArgs, CBArgsVar, NUMCBS>,
+    ) -> Self {
+        Self {
+            meth: (Arc::new(method), Arc::new(predicate)),
+            callbacks,
+            unchanged: None,
+            hidden_ids: None,
+            pseudonym_scope: None,
+        }
+    }
+
+    /// Declare the fields of the user data which the method may modify.
+    ///
+    /// Every other field is then enforced to be the same in the old and new user when proving the
+    /// interaction, so the predicate only needs to constrain the modified fields. The bookkeeping
+    /// in the [`ZKFields`](crate::generic::object::ZKFields) (the callback lists and scan state)
+    /// is always enforced by the interaction circuit, regardless of this declaration.
+    ///
+    /// As this changes the circuit, keys must be generated after declaring the modified fields.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::interaction::Interaction;
+    /// # use zk_callbacks::generic::user::{User, UserVar};
+    /// # use ark_bn254::Fr;
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub bad_rep: u8,
+    ///     pub posts: Fr,
+    /// }
+    ///
+    /// type Post = Interaction<Fr, Data, (), (), (), (), Fr, FpVar<Fr>, 0>;
+    ///
+    /// // The predicate only constrains `posts`, but `bad_rep` may not be changed either.
+    /// let post: Post = Interaction::new(
+    ///     |old: &User<Fr, Data>, _pub: (), _priv: ()| {
+    ///         let mut new = old.clone();
+    ///         new.data.posts += Fr::from(1);
+    ///         new
+    ///     },
+    ///     |old: &UserVar<Fr, Data>, new: &UserVar<Fr, Data>, _pub: (), _priv: ()| {
+    ///         (&old.data.posts + FpVar::Constant(Fr::from(1))).is_eq(&new.data.posts)
+    ///     },
+    ///     [],
+    /// )
+    /// .modifying(&[DataFields::posts()]);
+    ///
+    /// assert!(post.unchanged.is_some());
+    /// ```
+    pub fn modifying(mut self, fields: &[UserField<F, U>]) -> Self
+    where
+        U: UserFields<F>,
+    {
+        self.unchanged = Some(UserPredicate::unchanged_except(fields));
+        self
+    }
+
+    /// Hide the method ids of the issued callbacks from the service, revealing only that each is
+    /// one of the `allowed` ids.
+    ///
+    /// Each callback is handed to the service with its method id blinded (see
+    /// [`CallbackCom::blind_method_id`]), and the interaction proof shows that the hidden id is in
+    /// `allowed`. The user keeps the original callbacks, so scanning works as usual, dispatching
+    /// on the hidden id in-circuit.
+    ///
+    /// As the keys only depend on the allowed ids, a user may swap the callbacks of the
+    /// interaction for any of the allowed callbacks before interacting. Services should approve
+    /// such interactions with
+    /// [`approve_interaction_hidden_ids`](super::service::ServiceProvider::approve_interaction_hidden_ids).
+    /// Note that the expiration of a ticket is still revealed, so allowed callbacks should share
+    /// the same expiration to remain indistinguishable.
+    ///
+    /// As this changes the circuit, keys must be generated after setting the allowed ids.
+    pub fn hiding_ids(mut self, allowed: &[Id<F>]) -> Self {
+        self.hidden_ids = Some(allowed.to_vec());
+        self
+    }
+
+    /// Output the pseudonym of the user within `scope` with the interaction.
+    ///
+    /// The pseudonym is derived in-circuit from the secret of the user and the scope (see
+    /// [`derive_pseudonym`]), and is a public output of the proof, which
+    /// [`ExecutedMethod::pseudonym`](super::user::ExecutedMethod::pseudonym) holds. A user
+    /// always has the same pseudonym within a scope, so a service may give users a persistent
+    /// identity (such as a display name in a forum), while interactions in different scopes remain
+    /// unlinkable.
+    ///
+    /// To verify the interaction, the public arguments are given along with the pseudonym, as
+    /// [`PseudonymArgs`].
+    ///
+    /// As this changes the circuit, keys must be generated after setting the scope.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::user::{User, UserVar};
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use zk_callbacks::generic::interaction::{Interaction, PseudonymArgs};
+    /// # use zk_callbacks::generic::object::{Time, derive_pseudonym};
+    /// # use zk_callbacks::generic::bulletin::UserBul;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::impls::centralized::crypto::NoSigOTP;
+    /// # type Groth = Groth16<E>;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub num_posts: Fr,
+    /// }
+    ///
+    /// fn method<'a>(old_user: &'a User<Fr, Data>, _pub: (), _priv: ()) -> User<Fr, Data> {
+    ///     let mut new = old_user.clone();
+    ///     new.data.num_posts += Fr::from(1);
+    ///     new
+    /// }
+    ///
+    /// fn predicate<'a>(old_user: &'a UserVar<Fr, Data>, new_user: &'a UserVar<Fr, Data>, _pub: (), _priv: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     new_user.data.num_posts.is_eq(&(old_user.data.num_posts.clone() + FpVar::Constant(Fr::from(1))))
+    /// }
+    ///
+    /// fn main () {
+    ///     let forum = Fr::from(7);
+    ///
+    ///     let int = Interaction {
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
+    ///         callbacks: [],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
+    ///     }.with_pseudonym(forum);
+    ///
+    ///     let mut rng = thread_rng();
+    ///
+    ///     let (pk, vk) = int.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), None, false);
+    ///
+    ///     let mut u = User::create(Data { num_posts: Fr::from(0) }, &mut rng);
+    ///
+    ///     let first = u.interact::<Poseidon<2>, (), (), (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 0>(&mut rng, int.clone(), [], Time::from(0), ((), ()), true, &pk, (), (), false).unwrap();
+    ///     let second = u.interact::<Poseidon<2>, (), (), (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 0>(&mut rng, int.clone(), [], Time::from(0), ((), ()), true, &pk, (), (), false).unwrap();
+    ///
+    ///     // The user posts under the same pseudonym in the forum.
+    ///     assert_eq!(first.pseudonym, second.pseudonym);
+    ///     assert_eq!(first.pseudonym, Some(derive_pseudonym::<Fr, Poseidon<2>>(u.zk_fields.secret, forum)));
+    ///
+    ///     <DummyStore as UserBul<Fr, Data>>::verify_interact_and_append::<_, Groth, 0>(&mut DummyStore, second.new_object, second.old_nullifier, PseudonymArgs((), second.pseudonym.unwrap()), second.cb_com_list, second.proof, None, &vk).unwrap();
+    /// }
+    /// ```
+    pub fn with_pseudonym(mut self, scope: Id<F>) -> Self {
+        self.pseudonym_scope = Some(scope);
+        self
+    }
+}
+
+/// The public arguments of an interaction, along with the pseudonym it outputs.
+///
+/// An interaction with a pseudonym scope (see [`Interaction::with_pseudonym`]) has the pseudonym
+/// of the user as a public input directly after its public arguments. Such an interaction is
+/// verified by giving `PseudonymArgs(args, pseudonym)` to the bulletin in place of the public
+/// arguments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PseudonymArgs<F: PrimeField, A>(pub A, pub Pseudonym<F>);
+
+impl<F: PrimeField, A: ToConstraintField<F>> ToConstraintField<F> for PseudonymArgs<F, A> {
+    fn to_field_elements(&self) -> Option<Vec<F>> {
+        let mut elems = self.0.to_field_elements()?;
+        elems.push(self.1);
+        Some(elems)
+    }
+}
+
+impl<
+    F: PrimeField + Absorb,
+    U: UserData<F> + Default,
+    PubArgs: Clone + Default + std::fmt::Debug,
+    PubArgsVar: AllocVar<PubArgs, F> + Clone,
+    PrivArgs: Clone + Default + std::fmt::Debug,
+    PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+    CBArgs: Clone + Default + std::fmt::Debug,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone,
+    const NUMCBS: usize,
+> Interaction<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, NUMCBS>
+where
+    Standard: Distribution<F>,
+{
+    /// Generate proving and verification keys for an interaction.
+    ///
+    /// If the public membership data is a constant, it must be encoded into the key, and so it
+    /// must be set in `memb_data`. Otherwise, the `memb_data` should be set to `None`.
+    ///
+    /// For `aux_data`, if the auxiliary public arguments to the interaction method is a constant,
+    /// this should be done in the `AllocVar` implementation of the auxiliary data type.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::user::User;
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use zk_callbacks::generic::interaction::Interaction;
+    /// # use zk_callbacks::generic::interaction::Callback;
+    /// # use zk_callbacks::generic::object::Id;
+    /// # use zk_callbacks::generic::object::Time;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use zk_callbacks::generic::user::UserVar;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::impls::centralized::crypto::{FakeSigPubkey, NoSigOTP};
+    /// # type Groth = Groth16<E>;
+    ///#  #[zk_object(Fr)]
+    ///#  #[derive(Default)]
+    ///#  struct Data {
+    ///#      karma: Fr,
+    ///#      is_banned: bool,
+    ///#  }
+    ///#
+    ///#  fn method<'a>(old_user: &'a User<Fr, Data>, _pub: (), _priv: ()) -> User<Fr, Data> {
     ///#      old_user.clone()
     ///#  }
     ///#
@@ -461,13 +1397,16 @@ where
     ///         method_id: Id::from(0),
     ///         expirable: false,
     ///         expiration: Time::from(10),
-    ///         method: callback,
-    ///         predicate: enforce_callback
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
     ///     };
     ///
     ///     let int = Interaction {
-    ///         meth: (method, predicate),
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
     ///         callbacks: [cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
     ///     };
     ///
     ///     let mut rng = thread_rng();
@@ -490,7 +1429,7 @@ where
         let u = User::create(U::default(), rng);
 
         let cbs: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] =
-            create_defaults((*self).clone(), <Time<F>>::default());
+            create_defaults(&self.callbacks, <Time<F>>::default());
 
         let x = (*self).clone();
 
@@ -518,6 +1457,7 @@ where
             pub_old_nul: u.zk_fields.nul,
             pub_issued_callback_coms: cbs.map(|x| x.commit::<H>()),
             pub_args: aux_data,
+            pub_pseudonym: Pseudonym::<F>::default(),
             associated_method: x,
             is_scan,
             bul_memb_is_const: memb_data.is_some(),
@@ -545,16 +1485,1172 @@ pub struct ExecMethodCircuit<
     const NUMCBS: usize,
 > {
     // Private Inputs
-    /// The old user object.
-    pub priv_old_user: User<F, U>,
-    /// The new user object.
-    pub priv_new_user: User<F, U>,
-    /// The issued callback tickets.
+    /// The old user object.
+    pub priv_old_user: User<F, U>,
+    /// The new user object.
+    pub priv_new_user: User<F, U>,
+    /// The issued callback tickets.
+    pub priv_issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS],
+    /// The membership witness for the old object.
+    pub priv_bul_membership_witness: Bul::MembershipWitness,
+    /// Private arguments to the associated method.
+    pub priv_args: PrivArgs,
+
+    // Public Inputs
+    /// The commitment to the new object.
+    pub pub_new_com: Com<F>,
+    /// The nullifier of the old object.
+    pub pub_old_nul: Nul<F>,
+    /// Commitments to the callback tickets.
+    pub pub_issued_callback_coms: [Com<F>; NUMCBS],
+    /// Public arguments to the associated method.
+    pub pub_args: PubArgs,
+    /// The pseudonym of the user, if the method has a pseudonym scope.
+    pub pub_pseudonym: Pseudonym<F>,
+    /// Public membership data for the old object.
+    pub pub_bul_membership_data: Bul::MembershipPub,
+    /// If the public membership data is constant.
+    pub bul_memb_is_const: bool,
+
+    /// The method.
+    pub associated_method:
+        Interaction<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, NUMCBS>,
+    /// If this circuit should remove checks for not scanning.
+    pub is_scan: bool,
+    /// The hash used for commitments.
+    pub _phantom_hash: PhantomData<H>,
+}
+
+impl<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F>,
+    PubArgs: Clone + std::fmt::Debug,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone + std::fmt::Debug,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    CBArgs: Clone + std::fmt::Debug,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    Bul: PublicUserBul<F, U>,
+    const NUMCBS: usize,
+> ConstraintSynthesizer<F>
+    for ExecMethodCircuit<
+        F,
+        H,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        CBArgs,
+        CBArgsVar,
+        Crypto,
+        Bul,
+        NUMCBS,
+    >
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> ArkResult<()> {
+        // Create private variables
+        let old_user_var = UserVar::new_witness(ns!(cs, "old_user"), || Ok(self.priv_old_user))?;
+        let new_user_var = UserVar::new_witness(ns!(cs, "new_user"), || Ok(self.priv_new_user))?;
+        let issued_cbs: ArrayVar<CallbackComVar<F, CBArgs, Crypto>, NUMCBS> =
+            ArrayVar::new_witness(ns!(cs, "issued_cbs"), || Ok(&self.priv_issued_callbacks))?;
+        let priv_bul_witness =
+            Bul::MembershipWitnessVar::new_witness(ns!(cs, "priv_bul_witness"), || {
+                Ok(&self.priv_bul_membership_witness)
+            })?;
+        let priv_args_var = PrivArgsVar::new_witness(ns!(cs, "priv_args"), || Ok(&self.priv_args))?;
+
+        // Create public variables
+        let new_com_var = ComVar::new_input(ns!(cs, "new_com"), || Ok(&self.pub_new_com))?;
+        let old_nul_var = NulVar::new_input(ns!(cs, "old_nul"), || Ok(&self.pub_old_nul))?;
+        let pub_args_var = PubArgsVar::new_input(ns!(cs, "pub_args"), || Ok(&self.pub_args))?;
+        let pseudonym_var = match self.associated_method.pseudonym_scope {
+            Some(_) => Some(PseudonymVar::new_input(ns!(cs, "pseudonym"), || {
+                Ok(&self.pub_pseudonym)
+            })?),
+            None => None,
+        };
+
+        let issued_cb_coms: ArrayVar<ComVar<F>, NUMCBS> =
+            ArrayVar::new_input(ns!(cs, "issued_cb_coms"), || {
+                Ok(&self.pub_issued_callback_coms)
+            })?;
+
+        let pub_bul_data = match self.bul_memb_is_const {
+            true => Bul::MembershipPubVar::new_constant(cs.clone(), &self.pub_bul_membership_data)?,
+            false => Bul::MembershipPubVar::new_input(ns!(cs, "pub_bul_data"), || {
+                Ok(&self.pub_bul_membership_data)
+            })?,
+        };
+
+        // Constraints are enforced in named namespaces, so that a failing check can be identified
+        // (see `User::dry_run_interact`).
+
+        // Enforce old_user in bulletin
+        {
+            let _ns = ns!(cs, "bul_membership");
+            Bul::enforce_membership_of(
+                User::commit_in_zk::<H>(old_user_var.clone())?,
+                priv_bul_witness,
+                pub_bul_data,
+            )?
+            .enforce_equal(&Boolean::TRUE)?;
+        }
+
+        // Enforce any method-specific predicates
+        {
+            let _ns = ns!(cs, "predicate");
+            let b = (self.associated_method.meth.1)(
+                &old_user_var,
+                &new_user_var,
+                pub_args_var,
+                priv_args_var,
+            )?;
+
+            b.enforce_equal(&Boolean::TRUE)?;
+        }
+
+        // Enforce that fields not modified by the method are unchanged
+        if let Some(unchanged) = &self.associated_method.unchanged {
+            let _ns = ns!(cs, "unchanged_fields");
+            unchanged
+                .enforce(&old_user_var, &new_user_var)?
+                .enforce_equal(&Boolean::TRUE)?;
+        }
+
+        let mut old_zk_fields = old_user_var.clone().zk_fields;
+        let new_zk_fields = new_user_var.clone().zk_fields;
+
+        // Enforce revealed nullifier (previous state) == the old nullifier
+        {
+            let _ns = ns!(cs, "old_nul");
+            old_nul_var.enforce_equal(&old_zk_fields.nul)?;
+        }
+
+        // Enforce the new nullifier is derived from the new commitment randomness
+        {
+            let _ns = ns!(cs, "new_nul");
+            new_zk_fields
+                .nul
+                .enforce_equal(&derive_nul_in_zk::<F, H>(&new_zk_fields.com_rand)?)?;
+        }
+
+        // Enforce the revealed pseudonym is that of the user in the scope
+        if let (Some(scope), Some(pseudonym_var)) =
+            (self.associated_method.pseudonym_scope, &pseudonym_var)
+        {
+            let _ns = ns!(cs, "pseudonym");
+            derive_pseudonym_in_zk::<F, H>(&old_zk_fields.secret, &FpVar::Constant(scope))?
+                .enforce_equal(pseudonym_var)?;
+        }
+
+        // Enforce we are currently not sweeping.
+        if !self.is_scan {
+            let _ns = ns!(cs, "not_scanning");
+            old_zk_fields.is_ingest_over.enforce_equal(&Boolean::TRUE)?;
+        }
+
+        if !self.is_scan {
+            let _ns = ns!(cs, "callbacks");
+            for i in 0..NUMCBS {
+                // An inert ticket (padding an interaction which issues fewer than NUMCBS
+                // callbacks) has the zero commitment, and is not added to the callback list
+                let is_issued = issued_cb_coms.0[i].is_neq(&FpVar::Constant(F::zero()))?;
+
+                // Tickets may not expire before the last full scan, as scanning drops such
+                // tickets without checking the bulletin
+                issued_cbs.0[i]
+                    .cb_entry
+                    .expired_before(&old_zk_fields.last_full_scan)?
+                    .conditional_enforce_equal(&Boolean::FALSE, &is_issued)?;
+
+                // Enforce that the callback commitments are well-formed. If method ids are hidden,
+                // the commitment is to the callback with a blinded method id, and the hidden
+                // method id must be allowed
+                let com = match &self.associated_method.hidden_ids {
+                    Some(ids) => {
+                        let mut is_allowed = Boolean::FALSE;
+                        for id in ids {
+                            is_allowed |= issued_cbs.0[i]
+                                .cb_entry
+                                .cb_method_id
+                                .is_eq(&FpVar::Constant(*id))?;
+                        }
+                        is_allowed.conditional_enforce_equal(&Boolean::TRUE, &is_issued)?;
+
+                        CallbackCom::commit_in_zk::<H>(CallbackCom::blind_method_id_in_zk::<H>(
+                            issued_cbs.0[i].clone(),
+                        )?)?
+                    }
+                    None => CallbackCom::commit_in_zk::<H>(issued_cbs.0[i].clone())?,
+                };
+                issued_cb_coms.0[i].conditional_enforce_equal(&com, &is_issued)?;
+
+                // Append callbacks to the callback list
+                let mut appended = old_zk_fields.callback_hash.clone();
+                add_ticket_to_hc_zk::<F, H, CBArgs, Crypto>(
+                    &mut appended,
+                    issued_cbs.0[i].clone().cb_entry,
+                )?;
+                old_zk_fields.callback_hash = FpVar::conditionally_select(
+                    &is_issued,
+                    &appended,
+                    &old_zk_fields.callback_hash,
+                )?;
+            }
+
+            old_zk_fields.old_in_progress_callback_hash = old_zk_fields.callback_hash.clone();
+
+            // Enforce new == the updated states
+            new_zk_fields
+                .callback_hash
+                .enforce_equal(&old_zk_fields.callback_hash)?;
+
+            new_zk_fields
+                .old_in_progress_callback_hash
+                .enforce_equal(&old_zk_fields.old_in_progress_callback_hash)?;
+
+            new_zk_fields
+                .new_in_progress_callback_hash
+                .enforce_equal(&old_zk_fields.new_in_progress_callback_hash)?;
+
+            new_zk_fields
+                .is_ingest_over
+                .enforce_equal(&old_zk_fields.is_ingest_over)?;
+
+            new_zk_fields
+                .scan_start
+                .enforce_equal(&old_zk_fields.scan_start)?;
+
+            new_zk_fields
+                .last_full_scan
+                .enforce_equal(&old_zk_fields.last_full_scan)?;
+
+            new_zk_fields.secret.enforce_equal(&old_zk_fields.secret)?;
+        }
+
+        // Enforce that Com(new_user) == new_com
+        let _ns = ns!(cs, "new_com");
+        let com = User::commit_in_zk::<H>(new_user_var)?;
+
+        new_com_var.enforce_equal(&com)?;
+
+        Ok(())
+    }
+}
+
+impl<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F> + Clone,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    Bul: PublicUserBul<F, U>,
+    const NUMCBS: usize,
+> Clone
+    for ExecMethodCircuit<
+        F,
+        H,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        CBArgs,
+        CBArgsVar,
+        Crypto,
+        Bul,
+        NUMCBS,
+    >
+{
+    fn clone(&self) -> Self {
+        Self {
+            priv_old_user: self.priv_old_user.clone(),
+            priv_new_user: self.priv_new_user.clone(),
+            priv_issued_callbacks: self.priv_issued_callbacks.clone(),
+            priv_bul_membership_witness: self.priv_bul_membership_witness.clone(),
+            priv_args: self.priv_args.clone(),
+
+            pub_new_com: self.pub_new_com,
+            pub_old_nul: self.pub_old_nul,
+            pub_issued_callback_coms: self.pub_issued_callback_coms,
+            pub_args: self.pub_args.clone(),
+            pub_pseudonym: self.pub_pseudonym,
+            pub_bul_membership_data: self.pub_bul_membership_data.clone(),
+            bul_memb_is_const: self.bul_memb_is_const,
+
+            is_scan: self.is_scan,
+            associated_method: self.associated_method.clone(),
+            _phantom_hash: self._phantom_hash,
+        }
+    }
+}
+
+/// A method on several users at once.
+///
+/// This is a function `f([U_1, ..., U_N], args) -> [U_1', ..., U_N']`, updating all users of a
+/// [`MultiInteraction`] together. For example, a reputation transfer takes reputation from one
+/// user and gives it to another.
+pub type MultiMethod<User, PubArgs, PrivArgs, const N: usize> =
+    Arc<dyn Fn(&[User; N], PubArgs, PrivArgs) -> [User; N] + Send + Sync>;
+
+/// A joint predicate on several users at once.
+///
+/// This is a function `p([U_1, ..., U_N], [U_1', ..., U_N'], A, B) -> bool`, which outputs true
+/// if the old users and new users jointly satisfy some predicate.
+pub type MultiPredicate<F, UserVar, PubArgsVar, PrivArgsVar, const N: usize> = Arc<
+    dyn for<'a> Fn(
+            &'a [UserVar; N],
+            &'a [UserVar; N],
+            PubArgsVar,
+            PrivArgsVar,
+        ) -> ArkResult<Boolean<F>>
+        + Send
+        + Sync,
+>;
+
+/// An interaction between `NUMUSERS` users, proven in a single proof.
+///
+/// This is the analogue of an [`Interaction`] for several users: the method is executed on all
+/// users together, a joint predicate is enforced on the old and new users, and each user is
+/// issued its own list of `NUMCBS` callbacks. The proof reveals the nullifier of every old user
+/// and the commitment of every new user, and the bulletin accepts or rejects all of them together
+/// (see [`UserBul::verify_multi_interact_and_append`](super::bulletin::UserBul::verify_multi_interact_and_append)).
+///
+/// This may be used for reputation transfers, vouching, or trades between users. Note that the
+/// proof is made by [`User::interact_multi`] with all of the users, so all users must be known to
+/// the prover.
+///
+/// Method ids may not be hidden in a multi-user interaction.
+#[derive(Clone)]
+pub struct MultiInteraction<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    const NUMUSERS: usize,
+    const NUMCBS: usize,
+> {
+    /// A method on all users, and a joint predicate enforced on the update.
+    pub meth: (
+        MultiMethod<User<F, U>, PubArgs, PrivArgs, NUMUSERS>,
+        MultiPredicate<F, UserVar<F, U>, PubArgsVar, PrivArgsVar, NUMUSERS>,
+    ),
+    /// A list of callbacks for each user.
+    pub callbacks: [CallbackList<F, U, CBArgs, CBArgsVar, NUMCBS>; NUMUSERS],
+    /// An optional condition enforcing that fields the method does not modify are unchanged, for
+    /// each user.
+    ///
+    /// See [`MultiInteraction::modifying`].
+    pub unchanged: Option<UserPredicate<F, U>>,
+}
+
+impl<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    const NUMUSERS: usize,
+    const NUMCBS: usize,
+>
+    MultiInteraction<
+        F,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        CBArgs,
+        CBArgsVar,
+        NUMUSERS,
+        NUMCBS,
+    >
+{
+    /// Create a multi-user interaction from a method on all users, a joint predicate enforced on
+    /// the method, and a list of callbacks for each user.
+    ///
+    /// See [`User::interact_multi`] for an example.
+    pub fn new(
+        method: impl Fn(&[User<F, U>; NUMUSERS], PubArgs, PrivArgs) -> [User<F, U>; NUMUSERS]
+        + Send
+        + Sync
+        + 'static,
+        predicate: impl for<'a> Fn(
+            &'a [UserVar<F, U>; NUMUSERS],
+            &'a [UserVar<F, U>; NUMUSERS],
+            PubArgsVar,
+            PrivArgsVar,
+        ) -> ArkResult<Boolean<F>>
+        + Send
+        + Sync
+        + 'static,
+        callbacks: [CallbackList<F, U, CBArgs, CBArgsVar, NUMCBS>; NUMUSERS],
+    ) -> Self {
+        Self {
+            meth: (Arc::new(method), Arc::new(predicate)),
+            callbacks,
+            unchanged: None,
+        }
+    }
+
+    /// Declare the fields of the user data which the method may modify.
+    ///
+    /// Every other field is then enforced to be the same in the old and new version of each user.
+    /// See [`Interaction::modifying`].
+    pub fn modifying(mut self, fields: &[UserField<F, U>]) -> Self
+    where
+        U: UserFields<F>,
+    {
+        self.unchanged = Some(UserPredicate::unchanged_except(fields));
+        self
+    }
+}
+
+impl<
+    F: PrimeField + Absorb,
+    U: UserData<F> + Default,
+    PubArgs: Clone + Default + std::fmt::Debug,
+    PubArgsVar: AllocVar<PubArgs, F> + Clone,
+    PrivArgs: Clone + Default + std::fmt::Debug,
+    PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+    CBArgs: Clone + Default + std::fmt::Debug,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone,
+    const NUMUSERS: usize,
+    const NUMCBS: usize,
+>
+    MultiInteraction<
+        F,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        CBArgs,
+        CBArgsVar,
+        NUMUSERS,
+        NUMCBS,
+    >
+where
+    Standard: Distribution<F>,
+{
+    /// Generate proving and verification keys for a multi-user interaction.
+    ///
+    /// If the public membership data is a constant, it must be encoded into the key, and so the
+    /// membership data for each user must be set in `memb_data`. Otherwise, the `memb_data`
+    /// should be set to `None`.
+    ///
+    /// See [`Interaction::generate_keys`].
+    pub fn generate_keys<
+        H: FieldHash<F>,
+        Snark: SNARK<F>,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        Bul: PublicUserBul<F, U>,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        memb_data: Option<[Bul::MembershipPub; NUMUSERS]>,
+        aux_data: PubArgs,
+    ) -> (Snark::ProvingKey, Snark::VerifyingKey) {
+        let users: [User<F, U>; NUMUSERS] =
+            core::array::from_fn(|_| User::create(U::default(), rng));
+
+        let cbs: [[CallbackCom<F, CBArgs, Crypto>; NUMCBS]; NUMUSERS] =
+            core::array::from_fn(|i| create_defaults(&self.callbacks[i], <Time<F>>::default()));
+
+        let out: MultiExecMethodCircuit<
+            F,
+            H,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            Crypto,
+            Bul,
+            NUMUSERS,
+            NUMCBS,
+        > = MultiExecMethodCircuit {
+            priv_old_users: users.clone(),
+            priv_new_users: users.clone(),
+            priv_issued_callbacks: cbs.clone(),
+            priv_bul_membership_witnesses: core::array::from_fn(|_| {
+                Bul::MembershipWitness::default()
+            }),
+            priv_args: PrivArgs::default(),
+
+            pub_new_coms: core::array::from_fn(|i| users[i].commit::<H>()),
+            pub_old_nuls: core::array::from_fn(|i| users[i].zk_fields.nul),
+            pub_issued_callback_coms: cbs.map(|l| l.map(|x| x.commit::<H>())),
+            pub_args: aux_data,
+            bul_memb_is_const: memb_data.is_some(),
+            pub_bul_membership_data: memb_data
+                .unwrap_or_else(|| core::array::from_fn(|_| Bul::MembershipPub::default())),
+
+            associated_method: self.clone(),
+            _phantom_hash: PhantomData,
+        };
+
+        Snark::circuit_specific_setup(out, rng).unwrap()
+    }
+}
+
+/// The circuit used to generate proofs of a multi-user interaction. This is not necessary for use with the base system.
+pub struct MultiExecMethodCircuit<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    Bul: PublicUserBul<F, U>,
+    const NUMUSERS: usize,
+    const NUMCBS: usize,
+> {
+    // Private Inputs
+    /// The old user objects.
+    pub priv_old_users: [User<F, U>; NUMUSERS],
+    /// The new user objects.
+    pub priv_new_users: [User<F, U>; NUMUSERS],
+    /// The callback tickets issued to each user.
+    pub priv_issued_callbacks: [[CallbackCom<F, CBArgs, Crypto>; NUMCBS]; NUMUSERS],
+    /// The membership witnesses for the old objects.
+    pub priv_bul_membership_witnesses: [Bul::MembershipWitness; NUMUSERS],
+    /// Private arguments to the associated method.
+    pub priv_args: PrivArgs,
+
+    // Public Inputs
+    /// The commitments to the new objects.
+    pub pub_new_coms: [Com<F>; NUMUSERS],
+    /// The nullifiers of the old objects.
+    pub pub_old_nuls: [Nul<F>; NUMUSERS],
+    /// Commitments to the callback tickets issued to each user.
+    pub pub_issued_callback_coms: [[Com<F>; NUMCBS]; NUMUSERS],
+    /// Public arguments to the associated method.
+    pub pub_args: PubArgs,
+    /// Public membership data for the old objects.
+    pub pub_bul_membership_data: [Bul::MembershipPub; NUMUSERS],
+    /// If the public membership data is constant.
+    pub bul_memb_is_const: bool,
+
+    /// The method.
+    pub associated_method: MultiInteraction<
+        F,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        CBArgs,
+        CBArgsVar,
+        NUMUSERS,
+        NUMCBS,
+    >,
+    /// The hash used for commitments.
+    pub _phantom_hash: PhantomData<H>,
+}
+
+impl<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F>,
+    PubArgs: Clone + std::fmt::Debug,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone + std::fmt::Debug,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    CBArgs: Clone + std::fmt::Debug,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    Bul: PublicUserBul<F, U>,
+    const NUMUSERS: usize,
+    const NUMCBS: usize,
+> ConstraintSynthesizer<F>
+    for MultiExecMethodCircuit<
+        F,
+        H,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        CBArgs,
+        CBArgsVar,
+        Crypto,
+        Bul,
+        NUMUSERS,
+        NUMCBS,
+    >
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> ArkResult<()> {
+        // Create private variables
+        let old_users: ArrayVar<UserVar<F, U>, NUMUSERS> =
+            ArrayVar::new_witness(ns!(cs, "old_users"), || Ok(&self.priv_old_users))?;
+        let new_users: ArrayVar<UserVar<F, U>, NUMUSERS> =
+            ArrayVar::new_witness(ns!(cs, "new_users"), || Ok(&self.priv_new_users))?;
+        let issued_cbs: ArrayVar<ArrayVar<CallbackComVar<F, CBArgs, Crypto>, NUMCBS>, NUMUSERS> =
+            ArrayVar::new_witness(ns!(cs, "issued_cbs"), || Ok(&self.priv_issued_callbacks))?;
+        let priv_bul_witnesses: ArrayVar<Bul::MembershipWitnessVar, NUMUSERS> =
+            ArrayVar::new_witness(ns!(cs, "priv_bul_witnesses"), || {
+                Ok(&self.priv_bul_membership_witnesses)
+            })?;
+        let priv_args_var = PrivArgsVar::new_witness(ns!(cs, "priv_args"), || Ok(&self.priv_args))?;
+
+        // Create public variables, in the same order as a single user interaction
+        let new_com_vars: ArrayVar<ComVar<F>, NUMUSERS> =
+            ArrayVar::new_input(ns!(cs, "new_coms"), || Ok(&self.pub_new_coms))?;
+        let old_nul_vars: ArrayVar<NulVar<F>, NUMUSERS> =
+            ArrayVar::new_input(ns!(cs, "old_nuls"), || Ok(&self.pub_old_nuls))?;
+        let pub_args_var = PubArgsVar::new_input(ns!(cs, "pub_args"), || Ok(&self.pub_args))?;
+
+        let issued_cb_coms: ArrayVar<ArrayVar<ComVar<F>, NUMCBS>, NUMUSERS> =
+            ArrayVar::new_input(ns!(cs, "issued_cb_coms"), || {
+                Ok(&self.pub_issued_callback_coms)
+            })?;
+
+        let pub_bul_data: ArrayVar<Bul::MembershipPubVar, NUMUSERS> =
+            match self.bul_memb_is_const {
+                true => ArrayVar::new_constant(cs.clone(), &self.pub_bul_membership_data)?,
+                false => ArrayVar::new_input(ns!(cs, "pub_bul_data"), || {
+                    Ok(&self.pub_bul_membership_data)
+                })?,
+            };
+
+        // Enforce the joint predicate
+        {
+            let _ns = ns!(cs, "predicate");
+            let b = (self.associated_method.meth.1)(
+                &old_users.0,
+                &new_users.0,
+                pub_args_var,
+                priv_args_var,
+            )?;
+
+            b.enforce_equal(&Boolean::TRUE)?;
+        }
+
+        for i in 0..NUMUSERS {
+            let old_user_var = &old_users.0[i];
+            let new_user_var = &new_users.0[i];
+
+            // Enforce old_user in bulletin
+            {
+                let _ns = ns!(cs, "bul_membership");
+                Bul::enforce_membership_of(
+                    User::commit_in_zk::<H>(old_user_var.clone())?,
+                    priv_bul_witnesses.0[i].clone(),
+                    pub_bul_data.0[i].clone(),
+                )?
+                .enforce_equal(&Boolean::TRUE)?;
+            }
+
+            // Enforce that fields not modified by the method are unchanged
+            if let Some(unchanged) = &self.associated_method.unchanged {
+                let _ns = ns!(cs, "unchanged_fields");
+                unchanged
+                    .enforce(old_user_var, new_user_var)?
+                    .enforce_equal(&Boolean::TRUE)?;
+            }
+
+            let mut old_zk_fields = old_user_var.zk_fields.clone();
+            let new_zk_fields = &new_user_var.zk_fields;
+
+            // Enforce revealed nullifier (previous state) == the old nullifier
+            {
+                let _ns = ns!(cs, "old_nul");
+                old_nul_vars.0[i].enforce_equal(&old_zk_fields.nul)?;
+            }
+
+            // Enforce the new nullifier is derived from the new commitment randomness
+            {
+                let _ns = ns!(cs, "new_nul");
+                new_zk_fields
+                    .nul
+                    .enforce_equal(&derive_nul_in_zk::<F, H>(&new_zk_fields.com_rand)?)?;
+            }
+
+            // Enforce we are currently not sweeping.
+            {
+                let _ns = ns!(cs, "not_scanning");
+                old_zk_fields.is_ingest_over.enforce_equal(&Boolean::TRUE)?;
+            }
+
+            {
+                let _ns = ns!(cs, "callbacks");
+                for j in 0..NUMCBS {
+                    let cb = &issued_cbs.0[i].0[j];
+
+                    // Tickets may not expire before the last full scan
+                    cb.cb_entry
+                        .expired_before(&old_zk_fields.last_full_scan)?
+                        .enforce_equal(&Boolean::FALSE)?;
+
+                    // Enforce that the callback commitments are well-formed
+                    issued_cb_coms.0[i].0[j]
+                        .enforce_equal(&CallbackCom::commit_in_zk::<H>(cb.clone())?)?;
+
+                    // Append callbacks to the callback list
+                    add_ticket_to_hc_zk::<F, H, CBArgs, Crypto>(
+                        &mut old_zk_fields.callback_hash,
+                        cb.clone().cb_entry,
+                    )?;
+                }
+
+                old_zk_fields.old_in_progress_callback_hash = old_zk_fields.callback_hash.clone();
+
+                // Enforce new == the updated states
+                new_zk_fields
+                    .callback_hash
+                    .enforce_equal(&old_zk_fields.callback_hash)?;
+
+                new_zk_fields
+                    .old_in_progress_callback_hash
+                    .enforce_equal(&old_zk_fields.old_in_progress_callback_hash)?;
+
+                new_zk_fields
+                    .new_in_progress_callback_hash
+                    .enforce_equal(&old_zk_fields.new_in_progress_callback_hash)?;
+
+                new_zk_fields
+                    .is_ingest_over
+                    .enforce_equal(&old_zk_fields.is_ingest_over)?;
+
+                new_zk_fields
+                    .scan_start
+                    .enforce_equal(&old_zk_fields.scan_start)?;
+
+                new_zk_fields
+                    .last_full_scan
+                    .enforce_equal(&old_zk_fields.last_full_scan)?;
+
+                new_zk_fields.secret.enforce_equal(&old_zk_fields.secret)?;
+            }
+
+            // Enforce that Com(new_user) == new_com
+            {
+                let _ns = ns!(cs, "new_com");
+                let com = User::commit_in_zk::<H>(new_user_var.clone())?;
+
+                new_com_vars.0[i].enforce_equal(&com)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F> + Clone,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    Bul: PublicUserBul<F, U>,
+    const NUMUSERS: usize,
+    const NUMCBS: usize,
+> Clone
+    for MultiExecMethodCircuit<
+        F,
+        H,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        CBArgs,
+        CBArgsVar,
+        Crypto,
+        Bul,
+        NUMUSERS,
+        NUMCBS,
+    >
+{
+    fn clone(&self) -> Self {
+        Self {
+            priv_old_users: self.priv_old_users.clone(),
+            priv_new_users: self.priv_new_users.clone(),
+            priv_issued_callbacks: self.priv_issued_callbacks.clone(),
+            priv_bul_membership_witnesses: self.priv_bul_membership_witnesses.clone(),
+            priv_args: self.priv_args.clone(),
+
+            pub_new_coms: self.pub_new_coms,
+            pub_old_nuls: self.pub_old_nuls,
+            pub_issued_callback_coms: self.pub_issued_callback_coms,
+            pub_args: self.pub_args.clone(),
+            pub_bul_membership_data: self.pub_bul_membership_data.clone(),
+            bul_memb_is_const: self.bul_memb_is_const,
+
+            associated_method: self.associated_method.clone(),
+            _phantom_hash: self._phantom_hash,
+        }
+    }
+}
+
+/// The public arguments of a [`ChainedInteraction`], as field elements.
+///
+/// These are the public arguments of each step of the chain in order, and are passed to the
+/// bulletin in place of the public arguments of a single interaction (see
+/// [`ChainedInteraction::pub_args`]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChainArgs<F: PrimeField>(pub Vec<F>);
+
+impl<F: PrimeField> ToConstraintField<F> for ChainArgs<F> {
+    fn to_field_elements(&self) -> Option<Vec<F>> {
+        Some(self.0.clone())
+    }
+}
+
+type ChainMethod<F, U> = Arc<dyn Fn(&User<F, U>) -> User<F, U> + Send + Sync>;
+
+type ChainPredicate<F, U> = Arc<
+    dyn Fn(ConstraintSystemRef<F>, &UserVar<F, U>, &UserVar<F, U>) -> ArkResult<Boolean<F>>
+        + Send
+        + Sync,
+>;
+
+/// A step of a [`ChainedInteraction`]: an interaction with its arguments bound.
+#[derive(Clone)]
+pub(crate) struct ChainStep<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+> {
+    /// The method, applied with the bound arguments.
+    pub(crate) method: ChainMethod<F, U>,
+    /// Allocates the bound arguments, and enforces the predicate (and unchanged fields).
+    pub(crate) predicate: ChainPredicate<F, U>,
+    /// The public arguments, as field elements.
+    pub(crate) pub_args: Vec<F>,
+    /// The callbacks issued by this step.
+    pub(crate) callbacks: Vec<Callback<F, U, CBArgs, CBArgsVar>>,
+    /// If this step is a scan.
+    pub(crate) is_scan: bool,
+}
+
+/// Several interactions, applied one after the other to a user in a single proof.
+///
+/// Clients often perform several interactions back to back, for example scanning and then
+/// posting. Proving each interaction separately costs a proof and a nullifier for each. A chained
+/// interaction instead applies each interaction in turn to the user, enforcing the predicate of
+/// each on the intermediate users, and outputs a single nullifier, a single new commitment, and
+/// the callback tickets of every interaction concatenated in order.
+///
+/// Each step is added with its public and private arguments, with [`ChainedInteraction::then`],
+/// or with [`ChainedInteraction::then_scan`] for the scan interaction of
+/// [`get_scan_interaction`]. A chain of `NUMCBS` callbacks in total is proven with
+/// [`User::interact_chain`], and verified like any other interaction, with the public arguments
+/// given by [`ChainedInteraction::pub_args`].
+///
+/// As the arguments are bound when building the chain, the prover and verifier must build the
+/// chain with the same public arguments (the verifier may use any private arguments). Method ids
+/// may not be hidden, and pseudonyms may not be output, in a chained interaction.
+#[derive(Clone)]
+pub struct ChainedInteraction<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+> {
+    pub(crate) steps: Vec<ChainStep<F, U, CBArgs, CBArgsVar>>,
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>, CBArgs: Clone, CBArgsVar: AllocVar<CBArgs, F>> Default
+    for ChainedInteraction<F, U, CBArgs, CBArgsVar>
+{
+    fn default() -> Self {
+        Self { steps: vec![] }
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>, CBArgs: Clone, CBArgsVar: AllocVar<CBArgs, F> + Clone>
+    ChainedInteraction<F, U, CBArgs, CBArgsVar>
+{
+    /// The number of steps in the chain.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Whether the chain has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The total number of callbacks issued by the chain.
+    pub fn num_callbacks(&self) -> usize {
+        self.steps.iter().map(|step| step.callbacks.len()).sum()
+    }
+
+    /// The callbacks issued by each step of the chain, concatenated in order.
+    pub fn callbacks(&self) -> Vec<Callback<F, U, CBArgs, CBArgsVar>> {
+        self.steps
+            .iter()
+            .flat_map(|step| step.callbacks.iter().cloned())
+            .collect()
+    }
+
+    /// The public arguments of each step of the chain, concatenated in order.
+    ///
+    /// These are the public arguments to pass to the bulletin when verifying the chain.
+    pub fn pub_args(&self) -> ChainArgs<F> {
+        ChainArgs(
+            self.steps
+                .iter()
+                .flat_map(|step| step.pub_args.iter().copied())
+                .collect(),
+        )
+    }
+}
+
+impl<
+    F: PrimeField + Absorb,
+    U: UserData<F> + 'static,
+    CBArgs: Clone + 'static,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+> ChainedInteraction<F, U, CBArgs, CBArgsVar>
+{
+    /// Create an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an interaction to the chain, with its public and private arguments.
+    ///
+    /// Returns `None` if the public arguments cannot be converted to field elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interaction hides its method ids, or outputs a pseudonym.
+    pub fn then<
+        PubArgs: Clone + ToConstraintField<F> + Send + Sync + 'static,
+        PubArgsVar: AllocVar<PubArgs, F> + 'static,
+        PrivArgs: Clone + Send + Sync + 'static,
+        PrivArgsVar: AllocVar<PrivArgs, F> + 'static,
+        const NUMCBS: usize,
+    >(
+        self,
+        interaction: Interaction<
+            F,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            NUMCBS,
+        >,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+    ) -> Option<Self>
+    where
+        F: 'static,
+    {
+        self.push(interaction, pub_args, priv_args, false)
+    }
+
+    /// Append a scan to the chain, with its public and private arguments.
+    ///
+    /// The interaction should be the scan interaction from [`get_scan_interaction`], and the
+    /// arguments those of the user *before* this step (see
+    /// [`User::get_scan_arguments`]).
+    ///
+    /// Returns `None` if the public arguments cannot be converted to field elements.
+    pub fn then_scan<
+        PubArgs: Clone + ToConstraintField<F> + Send + Sync + 'static,
+        PubArgsVar: AllocVar<PubArgs, F> + 'static,
+        PrivArgs: Clone + Send + Sync + 'static,
+        PrivArgsVar: AllocVar<PrivArgs, F> + 'static,
+    >(
+        self,
+        interaction: Interaction<
+            F,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            0,
+        >,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+    ) -> Option<Self>
+    where
+        F: 'static,
+    {
+        self.push(interaction, pub_args, priv_args, true)
+    }
+
+    fn push<
+        PubArgs: Clone + ToConstraintField<F> + Send + Sync + 'static,
+        PubArgsVar: AllocVar<PubArgs, F> + 'static,
+        PrivArgs: Clone + Send + Sync + 'static,
+        PrivArgsVar: AllocVar<PrivArgs, F> + 'static,
+        const NUMCBS: usize,
+    >(
+        mut self,
+        interaction: Interaction<
+            F,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            NUMCBS,
+        >,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+        is_scan: bool,
+    ) -> Option<Self>
+    where
+        F: 'static,
+    {
+        assert!(
+            interaction.hidden_ids.is_none(),
+            "method ids may not be hidden in a chained interaction"
+        );
+        assert!(
+            interaction.pseudonym_scope.is_none(),
+            "pseudonyms may not be output in a chained interaction"
+        );
+
+        let (method, predicate) = interaction.meth;
+        let unchanged = interaction.unchanged;
+        let pub_elements = pub_args.to_field_elements()?;
+
+        let (method_pub, method_priv) = (pub_args.clone(), priv_args.clone());
+
+        self.steps.push(ChainStep {
+            method: Arc::new(move |user: &User<F, U>| {
+                (method)(user, method_pub.clone(), method_priv.clone())
+            }),
+            predicate: Arc::new(
+                move |cs: ConstraintSystemRef<F>,
+                      old: &UserVar<F, U>,
+                      new: &UserVar<F, U>|
+                      -> ArkResult<Boolean<F>> {
+                    let pub_args_var =
+                        PubArgsVar::new_input(ns!(cs, "pub_args"), || Ok(pub_args.clone()))?;
+                    let priv_args_var =
+                        PrivArgsVar::new_witness(ns!(cs, "priv_args"), || Ok(priv_args.clone()))?;
+
+                    let mut b = (predicate)(old, new, pub_args_var, priv_args_var)?;
+                    if let Some(unchanged) = &unchanged {
+                        b &= unchanged.enforce(old, new)?;
+                    }
+                    Ok(b)
+                },
+            ),
+            pub_args: pub_elements,
+            callbacks: interaction.callbacks.to_vec(),
+            is_scan,
+        });
+
+        Some(self)
+    }
+
+    /// Generate proving and verification keys for a chained interaction issuing `NUMCBS`
+    /// callbacks.
+    ///
+    /// If the public membership data is a constant, it must be encoded into the key, and so it
+    /// must be set in `memb_data`. Otherwise, the `memb_data` should be set to `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chain does not issue exactly `NUMCBS` callbacks.
+    pub fn generate_keys<
+        H: FieldHash<F>,
+        Snark: SNARK<F>,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        Bul: PublicUserBul<F, U>,
+        const NUMCBS: usize,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        memb_data: Option<Bul::MembershipPub>,
+    ) -> (Snark::ProvingKey, Snark::VerifyingKey)
+    where
+        U: Default,
+        CBArgs: std::fmt::Debug,
+        Standard: Distribution<F>,
+    {
+        assert_eq!(
+            self.num_callbacks(),
+            NUMCBS,
+            "the chain must issue exactly NUMCBS callbacks"
+        );
+
+        let u = User::create(U::default(), rng);
+
+        let cbs: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] =
+            create_defaults(&self.callbacks(), <Time<F>>::default());
+
+        let out: ChainedExecMethodCircuit<F, H, U, CBArgs, CBArgsVar, Crypto, Bul, NUMCBS> =
+            ChainedExecMethodCircuit {
+                priv_users: vec![u.clone(); self.steps.len() + 1],
+                priv_issued_callbacks: cbs.clone(),
+                priv_bul_membership_witness: Bul::MembershipWitness::default(),
+
+                pub_new_com: u.commit::<H>(),
+                pub_old_nul: u.zk_fields.nul,
+                pub_issued_callback_coms: cbs.map(|x| x.commit::<H>()),
+                bul_memb_is_const: memb_data.is_some(),
+                pub_bul_membership_data: memb_data.unwrap_or_default(),
+
+                associated_chain: self.clone(),
+                _phantom_hash: PhantomData,
+            };
+
+        Snark::circuit_specific_setup(out, rng).unwrap()
+    }
+}
+
+/// The circuit used to generate proofs of a chained interaction. This is not necessary for use with the base system.
+pub struct ChainedExecMethodCircuit<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    Bul: PublicUserBul<F, U>,
+    const NUMCBS: usize,
+> {
+    // Private Inputs
+    /// The old user object, followed by the user after each step of the chain.
+    pub priv_users: Vec<User<F, U>>,
+    /// The issued callback tickets of every step, in order.
     pub priv_issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS],
     /// The membership witness for the old object.
     pub priv_bul_membership_witness: Bul::MembershipWitness,
-    /// Private arguments to the associated method.
-    pub priv_args: PrivArgs,
 
     // Public Inputs
     /// The commitment to the new object.
@@ -563,18 +2659,13 @@ pub struct ExecMethodCircuit<
     pub pub_old_nul: Nul<F>,
     /// Commitments to the callback tickets.
     pub pub_issued_callback_coms: [Com<F>; NUMCBS],
-    /// Public arguments to the associated method.
-    pub pub_args: PubArgs,
     /// Public membership data for the old object.
     pub pub_bul_membership_data: Bul::MembershipPub,
     /// If the public membership data is constant.
     pub bul_memb_is_const: bool,
 
-    /// The method.
-    pub associated_method:
-        Interaction<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, NUMCBS>,
-    /// If this circuit should remove checks for not scanning.
-    pub is_scan: bool,
+    /// The chain of interactions, with their arguments.
+    pub associated_chain: ChainedInteraction<F, U, CBArgs, CBArgsVar>,
     /// The hash used for commitments.
     pub _phantom_hash: PhantomData<H>,
 }
@@ -583,47 +2674,44 @@ impl<
     F: PrimeField + Absorb,
     H: FieldHash<F>,
     U: UserData<F>,
-    PubArgs: Clone + std::fmt::Debug,
-    PubArgsVar: AllocVar<PubArgs, F>,
-    PrivArgs: Clone + std::fmt::Debug,
-    PrivArgsVar: AllocVar<PrivArgs, F>,
     CBArgs: Clone + std::fmt::Debug,
     CBArgsVar: AllocVar<CBArgs, F>,
     Crypto: AECipherSigZK<F, CBArgs>,
     Bul: PublicUserBul<F, U>,
     const NUMCBS: usize,
 > ConstraintSynthesizer<F>
-    for ExecMethodCircuit<
-        F,
-        H,
-        U,
-        PubArgs,
-        PubArgsVar,
-        PrivArgs,
-        PrivArgsVar,
-        CBArgs,
-        CBArgsVar,
-        Crypto,
-        Bul,
-        NUMCBS,
-    >
+    for ChainedExecMethodCircuit<F, H, U, CBArgs, CBArgsVar, Crypto, Bul, NUMCBS>
 {
     fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> ArkResult<()> {
         // Create private variables
-        let old_user_var = UserVar::new_witness(ns!(cs, "old_user"), || Ok(self.priv_old_user))?;
-        let new_user_var = UserVar::new_witness(ns!(cs, "new_user"), || Ok(self.priv_new_user))?;
+        let users = self
+            .priv_users
+            .iter()
+            .map(|u| UserVar::new_witness(ns!(cs, "user"), || Ok(u)))
+            .collect::<ArkResult<Vec<UserVar<F, U>>>>()?;
         let issued_cbs: ArrayVar<CallbackComVar<F, CBArgs, Crypto>, NUMCBS> =
             ArrayVar::new_witness(ns!(cs, "issued_cbs"), || Ok(&self.priv_issued_callbacks))?;
         let priv_bul_witness =
             Bul::MembershipWitnessVar::new_witness(ns!(cs, "priv_bul_witness"), || {
                 Ok(&self.priv_bul_membership_witness)
             })?;
-        let priv_args_var = PrivArgsVar::new_witness(ns!(cs, "priv_args"), || Ok(&self.priv_args))?;
 
-        // Create public variables
+        let steps = &self.associated_chain.steps;
+        if users.len() != steps.len() + 1 {
+            return Err(SynthesisError::AssignmentMissing);
+        }
+
+        // Create public variables. The public arguments of each step are allocated by its
+        // predicate, in order.
         let new_com_var = ComVar::new_input(ns!(cs, "new_com"), || Ok(&self.pub_new_com))?;
         let old_nul_var = NulVar::new_input(ns!(cs, "old_nul"), || Ok(&self.pub_old_nul))?;
-        let pub_args_var = PubArgsVar::new_input(ns!(cs, "pub_args"), || Ok(&self.pub_args))?;
+
+        // Enforce the predicate of each step on the users before and after it
+        for (k, step) in steps.iter().enumerate() {
+            let _ns = ns!(cs, "predicate");
+            (step.predicate)(cs.clone(), &users[k], &users[k + 1])?
+                .enforce_equal(&Boolean::TRUE)?;
+        }
 
         let issued_cb_coms: ArrayVar<ComVar<F>, NUMCBS> =
             ArrayVar::new_input(ns!(cs, "issued_cb_coms"), || {
@@ -638,46 +2726,67 @@ impl<
         };
 
         // Enforce old_user in bulletin
-        Bul::enforce_membership_of(
-            User::commit_in_zk::<H>(old_user_var.clone())?,
-            priv_bul_witness,
-            pub_bul_data,
-        )?
-        .enforce_equal(&Boolean::TRUE)?;
+        {
+            let _ns = ns!(cs, "bul_membership");
+            Bul::enforce_membership_of(
+                User::commit_in_zk::<H>(users[0].clone())?,
+                priv_bul_witness,
+                pub_bul_data,
+            )?
+            .enforce_equal(&Boolean::TRUE)?;
+        }
 
-        // Enforce any method-specific predicates
-        let b = (self.associated_method.meth.1)(
-            &old_user_var,
-            &new_user_var,
-            pub_args_var,
-            priv_args_var,
-        )?;
+        // Enforce revealed nullifier (previous state) == the old nullifier
+        {
+            let _ns = ns!(cs, "old_nul");
+            old_nul_var.enforce_equal(&users[0].zk_fields.nul)?;
+        }
 
-        b.enforce_equal(&Boolean::TRUE)?;
+        // Enforce the nullifier of the final user is derived from its commitment randomness
+        {
+            let _ns = ns!(cs, "new_nul");
+            let new_zk_fields = &users[steps.len()].zk_fields;
+            new_zk_fields
+                .nul
+                .enforce_equal(&derive_nul_in_zk::<F, H>(&new_zk_fields.com_rand)?)?;
+        }
 
-        let mut old_zk_fields = old_user_var.clone().zk_fields;
-        let new_zk_fields = new_user_var.clone().zk_fields;
+        // Enforce the callback bookkeeping of each step which is not a scan. Scans enforce their
+        // own bookkeeping in their predicate.
+        let mut next_cb = 0;
+        for (k, step) in steps.iter().enumerate() {
+            if step.is_scan {
+                continue;
+            }
 
-        // Enforce revealed nullifier (previous state) == the old nullifier
-        old_nul_var.enforce_equal(&old_zk_fields.nul)?;
+            let mut old_zk_fields = users[k].zk_fields.clone();
+            let new_zk_fields = &users[k + 1].zk_fields;
 
-        // Enforce we are currently not sweeping.
-        if !self.is_scan {
-            old_zk_fields.is_ingest_over.enforce_equal(&Boolean::TRUE)?;
-        }
+            // Enforce we are currently not sweeping.
+            {
+                let _ns = ns!(cs, "not_scanning");
+                old_zk_fields.is_ingest_over.enforce_equal(&Boolean::TRUE)?;
+            }
+
+            let _ns = ns!(cs, "callbacks");
+            for i in next_cb..next_cb + step.callbacks.len() {
+                let cb = &issued_cbs.0[i];
+
+                // Tickets may not expire before the last full scan
+                cb.cb_entry
+                    .expired_before(&old_zk_fields.last_full_scan)?
+                    .enforce_equal(&Boolean::FALSE)?;
 
-        if !self.is_scan {
-            for i in 0..NUMCBS {
                 // Enforce that the callback commitments are well-formed
-                issued_cb_coms.0[i]
-                    .enforce_equal(&CallbackCom::commit_in_zk::<H>(issued_cbs.0[i].clone())?)?;
+                issued_cb_coms.0[i].enforce_equal(&CallbackCom::commit_in_zk::<H>(cb.clone())?)?;
 
                 // Append callbacks to the callback list
                 add_ticket_to_hc_zk::<F, H, CBArgs, Crypto>(
                     &mut old_zk_fields.callback_hash,
-                    issued_cbs.0[i].clone().cb_entry,
+                    cb.clone().cb_entry,
                 )?;
             }
+            next_cb += step.callbacks.len();
 
             old_zk_fields.old_in_progress_callback_hash = old_zk_fields.callback_hash.clone();
 
@@ -697,10 +2806,25 @@ impl<
             new_zk_fields
                 .is_ingest_over
                 .enforce_equal(&old_zk_fields.is_ingest_over)?;
+
+            new_zk_fields
+                .scan_start
+                .enforce_equal(&old_zk_fields.scan_start)?;
+
+            new_zk_fields
+                .last_full_scan
+                .enforce_equal(&old_zk_fields.last_full_scan)?;
+
+            new_zk_fields.secret.enforce_equal(&old_zk_fields.secret)?;
+        }
+
+        if next_cb != NUMCBS {
+            return Err(SynthesisError::Unsatisfiable);
         }
 
         // Enforce that Com(new_user) == new_com
-        let com = User::commit_in_zk::<H>(new_user_var)?;
+        let _ns = ns!(cs, "new_com");
+        let com = User::commit_in_zk::<H>(users[steps.len()].clone())?;
 
         new_com_var.enforce_equal(&com)?;
 
@@ -712,48 +2836,26 @@ impl<
     F: PrimeField + Absorb,
     H: FieldHash<F>,
     U: UserData<F>,
-    PubArgs: Clone,
-    PubArgsVar: AllocVar<PubArgs, F> + Clone,
-    PrivArgs: Clone,
-    PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
     CBArgs: Clone,
     CBArgsVar: AllocVar<CBArgs, F> + Clone,
     Crypto: AECipherSigZK<F, CBArgs>,
     Bul: PublicUserBul<F, U>,
     const NUMCBS: usize,
-> Clone
-    for ExecMethodCircuit<
-        F,
-        H,
-        U,
-        PubArgs,
-        PubArgsVar,
-        PrivArgs,
-        PrivArgsVar,
-        CBArgs,
-        CBArgsVar,
-        Crypto,
-        Bul,
-        NUMCBS,
-    >
+> Clone for ChainedExecMethodCircuit<F, H, U, CBArgs, CBArgsVar, Crypto, Bul, NUMCBS>
 {
     fn clone(&self) -> Self {
         Self {
-            priv_old_user: self.priv_old_user.clone(),
-            priv_new_user: self.priv_new_user.clone(),
+            priv_users: self.priv_users.clone(),
             priv_issued_callbacks: self.priv_issued_callbacks.clone(),
             priv_bul_membership_witness: self.priv_bul_membership_witness.clone(),
-            priv_args: self.priv_args.clone(),
 
             pub_new_com: self.pub_new_com,
             pub_old_nul: self.pub_old_nul,
             pub_issued_callback_coms: self.pub_issued_callback_coms,
-            pub_args: self.pub_args.clone(),
             pub_bul_membership_data: self.pub_bul_membership_data.clone(),
             bul_memb_is_const: self.bul_memb_is_const,
 
-            is_scan: self.is_scan,
-            associated_method: self.associated_method.clone(),
+            associated_chain: self.associated_chain.clone(),
             _phantom_hash: self._phantom_hash,
         }
     }
@@ -771,6 +2873,7 @@ impl<
 ///
 ///# Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use zk_callbacks::zk_object;
 /// # use zk_callbacks::generic::user::User;
 /// # use rand::thread_rng;
@@ -810,7 +2913,7 @@ impl<
 ///
 ///     let mut rng = thread_rng();
 ///
-///     let (pk, vk) = generate_keys_for_statement::<Fr, Poseidon<2>, Data, _, _, _, _, Groth>(&mut rng, predicate, None);
+///     let (pk, vk) = generate_keys_for_statement::<Fr, Poseidon<2>, Data, _, _, _, _, Groth>(&mut rng, Arc::new(predicate), ());
 /// }
 /// ```
 pub fn generate_keys_for_statement<
@@ -827,6 +2930,42 @@ pub fn generate_keys_for_statement<
     pred: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
     aux_data: PubArgs,
 ) -> (Snark::ProvingKey, Snark::VerifyingKey)
+where
+    Standard: Distribution<F>,
+{
+    generate_keys_for_disclosed_statement::<
+        F,
+        H,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        Snark,
+    >(rng, pred, Disclosure::default(), aux_data)
+}
+
+/// Generate keys for proving a statement about a user object, while revealing some fields of the
+/// user.
+///
+/// This is associated to the [`User::prove_disclosed_statement`] function, and is otherwise the
+/// same as [`generate_keys_for_statement`]. The revealed fields are fixed into the keys, and so
+/// `disclosure` must be the same disclosure used when proving.
+pub fn generate_keys_for_disclosed_statement<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F> + Default,
+    PubArgs: Clone + Default,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone + Default,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    Snark: SNARK<F>,
+>(
+    rng: &mut (impl CryptoRng + RngCore),
+    pred: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+    disclosure: Disclosure<F, U>,
+    aux_data: PubArgs,
+) -> (Snark::ProvingKey, Snark::VerifyingKey)
 where
     Standard: Distribution<F>,
 {
@@ -835,8 +2974,10 @@ where
         priv_user: u.clone(),
         pub_com: u.commit::<H>(),
         pub_args: aux_data,
+        pub_revealed: disclosure.values(&u),
         priv_args: PrivArgs::default(),
         associated_method: pred,
+        disclosure,
     };
     Snark::circuit_specific_setup(out, rng).unwrap()
 }
@@ -862,9 +3003,13 @@ pub struct ProvePredicateCircuit<
     pub pub_com: Com<F>,
     /// The public arguments to the predicate.
     pub pub_args: PubArgs,
+    /// The values revealed by the disclosure.
+    pub pub_revealed: Revealed<F>,
 
     /// The predicate.
     pub associated_method: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+    /// The fields of the user revealed with the proof.
+    pub disclosure: Disclosure<F, U>,
 }
 
 impl<
@@ -884,6 +3029,136 @@ impl<
         let com_var = ComVar::new_input(ns!(cs, "com"), || Ok(&self.pub_com))?;
         let pub_args_var = PubArgsVar::new_input(ns!(cs, "pub_args"), || Ok(&self.pub_args))?;
 
+        self.disclosure
+            .enforce(cs.clone(), &user_var, &self.pub_revealed)?;
+
+        let _ns = ns!(cs, "predicate");
+        let b = (self.associated_method)(&user_var, &com_var, pub_args_var, priv_args_var)?;
+
+        b.enforce_equal(&Boolean::TRUE)?;
+
+        Ok(())
+    }
+}
+
+/// Generate keys for proving a statement about a user object committed with the scheme `C`.
+///
+/// This is associated to the [`User::prove_committed_statement`] function. The commitment
+/// parameters `params` are encoded into the key as constants.
+///
+/// For `aux_data`, if the auxiliary public arguments to the predicate are constant,
+/// this should be done in the `AllocVar` implementation of the auxiliary data type.
+pub fn generate_keys_for_committed_statement<
+    F: PrimeField + Absorb,
+    C: UserCommitment<F>,
+    U: UserData<F> + Default,
+    PubArgs: Clone + Default,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone + Default,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    Snark: SNARK<F>,
+>(
+    rng: &mut (impl CryptoRng + RngCore),
+    params: C::Params,
+    pred: SingularPredicate<F, UserVar<F, U>, C::ComVar, PubArgsVar, PrivArgsVar>,
+    aux_data: PubArgs,
+) -> (Snark::ProvingKey, Snark::VerifyingKey)
+where
+    Standard: Distribution<F>,
+{
+    let u = User::create(U::default(), rng);
+    let out: ProveCommittedCircuit<F, C, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> =
+        ProveCommittedCircuit {
+            priv_user: u.clone(),
+            pub_com: u.commit_with::<C>(&params),
+            pub_args: aux_data,
+            priv_args: PrivArgs::default(),
+            params,
+            associated_method: pred,
+        };
+    Snark::circuit_specific_setup(out, rng).unwrap()
+}
+
+/// The circuit used to generate proofs of some predicate on a user committed with the scheme `C`.
+/// This is not necessary for use with the base system.
+///
+/// Unlike [`ProvePredicateCircuit`], this circuit enforces that the user opens the public
+/// commitment.
+pub struct ProveCommittedCircuit<
+    F: PrimeField + Absorb,
+    C: UserCommitment<F>,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+> {
+    // Private
+    /// The private user object.
+    pub priv_user: User<F, U>,
+    /// Private predicate arguments.
+    pub priv_args: PrivArgs,
+
+    // Public
+    /// The commitment to the user object. Note that this is public.
+    pub pub_com: C::Com,
+    /// The public arguments to the predicate.
+    pub pub_args: PubArgs,
+
+    // Constant
+    /// The commitment parameters.
+    pub params: C::Params,
+
+    /// The predicate.
+    pub associated_method: SingularPredicate<F, UserVar<F, U>, C::ComVar, PubArgsVar, PrivArgsVar>,
+}
+
+impl<
+    F: PrimeField + Absorb,
+    C: UserCommitment<F>,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+> Clone for ProveCommittedCircuit<F, C, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar>
+{
+    fn clone(&self) -> Self {
+        Self {
+            priv_user: self.priv_user.clone(),
+            priv_args: self.priv_args.clone(),
+            pub_com: self.pub_com.clone(),
+            pub_args: self.pub_args.clone(),
+            params: self.params.clone(),
+            associated_method: self.associated_method.clone(),
+        }
+    }
+}
+
+impl<
+    F: PrimeField + Absorb,
+    C: UserCommitment<F>,
+    U: UserData<F>,
+    PubArgs: Clone,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+> ConstraintSynthesizer<F>
+    for ProveCommittedCircuit<F, C, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> ArkResult<()> {
+        let user_var = UserVar::new_witness(ns!(cs, "user"), || Ok(self.priv_user))?;
+        let priv_args_var = PrivArgsVar::new_witness(ns!(cs, "priv_args"), || Ok(&self.priv_args))?;
+
+        let com_var = C::ComVar::new_input(ns!(cs, "com"), || Ok(&self.pub_com))?;
+        let pub_args_var = PubArgsVar::new_input(ns!(cs, "pub_args"), || Ok(&self.pub_args))?;
+
+        let params_var = C::ParamsVar::new_constant(ns!(cs, "params"), &self.params)?;
+
+        let opened = User::commit_with_in_zk::<C>(&params_var, user_var.clone())?;
+        opened.enforce_equal(&com_var)?;
+
+        let _ns = ns!(cs, "predicate");
         let b = (self.associated_method)(&user_var, &com_var, pub_args_var, priv_args_var)?;
 
         b.enforce_equal(&Boolean::TRUE)?;
@@ -904,6 +3179,7 @@ impl<
 ///
 ///# Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use zk_callbacks::zk_object;
 /// # use zk_callbacks::generic::user::User;
 /// # use rand::thread_rng;
@@ -948,7 +3224,7 @@ impl<
 ///
 ///     let mut obj_store = UOVObjStore::new(&mut rng);
 ///
-///     let (pk, vk) = generate_keys_for_statement_in::<Fr, Poseidon<2>, Data, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, predicate, Some(obj_store.get_pubkey()), None);
+///     let (pk, vk) = generate_keys_for_statement_in::<Fr, Poseidon<2>, Data, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, Arc::new(predicate), Some(obj_store.get_pubkey()), ());
 /// }
 /// ```
 pub fn generate_keys_for_statement_in<
@@ -968,6 +3244,46 @@ pub fn generate_keys_for_statement_in<
 
     aux_data: PubArgs,
 ) -> (Snark::ProvingKey, Snark::VerifyingKey)
+where
+    Standard: Distribution<F>,
+{
+    generate_keys_for_disclosed_statement_in::<
+        F,
+        H,
+        U,
+        PubArgs,
+        PubArgsVar,
+        PrivArgs,
+        PrivArgsVar,
+        Snark,
+        Bul,
+    >(rng, pred, Disclosure::default(), memb_data, aux_data)
+}
+
+/// Generate keys for proving a statement about a user object and membership of user, while
+/// revealing some fields of the user.
+///
+/// This is associated to the [`User::prove_disclosed_statement_and_in`] function, and is
+/// otherwise the same as [`generate_keys_for_statement_in`]. The revealed fields are fixed into
+/// the keys, and so `disclosure` must be the same disclosure used when proving.
+pub fn generate_keys_for_disclosed_statement_in<
+    F: PrimeField + Absorb,
+    H: FieldHash<F>,
+    U: UserData<F> + Default,
+    PubArgs: Clone + Default,
+    PubArgsVar: AllocVar<PubArgs, F>,
+    PrivArgs: Clone + Default,
+    PrivArgsVar: AllocVar<PrivArgs, F>,
+    Snark: SNARK<F>,
+    Bul: PublicUserBul<F, U>,
+>(
+    rng: &mut (impl CryptoRng + RngCore),
+    pred: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+    disclosure: Disclosure<F, U>,
+    memb_data: Option<Bul::MembershipPub>,
+
+    aux_data: PubArgs,
+) -> (Snark::ProvingKey, Snark::VerifyingKey)
 where
     Standard: Distribution<F>,
 {
@@ -977,10 +3293,12 @@ where
             priv_user: u.clone(),
             priv_extra_membership_data: Bul::MembershipWitness::default(),
             pub_args: aux_data,
+            pub_revealed: disclosure.values(&u),
             priv_args: PrivArgs::default(),
             bul_memb_is_const: memb_data.is_some(),
             pub_extra_membership_data: memb_data.unwrap_or_default(),
             associated_method: pred,
+            disclosure,
 
             _phantom_hash: PhantomData,
         };
@@ -1009,12 +3327,16 @@ pub struct ProvePredInCircuit<
     // Public
     /// Public arguments to the predicate.
     pub pub_args: PubArgs,
+    /// The values revealed by the disclosure.
+    pub pub_revealed: Revealed<F>,
     /// Public membership data for the user commitment.
     pub pub_extra_membership_data: Bul::MembershipPub,
     /// If the public membership data constant.
     pub bul_memb_is_const: bool,
     /// The predicate.
     pub associated_method: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+    /// The fields of the user revealed with the proof.
+    pub disclosure: Disclosure<F, U>,
 
     /// The hash used for the commitment.
     pub _phantom_hash: PhantomData<H>,
@@ -1037,9 +3359,11 @@ impl<
             priv_extra_membership_data: self.priv_extra_membership_data.clone(),
             priv_args: self.priv_args.clone(),
             pub_args: self.pub_args.clone(),
+            pub_revealed: self.pub_revealed.clone(),
             pub_extra_membership_data: self.pub_extra_membership_data.clone(),
             bul_memb_is_const: self.bul_memb_is_const,
-            associated_method: self.associated_method,
+            associated_method: self.associated_method.clone(),
+            disclosure: self.disclosure.clone(),
             _phantom_hash: self._phantom_hash,
         }
     }
@@ -1068,6 +3392,9 @@ impl<
 
         let pub_args_var = PubArgsVar::new_input(ns!(cs, "pub_args"), || Ok(&self.pub_args))?;
 
+        self.disclosure
+            .enforce(cs.clone(), &user_var, &self.pub_revealed)?;
+
         let pub_data_for_membership = match self.bul_memb_is_const {
             true => {
                 Bul::MembershipPubVar::new_constant(cs.clone(), &self.pub_extra_membership_data)?
@@ -1104,6 +3431,7 @@ impl<
 ///
 /// # Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use zk_callbacks::generic::interaction::generate_keys_for_scan;
 /// # use zk_callbacks::zk_object;
 /// # use zk_callbacks::generic::user::User;
@@ -1182,15 +3510,18 @@ impl<
 ///         method_id: Id::from(0),
 ///         expirable: false,
 ///         expiration: Time::from(10),
-///         method: callback,
-///         predicate: enforce_callback
+///         method: Arc::new(callback),
+///         predicate: Arc::new(enforce_callback)
 ///     };
 ///
 ///     let cb_methods = vec![cb.clone()];
 ///
 ///     let int = Interaction {
-///         meth: (method, predicate),
+///         meth: (Arc::new(method), Arc::new(predicate)),
 ///         callbacks: [cb.clone()],
+///         unchanged: None,
+///         hidden_ids: None,
+///         pseudonym_scope: None,
 ///     };
 ///
 ///     let ex: PubScan = PubScanArgs {
@@ -1212,13 +3543,13 @@ impl<
 /// ```
 pub fn generate_keys_for_scan<
     F: PrimeField + Absorb,
-    U: UserData<F> + Default,
-    CBArgs: Clone + Default + std::fmt::Debug,
-    CBArgsVar: AllocVar<CBArgs, F> + Clone,
-    Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + Default,
+    U: UserData<F> + Default + 'static,
+    CBArgs: Clone + Default + std::fmt::Debug + 'static,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+    Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + Default + 'static,
     Bul: PublicUserBul<F, U>,
-    CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + Default,
-    H: FieldHash<F>,
+    CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + Default + 'static,
+    H: FieldHash<F> + 'static,
     Snark: SNARK<F>,
     const NUMSCANS: usize,
 >(
@@ -38,6 +38,14 @@ pub mod bulletin;
 /// Objects for tickets and callback commitments.
 pub mod callbacks;
 
+/// A test harness checking that native methods agree with their in-circuit counterparts.
+///
+/// Both [`Interaction`](`interaction::Interaction`)s and
+/// [`Callback`](`interaction::Callback`)s pair a native method with an in-circuit predicate or
+/// method, which must agree for users to be able to prove their updates. The functions in this
+/// module run both on generated inputs, and report a counterexample if they disagree.
+pub mod consistency;
+
 /// Objects and structs for folding scans using PSE's Sonobe.
 #[cfg(feature = "folding")]
 #[cfg(any(feature = "folding", doc))]
@@ -83,3 +91,9 @@ pub mod service;
 /// this module contains the [`User`](`user::User`) object and the [`UserData`](`user::UserData`) trait, which are integral to the
 /// system.
 pub mod user;
+
+/// An encrypted wallet for storing users.
+///
+/// The [`Wallet`](`wallet::Wallet`) keeps a user encrypted at rest under a password, along with a
+/// history of its previous states, and only commits updates to the user once they succeed.
+pub mod wallet;
@@ -1,18 +1,26 @@
+use crate::crypto::hash::{FieldHash, HashDomain, hash_with_domain, hash_with_domain_in_zk};
 use ark_ff::{PrimeField, ToConstraintField};
 use ark_r1cs_std::{
     R1CSVar,
     alloc::{AllocVar, AllocationMode},
     boolean::Boolean,
+    cmp::CmpGadget,
     convert::ToConstraintFieldGadget,
     fields::fp::FpVar,
     select::CondSelectGadget,
+    uint::UInt,
 };
 use ark_relations::{
     ns,
     r1cs::{ConstraintSystemRef, Namespace, SynthesisError},
 };
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use std::borrow::Borrow;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use std::{
+    borrow::Borrow,
+    io::{Read, Write},
+};
 
 /// A nullifier type. Represents a nullifier (or serial number).
 pub type Nul<F> = F;
@@ -42,10 +50,66 @@ pub type SerVar<F> = FpVar<F>;
 pub type Id<F> = F;
 /// A unique ID in zero knowledge.
 pub type IdVar<F> = FpVar<F>;
+/// A pseudonym of a user within some scope.
+pub type Pseudonym<F> = F;
+/// A pseudonym in zero knowledge.
+pub type PseudonymVar<F> = FpVar<F>;
+
+/// Derive the nullifier of a user state from its commitment randomness.
+///
+/// The nullifier is the hash of the randomness in the [`HashDomain::Nullifier`] domain, so it can
+/// never coincide with a commitment or hash chain produced by the same hash. Interactions enforce
+/// this derivation for the new user state in-circuit (see [`derive_nul_in_zk`]).
+///
+/// Note that the invariant `nul = derive_nul(com_rand)` only holds after the first interaction.
+/// A freshly created user (see [`User::create`](crate::generic::user::User::create)) has a random
+/// nullifier, since creation does not fix the hash; the old nullifier is never checked against
+/// the randomness, so this does not affect the first interaction.
+pub fn derive_nul<F: PrimeField, H: FieldHash<F>>(com_rand: ComRand<F>) -> Nul<F> {
+    hash_with_domain::<F, H>(HashDomain::Nullifier, &[com_rand])
+}
+
+/// Derive the nullifier of a user state in-circuit.
+///
+/// See [`derive_nul`].
+pub fn derive_nul_in_zk<F: PrimeField, H: FieldHash<F>>(
+    com_rand: &ComRandVar<F>,
+) -> Result<NulVar<F>, SynthesisError> {
+    hash_with_domain_in_zk::<F, H>(HashDomain::Nullifier, std::slice::from_ref(com_rand))
+}
+
+/// Derive the pseudonym of a user within a scope, from the secret of the user.
+///
+/// The pseudonym is the hash of the secret and the scope in the [`HashDomain::Pseudonym`] domain.
+/// A user therefore always has the same pseudonym within a scope, while pseudonyms in different
+/// scopes are unlinkable without the secret.
+pub fn derive_pseudonym<F: PrimeField, H: FieldHash<F>>(secret: F, scope: Id<F>) -> Pseudonym<F> {
+    hash_with_domain::<F, H>(HashDomain::Pseudonym, &[secret, scope])
+}
+
+/// Derive the pseudonym of a user within a scope in-circuit.
+///
+/// See [`derive_pseudonym`].
+pub fn derive_pseudonym_in_zk<F: PrimeField, H: FieldHash<F>>(
+    secret: &FpVar<F>,
+    scope: &IdVar<F>,
+) -> Result<PseudonymVar<F>, SynthesisError> {
+    hash_with_domain_in_zk::<F, H>(HashDomain::Pseudonym, &[secret.clone(), scope.clone()])
+}
+
+/// The version of the serialized layout of [`ZKFields`].
+///
+/// Serialized [`ZKFields`] are prefixed with this version, and deserialization rejects any other
+/// version. Version 1 is the original (unversioned) layout, without the scan times and the secret.
+///
+/// Users serialized with version 1 can not be read, and can not be migrated: the commitment covers
+/// every field, so a converted user would have a different commitment, with no proof linking it
+/// to the original. Such users must be created again and join the user bulletin anew.
+pub const ZK_FIELDS_VERSION: u8 = 2;
 
 /// The ZKFields type provides all the necessary types for a user to properly interact with a
 /// server. It is always contained within the `User` type.
-#[derive(Clone, Default, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
 pub struct ZKFields<F: PrimeField> {
     /// The nullifier or serial number of the user state.
     pub nul: Nul<F>,
@@ -59,6 +123,17 @@ pub struct ZKFields<F: PrimeField> {
     pub old_in_progress_callback_hash: CBHash<F>,
     /// If the current ingestion is over, or is in progress.
     pub is_ingest_over: bool,
+    /// The time at which the current (or last) scan started.
+    pub scan_start: Time<F>,
+    /// The time as of which every callback has been scanned.
+    ///
+    /// This is the start time of the last completed scan, so any callback called before this time
+    /// has been applied to the user.
+    pub last_full_scan: Time<F>,
+    /// The secret of the user, from which pseudonyms are derived (see [`derive_pseudonym`]).
+    ///
+    /// This is chosen when the user is created, and never changes.
+    pub secret: F,
 }
 
 /// The ZKFieldsVar type provides the necessary types to interact with a server in zero knowledge.
@@ -76,6 +151,12 @@ pub struct ZKFieldsVar<F: PrimeField> {
     pub old_in_progress_callback_hash: CBHashVar<F>,
     /// If the current ingestion is over, or is in progress.
     pub is_ingest_over: Boolean<F>,
+    /// The time at which the current (or last) scan started.
+    pub scan_start: TimeVar<F>,
+    /// The time as of which every callback has been scanned.
+    pub last_full_scan: TimeVar<F>,
+    /// The secret of the user, from which pseudonyms are derived.
+    pub secret: FpVar<F>,
 }
 
 impl<F: PrimeField> ZKFields<F> {
@@ -92,9 +173,105 @@ impl<F: PrimeField> ZKFields<F> {
                 .to_field_elements()
                 .unwrap(),
             self.is_ingest_over.to_field_elements().unwrap(),
+            self.scan_start.to_field_elements().unwrap(),
+            self.last_full_scan.to_field_elements().unwrap(),
+            self.secret.to_field_elements().unwrap(),
         ]
         .concat()
     }
+
+    /// Whether every callback called before `time` has been scanned.
+    ///
+    /// See [`ZKFieldsVar::scanned_since`].
+    pub fn scanned_since(&self, time: Time<F>) -> bool {
+        self.is_ingest_over && self.last_full_scan >= time
+    }
+}
+
+impl<F: PrimeField> CanonicalSerialize for ZKFields<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        ZK_FIELDS_VERSION.serialize_with_mode(&mut writer, compress)?;
+        self.nul.serialize_with_mode(&mut writer, compress)?;
+        self.com_rand.serialize_with_mode(&mut writer, compress)?;
+        self.callback_hash
+            .serialize_with_mode(&mut writer, compress)?;
+        self.new_in_progress_callback_hash
+            .serialize_with_mode(&mut writer, compress)?;
+        self.old_in_progress_callback_hash
+            .serialize_with_mode(&mut writer, compress)?;
+        self.is_ingest_over
+            .serialize_with_mode(&mut writer, compress)?;
+        self.scan_start.serialize_with_mode(&mut writer, compress)?;
+        self.last_full_scan
+            .serialize_with_mode(&mut writer, compress)?;
+        self.secret.serialize_with_mode(&mut writer, compress)?;
+
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        ZK_FIELDS_VERSION.serialized_size(compress)
+            + self.nul.serialized_size(compress)
+            + self.com_rand.serialized_size(compress)
+            + self.callback_hash.serialized_size(compress)
+            + self.new_in_progress_callback_hash.serialized_size(compress)
+            + self.old_in_progress_callback_hash.serialized_size(compress)
+            + self.is_ingest_over.serialized_size(compress)
+            + self.scan_start.serialized_size(compress)
+            + self.last_full_scan.serialized_size(compress)
+            + self.secret.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for ZKFields<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.nul.check()?;
+        self.com_rand.check()?;
+        self.callback_hash.check()?;
+        self.new_in_progress_callback_hash.check()?;
+        self.old_in_progress_callback_hash.check()?;
+        self.is_ingest_over.check()?;
+        self.scan_start.check()?;
+        self.last_full_scan.check()?;
+        self.secret.check()?;
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for ZKFields<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let version = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        if version != ZK_FIELDS_VERSION {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(ZKFields {
+            nul: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            com_rand: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            callback_hash: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            new_in_progress_callback_hash: F::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            old_in_progress_callback_hash: F::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            is_ingest_over: bool::deserialize_with_mode(&mut reader, compress, validate)?,
+            scan_start: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            last_full_scan: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            secret: F::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
 }
 
 impl<F: PrimeField> ZKFieldsVar<F> {
@@ -107,9 +284,23 @@ impl<F: PrimeField> ZKFieldsVar<F> {
             self.new_in_progress_callback_hash.to_constraint_field()?,
             self.old_in_progress_callback_hash.to_constraint_field()?,
             self.is_ingest_over.to_constraint_field()?,
+            self.scan_start.to_constraint_field()?,
+            self.last_full_scan.to_constraint_field()?,
+            self.secret.to_constraint_field()?,
         ]
         .concat())
     }
+
+    /// Whether every callback called before `time` has been scanned, in-circuit.
+    ///
+    /// This holds if no scan is in progress and the last full scan started at or after `time`.
+    /// Interactions may use this to require users to be up to date on their callbacks; see also
+    /// [`UserPredicate::scanned_since`](super::interaction::UserPredicate::scanned_since).
+    pub fn scanned_since(&self, time: &TimeVar<F>) -> Result<Boolean<F>, SynthesisError> {
+        let last_full_scan = <UInt<64, u64, F>>::from_fp(&self.last_full_scan)?.0;
+        let time = <UInt<64, u64, F>>::from_fp(time)?.0;
+        Ok(self.is_ingest_over.clone() & last_full_scan.is_ge(&time)?)
+    }
 }
 
 impl<F: PrimeField> R1CSVar<F> for ZKFieldsVar<F> {
@@ -123,6 +314,9 @@ impl<F: PrimeField> R1CSVar<F> for ZKFieldsVar<F> {
             .or(self.new_in_progress_callback_hash.cs())
             .or(self.old_in_progress_callback_hash.cs())
             .or(self.is_ingest_over.cs())
+            .or(self.scan_start.cs())
+            .or(self.last_full_scan.cs())
+            .or(self.secret.cs())
     }
 
     fn value(&self) -> Result<Self::Value, SynthesisError> {
@@ -133,6 +327,9 @@ impl<F: PrimeField> R1CSVar<F> for ZKFieldsVar<F> {
             new_in_progress_callback_hash: self.new_in_progress_callback_hash.value()?,
             old_in_progress_callback_hash: self.old_in_progress_callback_hash.value()?,
             is_ingest_over: self.is_ingest_over.value()?,
+            scan_start: self.scan_start.value()?,
+            last_full_scan: self.last_full_scan.value()?,
+            secret: self.secret.value()?,
         })
     }
 }
@@ -165,6 +362,11 @@ impl<F: PrimeField> AllocVar<ZKFields<F>, F> for ZKFieldsVar<F> {
             )?;
             let is_ingest_over =
                 Boolean::new_variable(ns!(cs, "is_ingest_over"), || Ok(rec.is_ingest_over), mode)?;
+            let scan_start =
+                TimeVar::new_variable(ns!(cs, "scan_start"), || Ok(rec.scan_start), mode)?;
+            let last_full_scan =
+                TimeVar::new_variable(ns!(cs, "last_full_scan"), || Ok(rec.last_full_scan), mode)?;
+            let secret = FpVar::new_variable(ns!(cs, "secret"), || Ok(rec.secret), mode)?;
             Ok(ZKFieldsVar {
                 nul,
                 com_rand,
@@ -172,6 +374,9 @@ impl<F: PrimeField> AllocVar<ZKFields<F>, F> for ZKFieldsVar<F> {
                 new_in_progress_callback_hash,
                 old_in_progress_callback_hash,
                 is_ingest_over,
+                scan_start,
+                last_full_scan,
+                secret,
             })
         })
     }
@@ -209,6 +414,18 @@ impl<F: PrimeField> CondSelectGadget<F> for ZKFieldsVar<F> {
             &true_value.is_ingest_over,
             &false_value.is_ingest_over,
         )?;
+        let scan_start = <TimeVar<F>>::conditionally_select(
+            cond,
+            &true_value.scan_start,
+            &false_value.scan_start,
+        )?;
+        let last_full_scan = <TimeVar<F>>::conditionally_select(
+            cond,
+            &true_value.last_full_scan,
+            &false_value.last_full_scan,
+        )?;
+        let secret =
+            <FpVar<F>>::conditionally_select(cond, &true_value.secret, &false_value.secret)?;
 
         Ok(Self {
             nul,
@@ -217,6 +434,75 @@ impl<F: PrimeField> CondSelectGadget<F> for ZKFieldsVar<F> {
             new_in_progress_callback_hash,
             old_in_progress_callback_hash,
             is_ingest_over,
+            scan_start,
+            last_full_scan,
+            secret,
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    // Serializes fields in the original (version 1) layout, which had no version byte, scan times
+    // or secret.
+    fn serialize_v1(fields: &ZKFields<Fr>) -> Vec<u8> {
+        let mut bytes = vec![];
+        for f in [
+            fields.nul,
+            fields.com_rand,
+            fields.callback_hash,
+            fields.new_in_progress_callback_hash,
+            fields.old_in_progress_callback_hash,
+        ] {
+            f.serialize_compressed(&mut bytes).unwrap();
+        }
+        fields
+            .is_ingest_over
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    // Tests that the current layout round trips, and that the version 1 layout is rejected
+    #[test]
+    fn zk_fields_reject_v1() {
+        let mut rng = thread_rng();
+        let fields = ZKFields {
+            nul: Fr::rand(&mut rng),
+            com_rand: Fr::rand(&mut rng),
+            callback_hash: Fr::rand(&mut rng),
+            new_in_progress_callback_hash: Fr::rand(&mut rng),
+            old_in_progress_callback_hash: Fr::rand(&mut rng),
+            is_ingest_over: true,
+            scan_start: Fr::from(3),
+            last_full_scan: Fr::from(2),
+            secret: Fr::rand(&mut rng),
+        };
+
+        let mut bytes = vec![];
+        fields.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes[0], ZK_FIELDS_VERSION);
+        assert_eq!(bytes.len(), fields.compressed_size());
+        assert_eq!(
+            ZKFields::<Fr>::deserialize_compressed(&bytes[..]).unwrap(),
+            fields
+        );
+
+        assert!(ZKFields::<Fr>::deserialize_compressed(&serialize_v1(&fields)[..]).is_err());
+
+        // Even a version 1 nullifier whose first byte reads as the current version is rejected.
+        let nul_like_version = ZKFields {
+            nul: Fr::from(ZK_FIELDS_VERSION as u64),
+            ..fields
+        };
+        let v1 = serialize_v1(&nul_like_version);
+        assert_eq!(v1[0], ZK_FIELDS_VERSION);
+        assert!(ZKFields::<Fr>::deserialize_compressed(&v1[..]).is_err());
+    }
+}
@@ -5,24 +5,30 @@ use ark_r1cs_std::{
     alloc::AllocVar, cmp::CmpGadget, eq::EqGadget, fields::fp::FpVar, prelude::Boolean,
     select::CondSelectGadget, uint::UInt,
 };
-use ark_relations::{ns, r1cs::Result as ArkResult};
+use ark_relations::{
+    ns,
+    r1cs::{Result as ArkResult, SynthesisError},
+};
 use ark_serialize::CanonicalSerialize;
+use ark_snark::SNARK;
+use rand::{CryptoRng, RngCore, distributions::Standard, prelude::Distribution};
 
 use crate::{
     crypto::{enc::AECipherSigZK, hash::FieldHash},
-    generic::callbacks::add_ticket_to_hc,
+    generic::callbacks::{add_ticket_to_hc, release_tag, release_tag_in_zk},
     util::ArrayVar,
 };
 
 use crate::generic::{
-    bulletin::PublicCallbackBul,
+    bulletin::{PublicCallbackBul, PublicUserBul},
     callbacks::{CallbackCom, CallbackComVar, add_ticket_to_hc_zk},
     interaction::Callback,
     object::{Time, TimeVar},
-    user::{User, UserData, UserVar},
+    user::{ExecutedMethod, User, UserData, UserError, UserVar},
 };
 
 use crate::generic::interaction::Interaction;
+use std::sync::Arc;
 
 /// Public arguments to the scan method.
 ///
@@ -32,6 +38,7 @@ use crate::generic::interaction::Interaction;
 ///
 /// # Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use ark_bn254::{Bn254 as E, Fr};
 /// # use ark_groth16::Groth16;
 /// # use ark_r1cs_std::{eq::EqGadget, fields::fp::FpVar, prelude::Boolean};
@@ -83,8 +90,8 @@ use crate::generic::interaction::Interaction;
 ///         method_id: Id::from(0),
 ///         expirable: false,
 ///         expiration: Time::from(300),
-///         method: cb_method,
-///         predicate: cb_enforce,
+///         method: Arc::new(cb_method),
+///         predicate: Arc::new(cb_enforce),
 ///     };
 ///
 ///     let mut store = <UOVStore<Fr, Fr>>::new(&mut rng);
@@ -308,6 +315,7 @@ impl<
 ///
 /// # Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use zk_callbacks::zk_object;
 /// # use zk_callbacks::generic::user::User;
 /// # use rand::thread_rng;
@@ -393,8 +401,8 @@ impl<
 ///         method_id: Id::from(0),
 ///         expirable: false,
 ///         expiration: Time::from(10),
-///         method: callback,
-///         predicate: enforce_callback
+///         method: Arc::new(callback),
+///         predicate: Arc::new(enforce_callback)
 ///     };
 ///
 ///     let cb_methods = vec![cb.clone()];
@@ -402,8 +410,11 @@ impl<
 ///     let mut store = <UOVStore<Fr, Fr>>::new(&mut rng);
 ///
 ///     let int = Interaction {
-///         meth: (method, predicate),
+///         meth: (Arc::new(method), Arc::new(predicate)),
 ///         callbacks: [cb.clone()],
+///         unchanged: None,
+///         hidden_ids: None,
+///         pseudonym_scope: None,
 ///     };
 ///
 ///     let example_pubscan: PubScan = PubScanArgs {
@@ -424,7 +435,7 @@ impl<
 ///
 ///     let exec_meth = u.interact::<Poseidon<2>, Time<Fr>, TimeVar<Fr>, (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 1>(&mut rng, int.clone(), [FakeSigPubkey::pk()], Time::from(20), ((), ()), true, &pk, Time::from(20), (), false).unwrap();
 ///
-///     let cb = u.get_cb::<Fr, NoSigOTP<Fr>>(0);
+///     let cb = u.get_cb::<Fr, NoSigOTP<Fr>>(0).unwrap();
 ///     let tik: FakeSigPubkey<Fr> = cb.get_ticket();
 ///
 ///     let x = <UOVCallbackStore<Fr, Fr> as PublicCallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_in(&store.callback_bul, tik.clone());
@@ -611,6 +622,12 @@ impl<
 /// This applies a scan with public arguments (callback bulletin public data, the time of
 /// scan) and private arguments (the callback tickets, encrypted arguments, and post times).
 ///
+/// Tickets which expired before the user's last full scan started (see
+/// [`ZKFields::last_full_scan`](super::object::ZKFields::last_full_scan)) are dropped from the
+/// callback list without checking the bulletin: had such a ticket been called before it expired,
+/// that scan would have removed it. Interactions may not issue tickets expiring before the last
+/// full scan, so this holds for every ticket in the list.
+///
 /// This is structured for use within an interaction.
 pub fn scan_method<
     F: PrimeField + Absorb,
@@ -632,6 +649,7 @@ pub fn scan_method<
         out_user.zk_fields.is_ingest_over = false;
         out_user.zk_fields.old_in_progress_callback_hash = F::zero();
         out_user.zk_fields.new_in_progress_callback_hash = F::zero();
+        out_user.zk_fields.scan_start = pub_args.cur_time;
         out_user.scan_index = Some(0);
         out_user.in_progress_cbs = out_user.callbacks.clone();
     }
@@ -644,36 +662,39 @@ pub fn scan_method<
             i.cb_entry.clone(),
         );
 
-        match pub_args.bulletin.verify_in(i.cb_entry.tik.clone()) {
-            Some((ct, sig, time)) => {
-                assert!(
-                    <Crypto as AECipherSigZK<F, CBArgs>>::SigPK::verify(
-                        &i.cb_entry.tik,
-                        ct.clone(),
-                        sig
-                    ) == true
-                );
-                if i.cb_entry.expirable && time > i.cb_entry.expiration {
-                } else {
-                    for x in &pub_args.cb_methods {
-                        if x.method_id == i.cb_entry.cb_method_id {
-                            let args = i.cb_entry.enc_key.decrypt(ct.clone());
-                            out_user = (x.method)(&out_user, args);
+        // A ticket which expired before the last full scan started was not called in time (or it
+        // would have been removed by that scan), so it is dropped without checking the bulletin.
+        if i.cb_entry.expired_before(out_user.zk_fields.last_full_scan) {
+            let mut cb = Vec::new();
+            i.clone().serialize_compressed(&mut cb).unwrap();
+            for x in 0..out_user.in_progress_cbs.len() {
+                if out_user.in_progress_cbs[x] == cb {
+                    marked_for_deletion.push(x);
+                }
+            }
+        } else {
+            match pub_args.bulletin.verify_in(i.cb_entry.tik.clone()) {
+                Some((ct, sig, time)) => {
+                    assert!(
+                        <Crypto as AECipherSigZK<F, CBArgs>>::SigPK::verify(
+                            &i.cb_entry.tik,
+                            ct.clone(),
+                            sig
+                        ) == true
+                    );
+                    let tag = release_tag::<F, H>(&i.cb_entry.tik);
+                    if (i.cb_entry.expirable && time > i.cb_entry.expiration)
+                        || Crypto::is_release(tag, &ct, time)
+                    {
+                    } else {
+                        for x in &pub_args.cb_methods {
+                            if x.method_id == i.cb_entry.cb_method_id {
+                                let args = i.cb_entry.enc_key.decrypt(ct.clone());
+                                out_user = (x.method)(&out_user, args);
+                            }
                         }
                     }
-                }
 
-                let mut cb = Vec::new();
-                i.clone().serialize_compressed(&mut cb).unwrap();
-                for x in 0..out_user.in_progress_cbs.len() {
-                    if out_user.in_progress_cbs[x] == cb {
-                        marked_for_deletion.push(x);
-                    }
-                }
-            }
-            None => {
-                assert!(pub_args.bulletin.verify_not_in(i.clone().cb_entry.tik));
-                if i.cb_entry.expirable && pub_args.cur_time > i.cb_entry.expiration {
                     let mut cb = Vec::new();
                     i.clone().serialize_compressed(&mut cb).unwrap();
                     for x in 0..out_user.in_progress_cbs.len() {
@@ -681,12 +702,24 @@ pub fn scan_method<
                             marked_for_deletion.push(x);
                         }
                     }
-                } else {
-                    out_user.zk_fields.new_in_progress_callback_hash =
-                        add_ticket_to_hc::<F, H, CBArgs, Crypto>(
-                            out_user.zk_fields.new_in_progress_callback_hash,
-                            i.cb_entry,
-                        );
+                }
+                None => {
+                    assert!(pub_args.bulletin.verify_not_in(i.clone().cb_entry.tik));
+                    if i.cb_entry.expirable && pub_args.cur_time > i.cb_entry.expiration {
+                        let mut cb = Vec::new();
+                        i.clone().serialize_compressed(&mut cb).unwrap();
+                        for x in 0..out_user.in_progress_cbs.len() {
+                            if out_user.in_progress_cbs[x] == cb {
+                                marked_for_deletion.push(x);
+                            }
+                        }
+                    } else {
+                        out_user.zk_fields.new_in_progress_callback_hash =
+                            add_ticket_to_hc::<F, H, CBArgs, Crypto>(
+                                out_user.zk_fields.new_in_progress_callback_hash,
+                                i.cb_entry,
+                            );
+                    }
                 }
             }
         }
@@ -708,6 +741,7 @@ pub fn scan_method<
         out_user.zk_fields.new_in_progress_callback_hash = F::ZERO;
         out_user.zk_fields.old_in_progress_callback_hash = out_user.zk_fields.callback_hash;
         out_user.zk_fields.is_ingest_over = true;
+        out_user.zk_fields.last_full_scan = out_user.zk_fields.scan_start;
         out_user.callbacks = out_user.in_progress_cbs.clone();
         out_user.in_progress_cbs = vec![];
         out_user.scan_index = None;
@@ -753,9 +787,16 @@ where
         &user_old.zk_fields.is_ingest_over,
     )?;
 
+    let updated_start = FpVar::<F>::conditionally_select(
+        &user_old.zk_fields.is_ingest_over,
+        &pub_args.cur_time,
+        &user_old.zk_fields.scan_start,
+    )?;
+
     inprog_user.zk_fields.is_ingest_over = updated_ingest;
     inprog_user.zk_fields.old_in_progress_callback_hash = updated_old;
     inprog_user.zk_fields.new_in_progress_callback_hash = updated_new;
+    inprog_user.zk_fields.scan_start = updated_start;
 
     // check the ids are sequentially assigned and in-order
     let mut r = F::ZERO;
@@ -770,7 +811,14 @@ where
             priv_args.priv_n_tickets[i].cb_entry.clone(),
         )?;
 
-        let memb = CBul::enforce_memb_nmemb(
+        // Tickets which expired before the last full scan are dropped, without checking the
+        // bulletin (see `scan_method`).
+        let is_stale = priv_args.priv_n_tickets[i]
+            .cb_entry
+            .expired_before(&inprog_user.zk_fields.last_full_scan)?;
+        let stale_world_user = inprog_user.clone();
+
+        let memb = CBul::conditional_enforce_memb_nmemb(
             (
                 priv_args.priv_n_tickets[i].cb_entry.tik.clone(),
                 priv_args.enc_args[i].clone(),
@@ -781,6 +829,7 @@ where
                 priv_args.nmemb_priv[i].clone(),
             ),
             (pub_args.memb_pub[i].clone(), pub_args.nmemb_pub[i].clone()),
+            &!is_stale.clone(),
         )?;
 
         // part 1: if we are in the membership setting
@@ -822,8 +871,17 @@ where
         let ut1 = <UInt<64, u64, F>>::from_fp(&priv_args.post_times[i])?.0;
         let ut2 = <UInt<64, u64, F>>::from_fp(&priv_args.priv_n_tickets[i].cb_entry.expiration)?.0;
 
+        // Released tickets, and those called after expiry, do nothing. A release is recognized by
+        // the signed release tag, as the post time is not signed.
+        let is_released = Crypto::is_release_in_zk(
+            &release_tag_in_zk::<F, H>(&priv_args.priv_n_tickets[i].cb_entry.tik)?,
+            &priv_args.enc_args[i],
+            &priv_args.post_times[i],
+        )?;
+
         memb_world_user = UserVar::conditionally_select(
-            &(priv_args.priv_n_tickets[i].clone().cb_entry.expirable & ((ut1.is_gt(&ut2))?)),
+            &((priv_args.priv_n_tickets[i].clone().cb_entry.expirable & ((ut1.is_gt(&ut2))?))
+                | is_released),
             &memb_world_user,
             &cond_user_select,
         )?;
@@ -860,6 +918,8 @@ where
         // together: using memb, select the correct user from part 1 / 2
         let correct_updated_user =
             UserVar::conditionally_select(&memb, &memb_world_user, &nmemb_world_user)?;
+        let correct_updated_user =
+            UserVar::conditionally_select(&is_stale, &stale_world_user, &correct_updated_user)?;
 
         inprog_user = correct_updated_user;
     }
@@ -900,10 +960,20 @@ where
         &inprog_user.zk_fields.is_ingest_over,
     )?;
 
+    let updated_last_scan = FpVar::<F>::conditionally_select(
+        &(inprog_user
+            .zk_fields
+            .callback_hash
+            .is_eq(&inprog_user.zk_fields.old_in_progress_callback_hash)?),
+        &inprog_user.zk_fields.scan_start,
+        &inprog_user.zk_fields.last_full_scan,
+    )?;
+
     inprog_user.zk_fields.callback_hash = updated_cbh;
     inprog_user.zk_fields.new_in_progress_callback_hash = updated_new;
     inprog_user.zk_fields.old_in_progress_callback_hash = updated_old;
     inprog_user.zk_fields.is_ingest_over = updated_ingest;
+    inprog_user.zk_fields.last_full_scan = updated_last_scan;
 
     Ok(inprog_user)
 }
@@ -938,7 +1008,23 @@ where
     // let b = User::commit_in_zk::<H>(inprog_user)?
     //    .is_eq(&(User::commit_in_zk::<H>(user_new.clone())?))?;
 
-    Ok(b)
+    // The bookkeeping fields (other than the nullifier and commitment randomness, which are
+    // refreshed) must also be those of the scanned user, so scan progress and the last full scan
+    // time can be relied on.
+    let (out, new) = (&out_user.zk_fields, &user_new.zk_fields);
+    let fields = out.callback_hash.is_eq(&new.callback_hash)?
+        & out
+            .new_in_progress_callback_hash
+            .is_eq(&new.new_in_progress_callback_hash)?
+        & out
+            .old_in_progress_callback_hash
+            .is_eq(&new.old_in_progress_callback_hash)?
+        & out.is_ingest_over.is_eq(&new.is_ingest_over)?
+        & out.scan_start.is_eq(&new.scan_start)?
+        & out.last_full_scan.is_eq(&new.last_full_scan)?
+        & out.secret.is_eq(&new.secret)?;
+
+    Ok(b & fields)
 }
 
 /// Returns the interaction associated with a scan.
@@ -951,6 +1037,7 @@ where
 ///
 /// # Example
 /// ```rust
+/// # use std::sync::Arc;
 /// # use zk_callbacks::zk_object;
 /// # use zk_callbacks::generic::user::User;
 /// # use rand::thread_rng;
@@ -1028,15 +1115,18 @@ where
 ///         method_id: Id::from(0),
 ///         expirable: false,
 ///         expiration: Time::from(10),
-///         method: callback,
-///         predicate: enforce_callback
+///         method: Arc::new(callback),
+///         predicate: Arc::new(enforce_callback)
 ///     };
 ///
 ///     let cb_methods = vec![cb.clone()];
 ///
 ///     let int = Interaction {
-///         meth: (method, predicate),
+///         meth: (Arc::new(method), Arc::new(predicate)),
 ///         callbacks: [cb.clone()],
+///         unchanged: None,
+///         hidden_ids: None,
+///         pseudonym_scope: None,
 ///     };
 ///
 ///     let ex: PubScan = PubScanArgs {
@@ -1058,12 +1148,12 @@ where
 /// ```
 pub fn get_scan_interaction<
     F: PrimeField + Absorb,
-    U: UserData<F>,
-    CBArgs: Clone,
-    CBArgsVar: AllocVar<CBArgs, F> + Clone,
-    Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar>,
-    CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone,
-    H: FieldHash<F>,
+    U: UserData<F> + 'static,
+    CBArgs: Clone + 'static,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+    Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + 'static,
+    CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + 'static,
+    H: FieldHash<F> + 'static,
     const NUMSCANS: usize,
 >() -> Interaction<
     F,
@@ -1081,9 +1171,237 @@ where
 {
     Interaction {
         meth: (
-            scan_method::<F, U, CBArgs, CBArgsVar, Crypto, CBul, H, NUMSCANS>,
-            scan_predicate::<F, U, CBArgs, CBArgsVar, Crypto, CBul, H, NUMSCANS>,
+            Arc::new(scan_method::<F, U, CBArgs, CBArgsVar, Crypto, CBul, H, NUMSCANS>),
+            Arc::new(scan_predicate::<F, U, CBArgs, CBArgsVar, Crypto, CBul, H, NUMSCANS>),
         ),
         callbacks: [],
+        unchanged: None,
+        hidden_ids: None,
+        pseudonym_scope: None,
+    }
+}
+
+/// Plan the fewest scans which together scan exactly `outstanding` callbacks.
+///
+/// Each scan may cover any number of callbacks in `sizes` (the `NUMSCANS` of the available scan
+/// keys). A scan may never go past the last callback, so the planned sizes must sum to exactly
+/// `outstanding`. Returns the size of each scan in order, largest first, or `None` if no
+/// combination of sizes covers the callbacks.
+///
+/// # Example
+/// ```rust
+/// # use zk_callbacks::generic::scan::plan_scans;
+/// assert_eq!(plan_scans(7, &[1, 2, 5]), Some(vec![5, 2]));
+/// assert_eq!(plan_scans(6, &[4, 3]), Some(vec![3, 3]));
+/// assert_eq!(plan_scans(5, &[2, 4]), None);
+/// assert_eq!(plan_scans(0, &[2]), Some(vec![]));
+/// ```
+pub fn plan_scans(outstanding: usize, sizes: &[usize]) -> Option<Vec<usize>> {
+    // best[n] is the fewest scans covering n callbacks, along with the size of the last scan.
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; outstanding + 1];
+    best[0] = Some((0, 0));
+    for n in 1..=outstanding {
+        for &size in sizes {
+            if size == 0 || size > n {
+                continue;
+            }
+            if let Some((count, _)) = best[n - size]
+                && best[n].is_none_or(|(c, _)| count + 1 < c)
+            {
+                best[n] = Some((count + 1, size));
+            }
+        }
+    }
+
+    let mut plan = vec![];
+    let mut n = outstanding;
+    while n > 0 {
+        let (_, size) = best[n]?;
+        plan.push(size);
+        n -= size;
+    }
+    plan.sort_unstable_by(|a, b| b.cmp(a));
+    Some(plan)
+}
+
+/// The public arguments of a scan over any number of callbacks, as field elements.
+///
+/// These are the elements of the [`PubScanArgs`] of the scan, and are passed to the bulletin in
+/// place of the [`PubScanArgs`], so a [`ScanProof`] may be verified without knowing its
+/// `NUMSCANS`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScanArgs<F: PrimeField>(pub Vec<F>);
+
+impl<F: PrimeField> ToConstraintField<F> for ScanArgs<F> {
+    fn to_field_elements(&self) -> Option<Vec<F>> {
+        Some(self.0.clone())
+    }
+}
+
+/// A single scan proof produced by [`User::scan_all`].
+#[derive(Clone)]
+pub struct ScanProof<
+    F: PrimeField + Absorb,
+    Snark: SNARK<F>,
+    CBArgs: Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+> {
+    /// The number of callbacks scanned by the proof.
+    pub num_scans: usize,
+    /// The public scan arguments of the proof.
+    pub pub_args: ScanArgs<F>,
+    /// The executed scan.
+    pub method: ExecutedMethod<F, Snark, CBArgs, Crypto, 0>,
+}
+
+/// A cryptographic rng which may be used as a trait object.
+trait CryptoRngCore: CryptoRng + RngCore {}
+
+impl<R: CryptoRng + RngCore + ?Sized> CryptoRngCore for R {}
+
+type ScanStep<'a, F, U, CBArgs, CBArgsVar, Crypto, CBul, Snark, Bul> = dyn Fn(
+        &mut User<F, U>,
+        &mut dyn CryptoRngCore,
+        (
+            <Bul as PublicUserBul<F, U>>::MembershipPub,
+            <Bul as PublicUserBul<F, U>>::MembershipWitness,
+        ),
+        bool,
+        &CBul,
+        (bool, bool),
+        Time<F>,
+        Vec<Callback<F, U, CBArgs, CBArgsVar>>,
+    ) -> Result<ScanProof<F, Snark, CBArgs, Crypto>, UserError>
+    + 'a;
+
+/// The proving key for a scan over some number of callbacks, used by [`User::scan_all`].
+///
+/// This wraps the proving key of [`get_scan_interaction`] for some `NUMSCANS`, so keys for
+/// different numbers of callbacks may be held in one list.
+pub struct ScanKey<
+    'a,
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    CBul: PublicCallbackBul<F, CBArgs, Crypto>,
+    Snark: SNARK<F>,
+    Bul: PublicUserBul<F, U>,
+> {
+    num_scans: usize,
+    step: Box<ScanStep<'a, F, U, CBArgs, CBArgsVar, Crypto, CBul, Snark, Bul>>,
+}
+
+impl<
+    'a,
+    F: PrimeField + Absorb,
+    U: UserData<F> + 'static,
+    CBArgs: Clone + std::fmt::Debug + PartialEq + Eq + 'static,
+    CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+    Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq + 'static,
+    CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + 'static,
+    Snark: SNARK<F, Error = SynthesisError>,
+    Bul: PublicUserBul<F, U>,
+> ScanKey<'a, F, U, CBArgs, CBArgsVar, Crypto, CBul, Snark, Bul>
+where
+    Standard: Distribution<F>,
+    U::UserDataVar: CondSelectGadget<F> + EqGadget<F>,
+    CBul::MembershipPub: std::fmt::Debug + ToConstraintField<F>,
+    CBul::NonMembershipPub: std::fmt::Debug + ToConstraintField<F>,
+{
+    /// Wrap the proving key for a scan over `NUMSCANS` callbacks.
+    ///
+    /// The key must be generated from [`get_scan_interaction`] with the same `H` and `NUMSCANS`.
+    pub fn new<H: FieldHash<F> + 'static, const NUMSCANS: usize>(
+        pk: &'a Snark::ProvingKey,
+    ) -> Self {
+        let step = move |user: &mut User<F, U>,
+                         mut rng: &mut dyn CryptoRngCore,
+                         bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
+                         is_memb_data_const: bool,
+                         cbul: &CBul,
+                         is_memb_nmemb_const: (bool, bool),
+                         cur_time: Time<F>,
+                         cb_methods: Vec<Callback<F, U, CBArgs, CBArgsVar>>|
+              -> Result<ScanProof<F, Snark, CBArgs, Crypto>, UserError> {
+            let (ps, prs) = user.get_scan_arguments::<CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>(
+                cbul,
+                is_memb_nmemb_const,
+                cur_time,
+                cb_methods,
+            )?;
+
+            let method = user.interact::<H, PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PubScanArgsVar<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS>, PrivScanArgsVar<F, CBArgs, Crypto, CBul, NUMSCANS>, CBArgs, CBArgsVar, Crypto, Snark, Bul, 0>(
+                &mut rng,
+                get_scan_interaction::<F, U, CBArgs, CBArgsVar, Crypto, CBul, H, NUMSCANS>(),
+                [],
+                cur_time,
+                bul_data,
+                is_memb_data_const,
+                pk,
+                ps.clone(),
+                prs,
+                true,
+            )?;
+
+            Ok(ScanProof {
+                num_scans: NUMSCANS,
+                pub_args: ScanArgs(ps.to_field_elements().unwrap_or_default()),
+                method,
+            })
+        };
+
+        Self {
+            num_scans: NUMSCANS,
+            step: Box::new(step),
+        }
+    }
+}
+
+impl<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    CBul: PublicCallbackBul<F, CBArgs, Crypto>,
+    Snark: SNARK<F>,
+    Bul: PublicUserBul<F, U>,
+> ScanKey<'_, F, U, CBArgs, CBArgsVar, Crypto, CBul, Snark, Bul>
+{
+    /// The number of callbacks scanned with this key.
+    pub fn num_scans(&self) -> usize {
+        self.num_scans
+    }
+
+    /// Scan the next `num_scans` callbacks of a user.
+    ///
+    /// This is [`User::scan_callbacks`] for the `NUMSCANS` of the key, with the membership data
+    /// of the user given directly, as in [`User::interact`]. The scan must then be verified and
+    /// appended to the user bulletin by the caller, with the public arguments
+    /// [`ScanProof::pub_args`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn scan(
+        &self,
+        user: &mut User<F, U>,
+        rng: &mut (impl CryptoRng + RngCore),
+        bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
+        is_memb_data_const: bool,
+        cbul: &CBul,
+        is_memb_nmemb_const: (bool, bool),
+        cur_time: Time<F>,
+        cb_methods: Vec<Callback<F, U, CBArgs, CBArgsVar>>,
+    ) -> Result<ScanProof<F, Snark, CBArgs, Crypto>, UserError> {
+        (self.step)(
+            user,
+            rng,
+            bul_data,
+            is_memb_data_const,
+            cbul,
+            is_memb_nmemb_const,
+            cur_time,
+            cb_methods,
+        )
     }
 }
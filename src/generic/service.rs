@@ -1,8 +1,8 @@
 use crate::{
     crypto::{enc::AECipherSigZK, hash::FieldHash, rr::RRSigner},
     generic::{
-        bulletin::{BulError, PublicUserBul},
-        callbacks::CallbackCom,
+        bulletin::{BulError, PublicCallbackBul, PublicUserBul},
+        callbacks::{CallbackCom, CallbackTicket, MultiUseTicket, is_inert_com, release_tag},
         interaction::Callback,
         object::Time,
         user::{ExecutedMethod, UserData},
@@ -89,7 +89,7 @@ pub type Called<F, A, Crypto> = (
 ///
 ///     fn store_interaction<U: UserData<Fr>, Snark: SNARK<Fr>, const NUMCBS: usize>(&mut self, interaction: ExecutedMethod<Fr, Snark, Fr, NoSigOTP<Fr>, NUMCBS>, data: u64) -> Result<(), Self::Error> {
 ///         self.interaction_ids.push(data);
-///         self.cb_tickets.push(interaction.cb_tik_list.to_vec());
+///         self.cb_tickets.push(interaction.issued_tickets());
 ///         Ok(())
 ///     }
 /// }
@@ -127,6 +127,151 @@ pub trait ServiceProvider<
         Ok((ticket.cb_entry.tik, enc, sig))
     }
 
+    /// Releases a callback, declaring the service will never call it.
+    ///
+    /// This produces a signed no-op: the ticket is signed over its
+    /// [`release_tag`](`super::callbacks::release_tag`) rather than over encrypted arguments, and
+    /// should be posted to the callback bulletin with
+    /// [`verify_release_and_append`](`super::bulletin::CallbackBul::verify_release_and_append`).
+    /// Once posted, the ticket can never be called, and the user drops it from their callback list
+    /// on their next scan without applying any callback. The release is recognized by the signed
+    /// tag, so it remains a no-op even if it is posted as a call.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::scannable_zk_object;
+    /// # use zk_callbacks::generic::user::{User, UserVar};
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use ark_groth16::Groth16;
+    /// # use zk_callbacks::generic::interaction::{Callback, Interaction};
+    /// # use zk_callbacks::generic::object::{Id, Time};
+    /// # use zk_callbacks::generic::bulletin::CallbackBul;
+    /// # use zk_callbacks::generic::service::ServiceProvider;
+    /// # use zk_callbacks::generic::scan::{PubScanArgs, get_scan_interaction};
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::impls::centralized::crypto::{FakeSigPrivkey, FakeSigPubkey, NoSigOTP};
+    /// # use zk_callbacks::impls::centralized::ds::sigstore::GRSchnorrCallbackStore;
+    /// # type Groth = Groth16<E>;
+    /// type CBul = GRSchnorrCallbackStore<Fr>;
+    ///
+    /// #[scannable_zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub karma: Fr,
+    /// }
+    ///
+    /// fn method<'a>(old_user: &'a User<Fr, Data>, _pub: (), _priv: ()) -> User<Fr, Data> {
+    ///     old_user.clone()
+    /// }
+    ///
+    /// fn predicate<'a>(old_user: &'a UserVar<Fr, Data>, new_user: &'a UserVar<Fr, Data>, _pub: (), _priv: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     old_user.data.karma.is_eq(&new_user.data.karma)
+    /// }
+    ///
+    /// fn callback<'a>(old_user: &'a User<Fr, Data>, args: Fr) -> User<Fr, Data> {
+    ///     let mut u = old_user.clone();
+    ///     u.data.karma += args;
+    ///     u
+    /// }
+    ///
+    /// fn enforce_callback<'a>(old_user: &'a UserVar<Fr, Data>, args: FpVar<Fr>) -> Result<UserVar<Fr, Data>, SynthesisError> {
+    ///     let mut u = old_user.clone();
+    ///     u.data.karma = u.data.karma + args;
+    ///     Ok(u)
+    /// }
+    ///
+    /// fn main () {
+    ///     let cb = Callback {
+    ///         method_id: Id::from(0),
+    ///         expirable: false,
+    ///         expiration: Time::from(10),
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
+    ///     };
+    ///
+    ///     let int = Interaction {
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
+    ///         callbacks: [cb.clone(), cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
+    ///     };
+    ///
+    ///     let mut rng = thread_rng();
+    ///     let mut cbul = CBul::new(&mut rng);
+    ///
+    ///     let ex: PubScanArgs<_, Data, _, _, NoSigOTP<Fr>, CBul, 2> = PubScanArgs {
+    ///         memb_pub: [cbul.get_pubkey(); 2],
+    ///         is_memb_data_const: true,
+    ///         nmemb_pub: [cbul.nmemb_bul.get_pubkey(); 2],
+    ///         is_nmemb_data_const: true,
+    ///         cur_time: Fr::from(0),
+    ///         bulletin: cbul.clone(),
+    ///         cb_methods: vec![cb.clone()],
+    ///     };
+    ///
+    ///     let (pk, _) = int.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), (), false);
+    ///     let (pks, _) = get_scan_interaction::<_, _, _, _, _, _, Poseidon<2>, 2>().generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), ex, true);
+    ///
+    ///     let mut u = User::create(Data { karma: Fr::from(0) }, &mut rng);
+    ///     let exec = u.interact::<Poseidon<2>, (), (), (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 2>(&mut rng, int, [FakeSigPubkey::pk(), FakeSigPubkey::pk()], Time::from(0), ((), ()), true, &pk, (), (), false).unwrap();
+    ///
+    ///     // The service releases the first ticket.
+    ///     let (tik, _, sig) = <DummyStore as ServiceProvider<Fr, Fr, FpVar<Fr>, NoSigOTP<Fr>>>::release::<Poseidon<2>>(&DummyStore, exec.cb_tik_list[0].0.clone(), FakeSigPrivkey::sk()).unwrap();
+    ///     <CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_release_and_append::<Poseidon<2>>(&mut cbul, tik, sig).unwrap();
+    ///
+    ///     // The release of the second ticket is posted as a call instead.
+    ///     let (tik, enc, sig) = <DummyStore as ServiceProvider<Fr, Fr, FpVar<Fr>, NoSigOTP<Fr>>>::release::<Poseidon<2>>(&DummyStore, exec.cb_tik_list[1].0.clone(), FakeSigPrivkey::sk()).unwrap();
+    ///     <CBul as CallbackBul<Fr, Fr, NoSigOTP<Fr>>>::verify_call_and_append(&mut cbul, tik, enc, sig, Time::from(5)).unwrap();
+    ///     cbul.update_epoch(&mut rng);
+    ///
+    ///     let cur_time = cbul.get_epoch();
+    ///     u.scan_callbacks::<Poseidon<2>, Fr, FpVar<Fr>, NoSigOTP<Fr>, CBul, Groth, DummyStore, 2>(&mut rng, &DummyStore, true, &pks, &cbul, (true, true), cur_time, vec![cb.clone()]).unwrap();
+    ///
+    ///     // Both tickets are dropped, and neither is called.
+    ///     assert!(!u.is_scanning());
+    ///     assert!(u.callback_view::<Fr, NoSigOTP<Fr>>().unwrap().is_empty());
+    ///     assert_eq!(u.data.karma, Fr::from(0));
+    /// }
+    /// ```
+    fn release<H: FieldHash<F>>(
+        &self,
+        ticket: CallbackCom<F, CBArgs, Crypto>,
+        sk: Crypto::SigSK,
+    ) -> Result<Called<F, CBArgs, Crypto>, Self::Error> {
+        let enc = Crypto::release_ct(release_tag::<F, H>(&ticket.cb_entry.tik));
+        let sig = sk.sign_message(&enc);
+        Ok((ticket.cb_entry.tik, enc, sig))
+    }
+
+    /// Calls a multi-use ticket, producing called data for the next unused ticket.
+    ///
+    /// The next ticket is the first ticket of the multi-use ticket which is not in the callback
+    /// bulletin `cbul`. Returns `None` if every ticket has already been called, so that a
+    /// multi-use ticket may never be called more times than it allows, or if the tickets in
+    /// `cbul` were not called in order. The call should be posted with
+    /// [`verify_call_next_and_append`](`super::bulletin::CallbackBul::verify_call_next_and_append`).
+    ///
+    /// See [`call`](`ServiceProvider::call`).
+    fn call_next<CBul: PublicCallbackBul<F, CBArgs, Crypto>>(
+        &self,
+        ticket: &MultiUseTicket<F, CBArgs, Crypto>,
+        cbul: &CBul,
+        arguments: CBArgs,
+        sk: Crypto::SigSK,
+    ) -> Option<Result<Called<F, CBArgs, Crypto>, Self::Error>> {
+        let next = cbul.num_calls(&ticket.get_tickets())?;
+        let (cb, _) = ticket.tickets.get(next)?;
+        Some(self.call(cb.clone(), arguments, sk))
+    }
+
     /// Check if the service has ever received a specific ticket before, as otherwise a service may
     /// receive overlapping callbacks.
     fn has_never_received_tik(&self, ticket: Crypto::SigPK) -> bool;
@@ -149,6 +294,8 @@ pub trait ServiceProvider<
     ///- The *new* user is contained within the user bulletin. (Note that this means the new
     ///updated user must be in the bulletin before calling this)
     ///- None of the tickets were previously received.
+    ///- The tickets match the first `NUMCBS` callbacks in `cb_list`, and any remaining tickets
+    ///are inert (see [`User::interact_padded`](super::user::User::interact_padded)).
     ///- The proof is correct.
     ///
     /// # Arguments
@@ -185,67 +332,82 @@ pub trait ServiceProvider<
         is_memb_data_const: bool,
         verif_key: &Snark::VerifyingKey,
     ) -> bool {
-        let out = bul.verify_in::<PubArgs, Snark, NUMCBS>(
-            interaction_request.new_object,
-            interaction_request.old_nullifier,
-            interaction_request.cb_com_list,
-            args.clone(),
-            interaction_request.proof.clone(),
-            memb_data.clone(),
+        approve_with::<F, CBArgs, CBArgsVar, Crypto, Self, U, Snark, PubArgs, Bul, H, NUMCBS>(
+            self,
+            interaction_request,
+            sk,
+            args,
+            bul,
+            cb_list.len().min(NUMCBS),
+            |i, ticket| {
+                ticket.expirable == cb_list[i].expirable
+                    && ticket.expiration == cb_list[i].expiration + cur_time
+                    && ticket.cb_method_id == cb_list[i].method_id
+            },
+            memb_data,
+            is_memb_data_const,
             verif_key,
-        );
-        if !out {
-            return false;
-        }
-
-        for i in 0..NUMCBS {
-            let cb = interaction_request.cb_tik_list[i].0.clone();
-
-            if cb.cb_entry.expirable != cb_list[i].expirable {
-                return false;
-            }
-
-            if cb.cb_entry.expiration != cb_list[i].expiration + cur_time {
-                return false;
-            }
-
-            if cb.cb_entry.cb_method_id != cb_list[i].method_id {
-                return false;
-            }
-
-            let cb_com = interaction_request.cb_com_list[i].clone();
-
-            if cb_com != CallbackCom::commit::<H>(&cb) {
-                return false;
-            }
-
-            let rand = interaction_request.cb_tik_list[i].1.clone();
-            let vpk = sk.rerand(rand).sk_to_pk();
-            if vpk != cb.cb_entry.tik {
-                return false;
-            }
+        )
+    }
 
-            if !self.has_never_received_tik(cb.cb_entry.tik) {
-                return false;
-            }
+    /// Given an interaction with hidden callback method ids, this function checks if the
+    /// interaction is approved.
+    ///
+    /// This is the same as [`approve_interaction`](`ServiceProvider::approve_interaction`), except
+    /// that the method ids of the tickets are blinded (see
+    /// [`Interaction::hiding_ids`](`super::interaction::Interaction::hiding_ids`)). Instead of a
+    /// list of callbacks, this takes the number of callbacks `num_cbs` the service expects, and
+    /// the `allowed` callbacks; each ticket must expire as one of the allowed callbacks. That the
+    /// hidden method ids are allowed is checked by the proof, so the verification key must be
+    /// generated for the same allowed ids.
+    fn approve_interaction_hidden_ids<
+        U: UserData<F>,
+        Snark: SNARK<F>,
+        PubArgs: Clone + ToConstraintField<F>,
+        Bul: PublicUserBul<F, U>,
+        H: FieldHash<F>,
+        const NUMCBS: usize,
+    >(
+        &self,
+        interaction_request: &ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>,
+        sk: Crypto::SigSK,
+        args: PubArgs,
+        bul: &Bul,
+        allowed: Vec<Callback<F, U, CBArgs, CBArgsVar>>,
+        num_cbs: usize,
+        cur_time: Time<F>,
+        memb_data: Bul::MembershipPub,
+        is_memb_data_const: bool,
+        verif_key: &Snark::VerifyingKey,
+    ) -> bool {
+        if num_cbs > NUMCBS {
+            return false;
         }
 
-        let mut pub_inputs = vec![
-            interaction_request.new_object,
-            interaction_request.old_nullifier,
-        ];
-        pub_inputs.extend::<Vec<F>>(args.to_field_elements().unwrap());
-        pub_inputs.extend::<Vec<F>>(interaction_request.cb_com_list.to_field_elements().unwrap());
-        if !is_memb_data_const {
-            pub_inputs.extend(memb_data.to_field_elements().unwrap());
-        }
-        Snark::verify(verif_key, &pub_inputs, &interaction_request.proof).unwrap_or(false)
+        approve_with::<F, CBArgs, CBArgsVar, Crypto, Self, U, Snark, PubArgs, Bul, H, NUMCBS>(
+            self,
+            interaction_request,
+            sk,
+            args,
+            bul,
+            num_cbs,
+            |_, ticket| {
+                allowed.iter().any(|cb| {
+                    ticket.expirable == cb.expirable
+                        && ticket.expiration == cb.expiration + cur_time
+                })
+            },
+            memb_data,
+            is_memb_data_const,
+            verif_key,
+        )
     }
 
     /// Approves an interaction, as well as stores it.
     ///
     /// # Example
     /// ```rust
+    /// # use std::sync::Arc;
     /// # use zk_callbacks::zk_object;
     /// # use zk_callbacks::generic::user::User;
     /// # use rand::thread_rng;
@@ -295,7 +457,7 @@ pub trait ServiceProvider<
     ///
     ///     fn store_interaction<U: UserData<Fr>, Snark: SNARK<Fr>, const NUMCBS: usize>(&mut self, interaction: ExecutedMethod<Fr, Snark, Fr, NoSigOTP<Fr>, NUMCBS>, data: u64) -> Result<(), Self::Error> {
     ///         self.interaction_ids.push(data);
-    ///         self.cb_tickets.push(interaction.cb_tik_list.to_vec());
+    ///         self.cb_tickets.push(interaction.issued_tickets());
     ///         Ok(())
     ///     }
     /// }
@@ -336,13 +498,16 @@ pub trait ServiceProvider<
     ///         method_id: Id::from(0),
     ///         expirable: false,
     ///         expiration: Time::from(10),
-    ///         method: callback,
-    ///         predicate: enforce_callback
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
     ///     };
     ///
     ///     let int = Interaction {
-    ///         meth: (method, predicate),
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
     ///         callbacks: [cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
     ///     };
     ///
     ///     let mut forum = AnonForum {
@@ -406,3 +571,86 @@ pub trait ServiceProvider<
             .map_err(BulError::AppendError)
     }
 }
+
+/// Checks an interaction for a service: the proof, that the first `num_cbs` tickets are
+/// well-formed and satisfy `ticket_ok`, and that any remaining tickets are inert.
+fn approve_with<
+    F: PrimeField + Absorb,
+    CBArgs: Clone,
+    CBArgsVar: AllocVar<CBArgs, F>,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    S: ServiceProvider<F, CBArgs, CBArgsVar, Crypto> + ?Sized,
+    U: UserData<F>,
+    Snark: SNARK<F>,
+    PubArgs: Clone + ToConstraintField<F>,
+    Bul: PublicUserBul<F, U>,
+    H: FieldHash<F>,
+    const NUMCBS: usize,
+>(
+    service: &S,
+    interaction_request: &ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>,
+    sk: Crypto::SigSK,
+    args: PubArgs,
+    bul: &Bul,
+    num_cbs: usize,
+    ticket_ok: impl Fn(usize, &CallbackTicket<F, CBArgs, Crypto>) -> bool,
+    memb_data: Bul::MembershipPub,
+    is_memb_data_const: bool,
+    verif_key: &Snark::VerifyingKey,
+) -> bool {
+    let out = bul.verify_in::<PubArgs, Snark, NUMCBS>(
+        interaction_request.new_object,
+        interaction_request.old_nullifier,
+        interaction_request.cb_com_list,
+        args.clone(),
+        interaction_request.proof.clone(),
+        memb_data.clone(),
+        verif_key,
+    );
+    if !out {
+        return false;
+    }
+
+    for i in 0..NUMCBS {
+        // Any slots beyond the expected callbacks must be padded with inert tickets.
+        if i >= num_cbs {
+            if !is_inert_com(&interaction_request.cb_com_list[i]) {
+                return false;
+            }
+            continue;
+        }
+
+        let cb = interaction_request.cb_tik_list[i].0.clone();
+
+        if !ticket_ok(i, &cb.cb_entry) {
+            return false;
+        }
+
+        let cb_com = interaction_request.cb_com_list[i].clone();
+
+        if cb_com != CallbackCom::commit::<H>(&cb) {
+            return false;
+        }
+
+        let rand = interaction_request.cb_tik_list[i].1.clone();
+        let vpk = sk.rerand(rand).sk_to_pk();
+        if vpk != cb.cb_entry.tik {
+            return false;
+        }
+
+        if !service.has_never_received_tik(cb.cb_entry.tik) {
+            return false;
+        }
+    }
+
+    let mut pub_inputs = vec![
+        interaction_request.new_object,
+        interaction_request.old_nullifier,
+    ];
+    pub_inputs.extend::<Vec<F>>(args.to_field_elements().unwrap());
+    pub_inputs.extend::<Vec<F>>(interaction_request.cb_com_list.to_field_elements().unwrap());
+    if !is_memb_data_const {
+        pub_inputs.extend(memb_data.to_field_elements().unwrap());
+    }
+    Snark::verify(verif_key, &pub_inputs, &interaction_request.proof).unwrap_or(false)
+}
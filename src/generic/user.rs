@@ -5,16 +5,31 @@ use crate::generic::fold::{FoldInput, Rerand, RerandVar};
 #[cfg(feature = "folding")]
 use crate::generic::object::{ComRand, ComRandVar};
 use crate::{
-    crypto::{enc::AECipherSigZK, hash::FieldHash, rr::RRVerifier},
+    crypto::{
+        com::{HashCom, UserCommitment},
+        enc::AECipherSigZK,
+        hash::FieldHash,
+        rr::RRVerifier,
+    },
     generic::{
         bulletin::{PublicCallbackBul, PublicUserBul},
-        callbacks::{CallbackCom, add_ticket_to_hc, create_cbs_from_interaction},
+        callbacks::{
+            CallbackCom, add_ticket_to_hc, callbacks_for_service, create_cbs_from_interaction,
+            is_inert_com,
+        },
         interaction::{
-            ExecMethodCircuit, Interaction, ProvePredInCircuit, ProvePredicateCircuit,
-            SingularPredicate,
+            ChainedExecMethodCircuit, ChainedInteraction, Disclosure, ExecMethodCircuit,
+            Interaction, MultiExecMethodCircuit, MultiInteraction, ProveCommittedCircuit,
+            ProvePredInCircuit, ProvePredicateCircuit, Revealed, SingularPredicate,
+        },
+        object::{
+            Com, ComVar, Id, Nul, Pseudonym, Ser, SerVar, Time, ZKFields, ZKFieldsVar, derive_nul,
+            derive_pseudonym,
+        },
+        scan::{
+            PrivScanArgs, PrivScanArgsVar, PubScanArgs, PubScanArgsVar, ScanKey, ScanProof,
+            get_scan_interaction, plan_scans,
         },
-        object::{Com, ComVar, Nul, Ser, SerVar, Time, ZKFields, ZKFieldsVar},
-        scan::{PrivScanArgs, PrivScanArgsVar, PubScanArgs, PubScanArgsVar, get_scan_interaction},
     },
 };
 use ark_crypto_primitives::sponge::Absorb;
@@ -38,6 +53,12 @@ use rand::{CryptoRng, Rng, RngCore, distributions::Standard, prelude::Distributi
 use std::{
     borrow::Borrow,
     io::{Read, Write},
+    marker::PhantomData,
+};
+#[cfg(feature = "trace")]
+use {
+    ark_relations::r1cs::{ConstraintLayer, TracingMode},
+    tracing_subscriber::layer::SubscriberExt,
 };
 
 use crate::generic::interaction::Callback;
@@ -285,6 +306,99 @@ pub trait UserData<F: PrimeField + Absorb>: Clone + Eq + std::fmt::Debug {
     fn serialize_in_zk(user_var: Self::UserDataVar) -> Result<Vec<SerVar<F>>, SynthesisError>;
 }
 
+/// A typed view of the callbacks stored within a user.
+///
+/// Users store their callbacks serialized, so that the user does not depend on the callback
+/// arguments or cryptography. A view, obtained with [`User::callback_view`], holds the
+/// deserialized callbacks, indexed as in the user, and answers queries about them. The view
+/// borrows the user, so the user can not change while the view is in use.
+///
+/// While a scan is in progress, the user keeps the callbacks as of the start of the scan, along
+/// with the callbacks which are still outstanding so far in the scan (see
+/// [`CallbackView::in_progress`]).
+#[derive(Clone, Debug)]
+pub struct CallbackView<'a, F: PrimeField + Absorb, Args: Clone, Crypto: AECipherSigZK<F, Args>> {
+    callbacks: Vec<CallbackCom<F, Args, Crypto>>,
+    in_progress: Option<Vec<usize>>,
+    _user: PhantomData<&'a ()>,
+}
+
+impl<F: PrimeField + Absorb, Args: Clone, Crypto: AECipherSigZK<F, Args>>
+    CallbackView<'_, F, Args, Crypto>
+{
+    /// The number of outstanding callbacks, or the number at the start of the scan if a scan is in
+    /// progress.
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Whether there are no outstanding callbacks.
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Get the callback at some index.
+    pub fn get(&self, index: usize) -> Option<&CallbackCom<F, Args, Crypto>> {
+        self.callbacks.get(index)
+    }
+
+    /// Iterate over the callbacks in order.
+    pub fn iter(&self) -> impl Iterator<Item = &CallbackCom<F, Args, Crypto>> {
+        self.callbacks.iter()
+    }
+
+    /// Get the index of the callback with some ticket.
+    pub fn index_of(&self, tik: &Crypto::SigPK) -> Option<usize> {
+        self.callbacks.iter().position(|cb| cb.cb_entry.tik == *tik)
+    }
+
+    /// Get the callback with some ticket.
+    pub fn get_by_ticket(&self, tik: &Crypto::SigPK) -> Option<&CallbackCom<F, Args, Crypto>> {
+        self.index_of(tik).map(|i| &self.callbacks[i])
+    }
+
+    /// The callbacks (with their indices) which call some method.
+    pub fn for_method(
+        &self,
+        method_id: Id<F>,
+    ) -> impl Iterator<Item = (usize, &CallbackCom<F, Args, Crypto>)> {
+        self.callbacks
+            .iter()
+            .enumerate()
+            .filter(move |(_, cb)| cb.cb_entry.cb_method_id == method_id)
+    }
+
+    /// The callbacks (with their indices) which expire before some time.
+    ///
+    /// See [`CallbackTicket::expired_before`](super::callbacks::CallbackTicket::expired_before).
+    pub fn expiring_before(
+        &self,
+        time: Time<F>,
+    ) -> impl Iterator<Item = (usize, &CallbackCom<F, Args, Crypto>)> {
+        self.callbacks
+            .iter()
+            .enumerate()
+            .filter(move |(_, cb)| cb.cb_entry.expired_before(time))
+    }
+
+    /// Whether a scan is in progress (see [`User::is_scanning`]).
+    pub fn scan_in_progress(&self) -> bool {
+        self.in_progress.is_some()
+    }
+
+    /// The callbacks (with their indices) which are still outstanding so far in the scan in
+    /// progress: those not yet scanned, and those scanned but not yet called. These are the
+    /// callbacks the user will hold once the scan completes, if no more are called.
+    ///
+    /// If no scan is in progress, these are all the callbacks.
+    pub fn in_progress(&self) -> impl Iterator<Item = (usize, &CallbackCom<F, Args, Crypto>)> {
+        self.callbacks
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| self.in_progress.as_ref().is_none_or(|ip| ip.contains(i)))
+    }
+}
+
 /// Struct representing the whole user object.
 ///
 /// This struct consists of user data (which implements [`UserData`]), along with other data. The
@@ -467,6 +581,180 @@ impl<F: PrimeField + Absorb, U: UserData<F>> AllocVar<User<F, U>, F> for UserVar
     }
 }
 
+/// An error indicating a user operation failed.
+///
+/// This is returned by every fallible (out of circuit) method on [`User`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UserError {
+    /// The statement being proven does not hold, for example because the predicate of an
+    /// interaction is not satisfied by the old and new user. Contains the namespace path of the
+    /// first unsatisfied constraint, if known.
+    ///
+    /// Returned by the `dry_run_*` methods, and by the proving methods in debug builds only. The
+    /// constraint is only named by the `dry_run_*` methods with the `trace` feature enabled.
+    PredicateUnsatisfied(Option<String>),
+    /// The user bulletin has no membership data for the user commitment.
+    MissingMembershipData,
+    /// A scan is in progress, so the operation may not be performed until it is complete.
+    ScanInProgress,
+    /// A callback index is out of range of the callbacks stored in the user.
+    CallbackIndexOutOfRange {
+        /// The requested index.
+        index: usize,
+        /// The number of callbacks stored in the user.
+        len: usize,
+    },
+    /// A callback stored in the user could not be deserialized.
+    CorruptedCallbackStorage {
+        /// The index of the corrupted callback.
+        index: usize,
+    },
+    /// A called callback does not have a valid signature from the service under its ticket.
+    InvalidCallbackSignature {
+        /// The index of the callback.
+        index: usize,
+    },
+    /// The number of callbacks issued does not match the number the proof is made for.
+    CallbackCountMismatch {
+        /// The number of callbacks the proof is made for.
+        expected: usize,
+        /// The number of callbacks issued.
+        found: usize,
+    },
+    /// The outstanding callbacks cannot be scanned exactly with the available scan keys.
+    NoScanPlan {
+        /// The number of callbacks left to scan.
+        outstanding: usize,
+    },
+    /// The user bulletin rejected an updated user.
+    BulletinRejected,
+    /// A pending interaction was finalized into a user other than the one it was made from.
+    StalePendingInteraction,
+    /// Constraint synthesis or proof generation failed.
+    Snark(SynthesisError),
+}
+
+impl From<SynthesisError> for UserError {
+    fn from(e: SynthesisError) -> Self {
+        UserError::Snark(e)
+    }
+}
+
+impl std::fmt::Display for UserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserError::PredicateUnsatisfied(Some(name)) => {
+                write!(f, "statement unsatisfied at constraint {}", name)
+            }
+            UserError::PredicateUnsatisfied(None) => write!(f, "statement unsatisfied"),
+            UserError::MissingMembershipData => write!(f, "no membership data for the user"),
+            UserError::ScanInProgress => write!(f, "a scan is in progress"),
+            UserError::CallbackIndexOutOfRange { index, len } => {
+                write!(
+                    f,
+                    "callback index {} out of range ({} callbacks)",
+                    index, len
+                )
+            }
+            UserError::CorruptedCallbackStorage { index } => {
+                write!(f, "stored callback {} is corrupted", index)
+            }
+            UserError::InvalidCallbackSignature { index } => {
+                write!(f, "called callback {} has an invalid signature", index)
+            }
+            UserError::CallbackCountMismatch { expected, found } => {
+                write!(f, "expected {} callbacks, found {}", expected, found)
+            }
+            UserError::NoScanPlan { outstanding } => {
+                write!(
+                    f,
+                    "no scan plan covers {} outstanding callbacks",
+                    outstanding
+                )
+            }
+            UserError::BulletinRejected => write!(f, "the user bulletin rejected the user"),
+            UserError::StalePendingInteraction => {
+                write!(f, "the pending interaction was not made from this user")
+            }
+            UserError::Snark(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for UserError {}
+
+/// Synthesizes a circuit and checks that it is satisfied.
+///
+/// This is run by the `dry_run_*` methods, and before proving in debug builds only, since it
+/// synthesizes the circuit a second time. The first unsatisfied constraint is only looked up if
+/// `named` is set, which requires a [`ConstraintLayer`](ark_relations::r1cs::ConstraintLayer) to
+/// be installed (see [`dry_run_circuit`]); otherwise the check is silent.
+fn ensure_satisfied<F: PrimeField>(
+    circuit: impl ConstraintSynthesizer<F>,
+    named: bool,
+) -> Result<(), UserError> {
+    let cs = ConstraintSystem::<F>::new_ref();
+    circuit.generate_constraints(cs.clone())?;
+    if named {
+        return match cs.which_is_unsatisfied()? {
+            None => Ok(()),
+            name => Err(UserError::PredicateUnsatisfied(name)),
+        };
+    }
+    match is_satisfied_untraced(&cs)? {
+        true => Ok(()),
+        false => Err(UserError::PredicateUnsatisfied(None)),
+    }
+}
+
+/// Checks that a synthesized constraint system is satisfied.
+///
+/// Unlike [`ConstraintSystemRef::is_satisfied`], this never looks up constraint traces, so it does
+/// not print a warning when no [`ConstraintLayer`](ark_relations::r1cs::ConstraintLayer) is
+/// installed.
+pub(crate) fn is_satisfied_untraced<F: PrimeField>(
+    cs: &ConstraintSystemRef<F>,
+) -> Result<bool, SynthesisError> {
+    if cs.is_in_setup_mode() {
+        return Err(SynthesisError::AssignmentMissing);
+    }
+    cs.finalize();
+    let matrices = cs.to_matrices().ok_or(SynthesisError::AssignmentMissing)?;
+    let cs = cs.borrow().ok_or(SynthesisError::MissingCS)?;
+    let assignment = [
+        cs.instance_assignment.as_slice(),
+        cs.witness_assignment.as_slice(),
+    ]
+    .concat();
+    let eval = |row: &[(F, usize)]| -> F { row.iter().map(|(c, i)| *c * assignment[*i]).sum() };
+    Ok(matrices
+        .a
+        .iter()
+        .zip(&matrices.b)
+        .zip(&matrices.c)
+        .all(|((a, b), c)| eval(a) * eval(b) == eval(c)))
+}
+
+/// Synthesizes a circuit while tracing constraint namespaces, and checks that it is satisfied.
+///
+/// With the `trace` feature, an unsatisfied constraint is reported by its full namespace path
+/// (for example `ExecMethodCircuit::generate_constraints::...`).
+#[cfg(feature = "trace")]
+fn dry_run_circuit<F: PrimeField>(circuit: impl ConstraintSynthesizer<F>) -> Result<(), UserError> {
+    let mut layer = ConstraintLayer::default();
+    layer.mode = TracingMode::OnlyConstraints;
+    let subscriber = tracing_subscriber::Registry::default().with(layer);
+    tracing::subscriber::with_default(subscriber, || ensure_satisfied(circuit, true))
+}
+
+/// Synthesizes a circuit and checks that it is satisfied.
+///
+/// Without the `trace` feature, an unsatisfied constraint is not named.
+#[cfg(not(feature = "trace"))]
+fn dry_run_circuit<F: PrimeField>(circuit: impl ConstraintSynthesizer<F>) -> Result<(), UserError> {
+    ensure_satisfied(circuit, false)
+}
+
 /// Output data after a method has been executed on a user.
 ///
 /// When a user executes a method, it must prove correctness of execution. To do so, the user
@@ -498,10 +786,138 @@ pub struct ExecutedMethod<
     pub cb_com_list: [Com<F>; NUMCBS],
     /// The current time. This should be validated.
     pub cur_time: Time<F>,
+    /// The pseudonym of the user, if the interaction has a pseudonym scope (see
+    /// [`Interaction::with_pseudonym`]).
+    pub pseudonym: Option<Pseudonym<F>>,
     /// Proof of valid user object update.
     pub proof: Snark::Proof,
 }
 
+impl<
+    F: PrimeField + Absorb,
+    Snark: SNARK<F>,
+    CBArgs: Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    const NUMCBS: usize,
+> ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>
+{
+    /// The number of callback tickets issued, excluding inert tickets padding the list.
+    ///
+    /// Inert tickets are not assumed to be at the end of the list. See
+    /// [`User::interact_padded`].
+    pub fn num_issued(&self) -> usize {
+        self.cb_com_list
+            .iter()
+            .filter(|com| !is_inert_com(*com))
+            .count()
+    }
+
+    /// The issued callback tickets, excluding inert tickets padding the list, in order.
+    ///
+    /// These are the only tickets a service needs to store.
+    pub fn issued_tickets(&self) -> Vec<(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand)> {
+        self.cb_tik_list
+            .iter()
+            .zip(&self.cb_com_list)
+            .filter(|(_, com)| !is_inert_com(*com))
+            .map(|(tik, _)| tik.clone())
+            .collect()
+    }
+}
+
+/// Output data after a multi-user interaction is executed.
+///
+/// This is the analogue of an [`ExecutedMethod`] for a [`MultiInteraction`], with a commitment,
+/// nullifier and list of callback tickets for each user, and a single proof. It is output by
+/// [`User::interact_multi`], and verified by
+/// [`UserBul::verify_multi_interact_and_append`](super::bulletin::UserBul::verify_multi_interact_and_append).
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiExecutedMethod<
+    F: PrimeField + Absorb,
+    Snark: SNARK<F>,
+    CBArgs: Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    const NUMUSERS: usize,
+    const NUMCBS: usize,
+> {
+    /// *Commitments* to the new objects after the method update.
+    pub new_objects: [Com<F>; NUMUSERS],
+    /// The nullifiers of the old users.
+    pub old_nullifiers: [Nul<F>; NUMUSERS],
+    /// The list of callback tickets added to each user.
+    pub cb_tik_lists: [[(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS]; NUMUSERS],
+    /// Commitments to the tickets added to each user.
+    pub cb_com_lists: [[Com<F>; NUMCBS]; NUMUSERS],
+    /// The current time. This should be validated.
+    pub cur_time: Time<F>,
+    /// Proof of valid user object updates.
+    pub proof: Snark::Proof,
+}
+
+/// An interaction which has been proven, but not yet applied to the user.
+///
+/// This is the output of [`User::interact_pending`]. The user it was made from is left unchanged,
+/// so it remains usable if the interaction is rejected. Once the bulletin has accepted the
+/// interaction (for example, once
+/// [`verify_interact_and_append`](super::bulletin::UserBul::verify_interact_and_append)
+/// succeeds), call [`PendingInteraction::finalize`] to update the user. Otherwise, call
+/// [`PendingInteraction::discard`], and the old user may be used as if the interaction never
+/// happened.
+#[derive(Clone)]
+pub struct PendingInteraction<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    Snark: SNARK<F>,
+    CBArgs: Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    const NUMCBS: usize,
+> {
+    new_user: User<F, U>,
+    method: ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>,
+}
+
+impl<
+    F: PrimeField + Absorb,
+    U: UserData<F>,
+    Snark: SNARK<F>,
+    CBArgs: Clone,
+    Crypto: AECipherSigZK<F, CBArgs>,
+    const NUMCBS: usize,
+> PendingInteraction<F, U, Snark, CBArgs, Crypto, NUMCBS>
+{
+    /// The executed method, to be verified by the bulletin and the service.
+    pub fn method(&self) -> &ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS> {
+        &self.method
+    }
+
+    /// The user after the interaction.
+    pub fn new_user(&self) -> &User<F, U> {
+        &self.new_user
+    }
+
+    /// Apply the interaction to the user it was made from, returning the executed method.
+    ///
+    /// Fails with [`UserError::StalePendingInteraction`] (and leaves `user` unchanged) if `user`
+    /// is not the user the interaction was made from, for example if it has been updated since.
+    pub fn finalize(
+        self,
+        user: &mut User<F, U>,
+    ) -> Result<ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>, UserError> {
+        if user.zk_fields.nul != self.method.old_nullifier {
+            return Err(UserError::StalePendingInteraction);
+        }
+        *user = self.new_user;
+        Ok(self.method)
+    }
+
+    /// Discard the interaction, keeping the old user.
+    ///
+    /// The old nullifier has not been revealed to the bulletin, so the old user may still be used.
+    /// If the bulletin *did* accept the interaction, the old user can no longer interact, and the
+    /// interaction should be finalized instead.
+    pub fn discard(self) {}
+}
+
 /// Output data after a proof is made on the user object.
 ///
 /// If one wants to make a standard proof for a user object, this struct captures the data
@@ -547,6 +963,10 @@ where
     /// ```
     ///
     /// Here, `u` is a single user object, with all the data associated to it.
+    ///
+    /// The nullifier of a new user is random rather than derived from the commitment randomness;
+    /// it is only derived (see [`derive_nul`](crate::generic::object::derive_nul)) from the first
+    /// interaction onwards.
     pub fn create(user: U, rng: &mut (impl CryptoRng + RngCore)) -> Self {
         Self {
             data: user,
@@ -557,6 +977,9 @@ where
                 new_in_progress_callback_hash: F::zero(),
                 old_in_progress_callback_hash: F::zero(),
                 is_ingest_over: true,
+                scan_start: F::zero(),
+                last_full_scan: F::zero(),
+                secret: rng.r#gen(),
             },
             callbacks: vec![],
             scan_index: None,
@@ -564,11 +987,14 @@ where
         }
     }
 
-    /// Gets the i-th callback stored within the user. If this callback does not exist, this
-    /// function will panic.
+    /// Gets the i-th callback stored within the user.
+    ///
+    /// Returns [`UserError::CallbackIndexOutOfRange`] if the callback does not exist, and
+    /// [`UserError::CorruptedCallbackStorage`] if it could not be deserialized.
     ///
     /// # Example
     /// ```rust
+    /// # use std::sync::Arc;
     /// # use zk_callbacks::zk_object;
     /// # use zk_callbacks::generic::user::User;
     /// # use rand::thread_rng;
@@ -622,13 +1048,16 @@ where
     ///         method_id: Id::from(0),
     ///         expirable: false,
     ///         expiration: Time::from(10),
-    ///         method: callback,
-    ///         predicate: enforce_callback
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
     ///     };
     ///
     ///     let int = Interaction {
-    ///         meth: (method, predicate),
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
     ///         callbacks: [cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
     ///     };
     ///
     ///     let mut rng = thread_rng();
@@ -644,7 +1073,7 @@ where
     ///     // Get the first callback stored in the user.
     ///     let first_callback = u.get_cb
     ///         ::<Fr, NoSigOTP<Fr>>
-    ///     (0);
+    ///     (0).unwrap();
     ///
     ///     // Ensure the callback is the correct callback method.
     ///     assert_eq!(first_callback.cb_entry.cb_method_id, cb.method_id);
@@ -653,8 +1082,167 @@ where
     pub fn get_cb<Args: Clone, Crypto: AECipherSigZK<F, Args>>(
         &self,
         index: usize,
-    ) -> CallbackCom<F, Args, Crypto> {
-        CallbackCom::deserialize_compressed(&*self.callbacks[index]).unwrap()
+    ) -> Result<CallbackCom<F, Args, Crypto>, UserError> {
+        let cb = self
+            .callbacks
+            .get(index)
+            .ok_or(UserError::CallbackIndexOutOfRange {
+                index,
+                len: self.callbacks.len(),
+            })?;
+        CallbackCom::deserialize_compressed(&**cb)
+            .map_err(|_| UserError::CorruptedCallbackStorage { index })
+    }
+
+    /// Get a typed view of the callbacks stored within the user.
+    ///
+    /// Every callback is deserialized once, so the view may be queried repeatedly without the
+    /// cost of [`User::get_cb`]. Returns [`UserError::CorruptedCallbackStorage`] if any callback
+    /// could not be deserialized.
+    ///
+    /// The view borrows the user, so it always reflects the callbacks currently stored in the
+    /// user, including the progress of a scan (see [`CallbackView::in_progress`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::scannable_zk_object;
+    /// # use zk_callbacks::generic::user::User;
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use zk_callbacks::generic::interaction::Interaction;
+    /// # use zk_callbacks::generic::interaction::Callback;
+    /// # use zk_callbacks::generic::object::Id;
+    /// # use zk_callbacks::generic::object::Time;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use zk_callbacks::generic::user::UserVar;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::impls::centralized::crypto::{FakeSigPubkey, NoSigOTP};
+    /// # use zk_callbacks::generic::scan::{PubScanArgs, ScanKey, get_scan_interaction};
+    /// # type Groth = Groth16<E>;
+    ///#  #[scannable_zk_object(Fr)]
+    ///#  #[derive(Default)]
+    ///#  struct Data {
+    ///#      karma: Fr,
+    ///#      is_banned: bool,
+    ///#  }
+    ///#
+    ///#  fn method<'a>(old_user: &'a User<Fr, Data>, _pub: (), _priv: ()) -> User<Fr, Data> {
+    ///#      old_user.clone()
+    ///#  }
+    ///#
+    ///#  fn predicate<'a>(old_user: &'a UserVar<Fr, Data>, new_user: &'a UserVar<Fr, Data>, _pub: (), _priv: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///#      let o1 = old_user.data.karma.is_eq(&new_user.data.karma)?;
+    ///#      let o2 = old_user.data.is_banned.is_eq(&new_user.data.is_banned)?;
+    ///#      Ok(o1 & o2)
+    ///#  }
+    ///#
+    ///#  fn callback<'a>(old_user: &'a User<Fr, Data>, args: Fr) -> User<Fr, Data> {
+    ///#      let mut u = old_user.clone();
+    ///#      u.data.karma = args;
+    ///#      u
+    ///#  }
+    ///#
+    ///#  fn enforce_callback<'a>(old_user: &'a UserVar<Fr, Data>, args: FpVar<Fr>) -> Result<UserVar<Fr, Data>, SynthesisError> {
+    ///#      let mut u = old_user.clone();
+    ///#      u.data.karma = args;
+    ///#      Ok(u)
+    ///#  }
+    ///#
+    ///#
+    /// fn main () {
+    ///     let set_karma = Callback {
+    ///         method_id: Id::from(0),
+    ///         expirable: false,
+    ///         expiration: Time::from(0),
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
+    ///     };
+    ///     let set_karma_soon = Callback {
+    ///         method_id: Id::from(1),
+    ///         expirable: true,
+    ///         expiration: Time::from(10),
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
+    ///     };
+    ///
+    ///     let int = Interaction {
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
+    ///         callbacks: [set_karma_soon.clone(), set_karma.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
+    ///     };
+    ///
+    ///     let mut rng = thread_rng();
+    ///     let (pk, _) = int.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), (), false);
+    ///
+    ///     let mut u = User::create(Data { karma: Fr::from(0), is_banned: false }, &mut rng);
+    ///     let _ = u.exec_method_create_cb::<Poseidon<2>, _, _, _, _, _, _, NoSigOTP<Fr>, Groth, DummyStore, 2>(&mut rng, int.clone(), [FakeSigPubkey::pk(), FakeSigPubkey::pk()], Time::from(0), &DummyStore, true, &pk, (), ()).unwrap();
+    ///
+    ///     let view = u.callback_view::<Fr, NoSigOTP<Fr>>().unwrap();
+    ///     assert_eq!(view.len(), 2);
+    ///
+    ///     // No scan is in progress, so every callback is still outstanding.
+    ///     assert!(!view.scan_in_progress());
+    ///     assert_eq!(view.in_progress().count(), 2);
+    ///
+    ///     // One ticket for each method.
+    ///     assert_eq!(view.for_method(Id::from(1)).count(), 1);
+    ///
+    ///     // Only the first ticket expires, at time 10.
+    ///     let expiring: Vec<_> = view.expiring_before(Time::from(20)).map(|(i, _)| i).collect();
+    ///     assert_eq!(expiring, vec![0]);
+    ///
+    ///     // Tickets may be looked up directly.
+    ///     let tik = view.get(1).unwrap().get_ticket();
+    ///     assert_eq!(view.index_of(&tik), Some(1));
+    ///
+    ///     // Scan only the first callback, once it has expired.
+    ///     let cb_methods = vec![set_karma.clone(), set_karma_soon.clone()];
+    ///     let ex: PubScanArgs<_, Data, _, _, NoSigOTP<Fr>, DummyStore, 1> = PubScanArgs {
+    ///         memb_pub: [(); 1],
+    ///         is_memb_data_const: true,
+    ///         nmemb_pub: [(); 1],
+    ///         is_nmemb_data_const: true,
+    ///         cur_time: Fr::from(20),
+    ///         bulletin: DummyStore,
+    ///         cb_methods: cb_methods.clone(),
+    ///     };
+    ///     let (scan_pk, _) = get_scan_interaction::<_, _, _, _, _, _, Poseidon<2>, 1>().generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), ex, true);
+    ///     let key: ScanKey<'_, Fr, Data, Fr, FpVar<Fr>, NoSigOTP<Fr>, DummyStore, Groth, DummyStore> = ScanKey::new::<Poseidon<2>, 1>(&scan_pk);
+    ///     key.scan(&mut u, &mut rng, ((), ()), true, &DummyStore, (true, true), Time::from(20), cb_methods).unwrap();
+    ///
+    ///     // The expired callback is dropped, while the other is yet to be scanned.
+    ///     let view = u.callback_view::<Fr, NoSigOTP<Fr>>().unwrap();
+    ///     assert!(view.scan_in_progress());
+    ///     assert_eq!(view.len(), 2);
+    ///     let pending: Vec<_> = view.in_progress().map(|(i, _)| i).collect();
+    ///     assert_eq!(pending, vec![1]);
+    /// }
+    /// ```
+    pub fn callback_view<Args: Clone, Crypto: AECipherSigZK<F, Args>>(
+        &self,
+    ) -> Result<CallbackView<'_, F, Args, Crypto>, UserError> {
+        let callbacks = (0..self.callbacks.len())
+            .map(|i| self.get_cb::<Args, Crypto>(i))
+            .collect::<Result<_, _>>()?;
+        // The callbacks in progress are a subset of the callbacks at the start of the scan.
+        let in_progress = self.is_scanning().then(|| {
+            (0..self.callbacks.len())
+                .filter(|i| self.in_progress_cbs.contains(&self.callbacks[*i]))
+                .collect()
+        });
+        Ok(CallbackView {
+            callbacks,
+            in_progress,
+            _user: PhantomData,
+        })
     }
 
     /// Get the total number of callbacks stored within the user object.
@@ -692,25 +1280,25 @@ where
         CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone,
         const NUMSCANS: usize,
     >(
-        &mut self,
+        &self,
         cbul: &CBul,
         is_memb_nmemb_const: (bool, bool),
         cur_time: Time<F>,
         cb_methods: Vec<Callback<F, U, CBArgs, CBArgsVar>>,
-    ) -> (
-        PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>,
-        PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS>,
-    ) {
-        let start_ind = match self.scan_index {
-            Some(ind) => {
-                assert!(NUMSCANS + ind <= self.callbacks.len());
-                ind
-            }
-            None => {
-                assert!(NUMSCANS <= self.callbacks.len());
-                0
-            }
-        };
+    ) -> Result<
+        (
+            PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>,
+            PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS>,
+        ),
+        UserError,
+    > {
+        let start_ind = self.scan_index.unwrap_or(0);
+        if start_ind + NUMSCANS > self.callbacks.len() {
+            return Err(UserError::CallbackIndexOutOfRange {
+                index: start_ind + NUMSCANS - 1,
+                len: self.callbacks.len(),
+            });
+        }
 
         let mut vec_cbs = vec![];
         let mut vec_memb_pub = vec![];
@@ -721,7 +1309,23 @@ where
         let mut vec_times = vec![];
 
         for i in 0..NUMSCANS {
-            let cb: CallbackCom<F, CBArgs, Crypto> = self.get_cb::<CBArgs, Crypto>(start_ind + i);
+            let cb: CallbackCom<F, CBArgs, Crypto> =
+                self.get_cb::<CBArgs, Crypto>(start_ind + i)?;
+
+            // Tickets which expired before the last full scan are dropped without checking the
+            // bulletin.
+            if cb.cb_entry.expired_before(self.zk_fields.last_full_scan) {
+                let data = cbul.get_membership_data_unchecked(cb.get_ticket());
+                vec_enc.push(Crypto::Ct::default());
+                vec_times.push(Time::default());
+                vec_cbs.push(cb);
+                vec_memb_pub.push(data.0);
+                vec_memb_priv.push(data.1);
+                vec_nmemb_pub.push(data.2);
+                vec_nmemb_priv.push(data.3);
+                continue;
+            }
+
             let data = cbul.get_membership_data(cb.get_ticket());
             let if_in = cbul.verify_in(cb.get_ticket());
             let (enc, sig, time) = match if_in {
@@ -733,13 +1337,15 @@ where
                 ),
             };
 
-            assert!(
-                <Crypto as AECipherSigZK<F, CBArgs>>::SigPK::verify(
-                    &cb.get_ticket(),
-                    enc.clone(),
-                    sig
-                ) == true
-            );
+            if !<Crypto as AECipherSigZK<F, CBArgs>>::SigPK::verify(
+                &cb.get_ticket(),
+                enc.clone(),
+                sig,
+            ) {
+                return Err(UserError::InvalidCallbackSignature {
+                    index: start_ind + i,
+                });
+            }
 
             vec_enc.push(enc);
             vec_times.push(time);
@@ -778,7 +1384,7 @@ where
                 .unwrap_or_else(|_| panic!("Unexpected failure.")),
         };
 
-        (ps, prs)
+        Ok((ps, prs))
     }
 
     /// Execute a method, add on callbacks, and produce a proof to a server.
@@ -859,8 +1465,17 @@ where
     /// Therefore, this method captures both *the creation of callbacks*, and *the scan / ingestion of
     /// callbacks*.
     ///
+    /// # Errors
+    ///
+    /// The statement is not checked before proving in release builds: if the new user does not
+    /// satisfy the predicate (or any other constraint), the prover still returns a proof, which
+    /// will fail verification. Use [`User::dry_run_interact`] to check an interaction ahead of
+    /// time. With `debug_assertions` enabled, the statement is checked first and
+    /// [`UserError::PredicateUnsatisfied`] is returned instead of proving.
+    ///
     ///# Example
     /// ```rust
+    /// # use std::sync::Arc;
     /// # use zk_callbacks::zk_object;
     /// # use zk_callbacks::generic::user::User;
     /// # use rand::thread_rng;
@@ -933,13 +1548,16 @@ where
     ///         method_id: Id::from(0),
     ///         expirable: false,
     ///         expiration: Time::from(10),
-    ///         method: callback,
-    ///         predicate: enforce_callback
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
     ///     };
     ///
     ///     let int = Interaction {
-    ///         meth: (method, predicate),
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
     ///         callbacks: [cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
     ///     };
     ///
     ///     let mut rng = thread_rng();
@@ -992,119 +1610,40 @@ where
         pub_args: PubArgs,
         priv_args: PrivArgs,
         is_scan: bool,
-    ) -> Result<ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>, SynthesisError> {
-        // Steps:
-        // a) update user/self [ old user ] --> method(user) [ new user ]
-        // b) update user's zk fields properly (new nul, new comrand, proper cblist, etc)
-        // c) generate proof of correctness for
-        //      - a) the user was properly updated via the predicate
-        //      - b) the zk statements (nul == old nul, proper cblist, etc)
-
-        // (A) update the user object
-        // Create the new zk_object from the method
-        let mut new_user = (method.meth.0)(self, pub_args.clone(), priv_args.clone());
-
-        // (B) update the new users zk fields properly
-
-        new_user.zk_fields.nul = rng.r#gen();
-        new_user.zk_fields.com_rand = rng.r#gen();
-
-        let cb_tik_list: [(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS] =
-            create_cbs_from_interaction(rng, method.clone(), rpks, cur_time);
-
-        let issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] = cb_tik_list
-            .iter()
-            .map(|(x, _)| x.clone())
-            .collect::<Vec<CallbackCom<F, CBArgs, Crypto>>>()
-            .try_into()
-            .unwrap();
-
-        let issued_cb_coms = cb_tik_list
-            .iter()
-            .map(|(x, _)| x.commit::<H>())
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
-
-        for item in issued_callbacks.iter().take(NUMCBS) {
-            let mut cb = Vec::new();
-            item.clone().serialize_compressed(&mut cb).unwrap();
-            new_user.callbacks.push(cb);
-
-            new_user.zk_fields.callback_hash = add_ticket_to_hc::<F, H, CBArgs, Crypto>(
-                new_user.zk_fields.callback_hash,
-                item.clone().cb_entry,
-            );
-        }
-
-        if !is_scan {
-            new_user.zk_fields.old_in_progress_callback_hash = new_user.zk_fields.callback_hash;
-        }
-
-        // (C) Generate proof of correctness
-        // Extract the zk fields from the objects to do bookkeeping
-
-        let out_commit = new_user.commit::<H>();
-
-        let out_nul = self.zk_fields.nul;
-
-        let exec_method_circ: ExecMethodCircuit<
-            F,
-            H,
-            U,
-            PubArgs,
-            PubArgsVar,
-            PrivArgs,
-            PrivArgsVar,
-            CBArgs,
-            CBArgsVar,
-            Crypto,
-            Bul,
+    ) -> Result<ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>, UserError> {
+        self.interact_padded::<H, _, _, _, _, _, _, Crypto, Snark, Bul, NUMCBS>(
+            rng,
+            method,
             NUMCBS,
-        > = ExecMethodCircuit {
-            priv_old_user: self.clone(),
-            priv_new_user: new_user.clone(),
-            priv_issued_callbacks: issued_callbacks,
-            priv_bul_membership_witness: bul_data.1,
-            priv_args,
-
-            pub_new_com: out_commit,
-            pub_old_nul: out_nul,
-            pub_issued_callback_coms: issued_cb_coms,
+            rpks,
+            cur_time,
+            bul_data,
+            is_memb_data_const,
+            pk,
             pub_args,
-            pub_bul_membership_data: bul_data.0,
-            bul_memb_is_const: is_memb_data_const,
-
-            associated_method: method,
+            priv_args,
             is_scan,
-            _phantom_hash: core::marker::PhantomData,
-        };
-
-        let new_cs = ConstraintSystem::<F>::new_ref();
-        exec_method_circ
-            .clone()
-            .generate_constraints(new_cs.clone())?;
-        new_cs.is_satisfied()?;
-
-        let proof = Snark::prove(pk, exec_method_circ, rng)?;
-
-        // (D) Update current object
-        *self = new_user;
-
-        Ok(ExecutedMethod {
-            new_object: out_commit,
-            old_nullifier: out_nul,
-            cb_tik_list,
-            cb_com_list: issued_cb_coms,
-            cur_time,
-            proof,
-        })
+        )
     }
 
-    /// Get the execute method circuit for an interaction.
+    /// Execute a method on a user, issuing only some of the callbacks of the interaction.
     ///
-    /// For advanced use only.
-    pub fn circuit_interact<
+    /// The first `num_issued` callbacks of the interaction are issued as in [`User::interact`],
+    /// and the remaining slots are padded with *inert* tickets. An inert ticket has the zero
+    /// commitment (see [`is_inert_com`](super::callbacks::is_inert_com)) and is never added to the
+    /// user, so it can never be called and is skipped when scanning. This way, an interaction
+    /// with `NUMCBS` callbacks acts as one with a maximum of `NUMCBS` callbacks, and a single key
+    /// pair covers issuing anywhere from `0` to `NUMCBS` callbacks.
+    ///
+    /// Services should only store the real tickets (see [`ExecutedMethod::issued_tickets`]), and
+    /// pass the callbacks they expect to
+    /// [`approve_interaction`](super::service::ServiceProvider::approve_interaction), which checks
+    /// that the remaining slots are inert.
+    ///
+    /// Fails with [`UserError::CallbackIndexOutOfRange`] if `num_issued` exceeds `NUMCBS`.
+    ///
+    /// See [`User::interact`] for more documentation.
+    pub fn interact_padded<
         H: FieldHash<F>,
         PubArgs: Clone + std::fmt::Debug,
         PubArgsVar: AllocVar<PubArgs, F> + Clone,
@@ -1113,10 +1652,11 @@ where
         CBArgs: Clone + std::fmt::Debug,
         CBArgsVar: AllocVar<CBArgs, F> + Clone,
         Crypto: AECipherSigZK<F, CBArgs>,
+        Snark: SNARK<F, Error = SynthesisError>,
         Bul: PublicUserBul<F, U>,
         const NUMCBS: usize,
     >(
-        &self,
+        &mut self,
         rng: &mut (impl CryptoRng + RngCore),
         method: Interaction<
             F,
@@ -1129,30 +1669,16 @@ where
             CBArgsVar,
             NUMCBS,
         >,
+        num_issued: usize,
         rpks: [Crypto::SigPK; NUMCBS],
         cur_time: Time<F>,
         bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
         is_memb_data_const: bool,
+        pk: &Snark::ProvingKey,
         pub_args: PubArgs,
         priv_args: PrivArgs,
         is_scan: bool,
-    ) -> Result<
-        ExecMethodCircuit<
-            F,
-            H,
-            U,
-            PubArgs,
-            PubArgsVar,
-            PrivArgs,
-            PrivArgsVar,
-            CBArgs,
-            CBArgsVar,
-            Crypto,
-            Bul,
-            NUMCBS,
-        >,
-        SynthesisError,
-    > {
+    ) -> Result<ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>, UserError> {
         // Steps:
         // a) update user/self [ old user ] --> method(user) [ new user ]
         // b) update user's zk fields properly (new nul, new comrand, proper cblist, etc)
@@ -1160,17 +1686,24 @@ where
         //      - a) the user was properly updated via the predicate
         //      - b) the zk statements (nul == old nul, proper cblist, etc)
 
+        if num_issued > NUMCBS {
+            return Err(UserError::CallbackIndexOutOfRange {
+                index: num_issued,
+                len: NUMCBS,
+            });
+        }
+
         // (A) update the user object
         // Create the new zk_object from the method
         let mut new_user = (method.meth.0)(self, pub_args.clone(), priv_args.clone());
 
         // (B) update the new users zk fields properly
 
-        new_user.zk_fields.nul = rng.r#gen();
         new_user.zk_fields.com_rand = rng.r#gen();
+        new_user.zk_fields.nul = derive_nul::<F, H>(new_user.zk_fields.com_rand);
 
         let cb_tik_list: [(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS] =
-            create_cbs_from_interaction(rng, method.clone(), rpks, cur_time);
+            create_cbs_from_interaction(rng, &method.callbacks, rpks, cur_time, num_issued);
 
         let issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] = cb_tik_list
             .iter()
@@ -1179,14 +1712,15 @@ where
             .try_into()
             .unwrap();
 
-        let issued_cb_coms = cb_tik_list
-            .iter()
-            .map(|(x, _)| x.commit::<H>())
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
+        // Inert tickets have the zero commitment, and are not added to the user.
+        let (service_tik_list, issued_cb_coms) =
+            callbacks_for_service::<F, H, CBArgs, Crypto, NUMCBS>(
+                &cb_tik_list,
+                num_issued,
+                method.hidden_ids.is_some(),
+            );
 
-        for item in issued_callbacks.iter().take(NUMCBS) {
+        for item in issued_callbacks.iter().take(num_issued) {
             let mut cb = Vec::new();
             item.clone().serialize_compressed(&mut cb).unwrap();
             new_user.callbacks.push(cb);
@@ -1208,6 +1742,10 @@ where
 
         let out_nul = self.zk_fields.nul;
 
+        let pseudonym = method
+            .pseudonym_scope
+            .map(|scope| derive_pseudonym::<F, H>(self.zk_fields.secret, scope));
+
         let exec_method_circ: ExecMethodCircuit<
             F,
             H,
@@ -1232,6 +1770,7 @@ where
             pub_old_nul: out_nul,
             pub_issued_callback_coms: issued_cb_coms,
             pub_args,
+            pub_pseudonym: pseudonym.unwrap_or_default(),
             pub_bul_membership_data: bul_data.0,
             bul_memb_is_const: is_memb_data_const,
 
@@ -1240,17 +1779,104 @@ where
             _phantom_hash: core::marker::PhantomData,
         };
 
-        Ok(exec_method_circ)
+        #[cfg(debug_assertions)]
+        ensure_satisfied(exec_method_circ.clone(), false)?;
+
+        let proof = Snark::prove(pk, exec_method_circ, rng)?;
+
+        // (D) Update current object
+        *self = new_user;
+
+        Ok(ExecutedMethod {
+            new_object: out_commit,
+            old_nullifier: out_nul,
+            cb_tik_list: service_tik_list,
+            cb_com_list: issued_cb_coms,
+            cur_time,
+            pseudonym,
+            proof,
+        })
     }
 
-    /// Get the constraint system for an interaction.
+    /// Execute a method on a user, without updating the user until the interaction is accepted.
     ///
-    /// Useful for debugging.
+    /// This proves the interaction as in [`User::interact`], but returns a
+    /// [`PendingInteraction`] instead of updating the user. The user is updated with
+    /// [`PendingInteraction::finalize`] once the bulletin accepts the interaction; if the
+    /// bulletin (or the service) rejects it, the pending interaction may be discarded, and the
+    /// user is left as it was.
     ///
-    /// **Note that this does not modify the user, it modifies a cloned user internally.**
+    /// **Note that this does not modify the user.**
     ///
-    /// See [`User::interact`] for more documentation.
-    pub fn constraint_interact<
+    /// # Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::user::{User, UserVar};
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use zk_callbacks::generic::interaction::Interaction;
+    /// # use zk_callbacks::generic::object::{Time, TimeVar};
+    /// # use zk_callbacks::generic::bulletin::UserBul;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::impls::centralized::crypto::NoSigOTP;
+    /// # type Groth = Groth16<E>;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub num_visits: Fr,
+    /// }
+    ///
+    /// fn method<'a>(old_user: &'a User<Fr, Data>, _pub: (), _priv: ()) -> User<Fr, Data> {
+    ///     let mut new = old_user.clone();
+    ///     new.data.num_visits += Fr::from(1);
+    ///     new
+    /// }
+    ///
+    /// fn predicate<'a>(old_user: &'a UserVar<Fr, Data>, new_user: &'a UserVar<Fr, Data>, _pub: (), _priv: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     new_user.data.num_visits.is_eq(&(old_user.data.num_visits.clone() + FpVar::Constant(Fr::from(1))))
+    /// }
+    ///
+    /// fn main () {
+    ///     let int = Interaction {
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
+    ///         callbacks: [],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
+    ///     };
+    ///
+    ///     let mut rng = thread_rng();
+    ///
+    ///     let (pk, vk) = int.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), (), false);
+    ///
+    ///     let mut u = User::create(Data { num_visits: Fr::from(0) }, &mut rng);
+    ///
+    ///     let pending = u.interact_pending::<Poseidon<2>, (), (), (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 0>(&mut rng, int.clone(), [], Time::from(0), ((), ()), true, &pk, (), (), false).unwrap();
+    ///
+    ///     // The user is unchanged until the interaction is finalized.
+    ///     assert_eq!(u.data.num_visits, Fr::from(0));
+    ///
+    ///     let m = pending.method();
+    ///     let accepted = <DummyStore as UserBul<Fr, Data>>::verify_interact_and_append::<(), Groth, 0>(&mut DummyStore, m.new_object, m.old_nullifier, (), m.cb_com_list, m.proof.clone(), None, &vk);
+    ///
+    ///     if accepted.is_ok() {
+    ///         let exec_meth = pending.finalize(&mut u).unwrap();
+    ///         assert_eq!(u.commit::<Poseidon<2>>(), exec_meth.new_object);
+    ///     } else {
+    ///         pending.discard();
+    ///     }
+    ///
+    ///     assert_eq!(u.data.num_visits, Fr::from(1));
+    /// }
+    /// ```
+    pub fn interact_pending<
         H: FieldHash<F>,
         PubArgs: Clone + std::fmt::Debug,
         PubArgsVar: AllocVar<PubArgs, F> + Clone,
@@ -1259,6 +1885,7 @@ where
         CBArgs: Clone + std::fmt::Debug,
         CBArgsVar: AllocVar<CBArgs, F> + Clone,
         Crypto: AECipherSigZK<F, CBArgs>,
+        Snark: SNARK<F, Error = SynthesisError>,
         Bul: PublicUserBul<F, U>,
         const NUMCBS: usize,
     >(
@@ -1279,64 +1906,763 @@ where
         cur_time: Time<F>,
         bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
         is_memb_data_const: bool,
+        pk: &Snark::ProvingKey,
         pub_args: PubArgs,
         priv_args: PrivArgs,
         is_scan: bool,
-    ) -> Result<ConstraintSystemRef<F>, SynthesisError> {
-        // Steps:
-        // a) update user/self [ old user ] --> method(user) [ new user ]
-        // b) update user's zk fields properly (new nul, new comrand, proper cblist, etc)
-        // c) generate proof of correctness for
-        //      - a) the user was properly updated via the predicate
-        //      - b) the zk statements (nul == old nul, proper cblist, etc)
-
-        // (A) update the user object
-        // Create the new zk_object from the method
-        let mut new_user = (method.meth.0)(self, pub_args.clone(), priv_args.clone());
-
-        // (B) update the new users zk fields properly
-
-        new_user.zk_fields.nul = rng.r#gen();
-        new_user.zk_fields.com_rand = rng.r#gen();
-
-        let cb_tik_list: [(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS] =
-            create_cbs_from_interaction(rng, method.clone(), rpks, cur_time);
-
-        let issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] = cb_tik_list
-            .iter()
-            .map(|(x, _)| x.clone())
-            .collect::<Vec<CallbackCom<F, CBArgs, Crypto>>>()
-            .try_into()
-            .unwrap();
-
-        let issued_cb_coms = cb_tik_list
-            .iter()
-            .map(|(x, _)| x.commit::<H>())
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
-
-        for item in issued_callbacks.iter().take(NUMCBS) {
-            let mut cb = Vec::new();
-            item.clone().serialize_compressed(&mut cb).unwrap();
-            new_user.callbacks.push(cb);
-
-            new_user.zk_fields.callback_hash = add_ticket_to_hc::<F, H, CBArgs, Crypto>(
-                new_user.zk_fields.callback_hash,
-                item.clone().cb_entry,
-            );
-        }
-
-        if !is_scan {
-            new_user.zk_fields.old_in_progress_callback_hash = new_user.zk_fields.callback_hash;
-        }
-
-        // (C) Generate proof of correctness
-        // Extract the zk fields from the objects to do bookkeeping
-
-        let out_commit = new_user.commit::<H>();
+    ) -> Result<PendingInteraction<F, U, Snark, CBArgs, Crypto, NUMCBS>, UserError> {
+        let mut new_user = self.clone();
+        let method = new_user.interact::<H, _, _, _, _, _, _, Crypto, Snark, Bul, NUMCBS>(
+            rng,
+            method,
+            rpks,
+            cur_time,
+            bul_data,
+            is_memb_data_const,
+            pk,
+            pub_args,
+            priv_args,
+            is_scan,
+        )?;
+        Ok(PendingInteraction { new_user, method })
+    }
 
-        let out_nul = self.zk_fields.nul;
+    /// Execute a method on several users at once, proving it with a single proof.
+    ///
+    /// The method of the [`MultiInteraction`] is executed on all `users` together, and each user
+    /// is issued its own callbacks (with ticket identities `rpks[i]` for the `i`-th user). The
+    /// output contains the nullifier and new commitment of every user, which the bulletin accepts
+    /// or rejects together, with
+    /// [`verify_multi_interact_and_append`](super::bulletin::UserBul::verify_multi_interact_and_append).
+    ///
+    /// The users must be distinct, as the bulletin rejects repeated nullifiers.
+    ///
+    /// On success, each user is updated. On failure, no user is modified.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::user::{User, UserVar};
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use zk_callbacks::generic::interaction::MultiInteraction;
+    /// # use zk_callbacks::generic::object::Time;
+    /// # use zk_callbacks::generic::bulletin::UserBul;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::impls::centralized::crypto::NoSigOTP;
+    /// # type Groth = Groth16<E>;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub karma: Fr,
+    /// }
+    ///
+    /// // The first user transfers one karma to the second user.
+    /// fn transfer(old: &[User<Fr, Data>; 2], _pub: (), _priv: ()) -> [User<Fr, Data>; 2] {
+    ///     let mut new = old.clone();
+    ///     new[0].data.karma -= Fr::from(1);
+    ///     new[1].data.karma += Fr::from(1);
+    ///     new
+    /// }
+    ///
+    /// fn predicate<'a>(old: &'a [UserVar<Fr, Data>; 2], new: &'a [UserVar<Fr, Data>; 2], _pub: (), _priv: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     let one = FpVar::Constant(Fr::from(1));
+    ///     let o1 = (&new[0].data.karma + &one).is_eq(&old[0].data.karma)?;
+    ///     let o2 = (&old[1].data.karma + &one).is_eq(&new[1].data.karma)?;
+    ///     Ok(o1 & o2)
+    /// }
+    ///
+    /// fn main () {
+    ///     let int: MultiInteraction<Fr, Data, (), (), (), (), Fr, FpVar<Fr>, 2, 0> =
+    ///         MultiInteraction::new(transfer, predicate, [[], []]);
+    ///
+    ///     let mut rng = thread_rng();
+    ///
+    ///     let (pk, vk) = int.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some([(), ()]), ());
+    ///
+    ///     let mut alice = User::create(Data { karma: Fr::from(5) }, &mut rng);
+    ///     let mut bob = User::create(Data { karma: Fr::from(0) }, &mut rng);
+    ///
+    ///     let exec_meth = User::interact_multi::<Poseidon<2>, (), (), (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 2, 0>(&mut rng, [&mut alice, &mut bob], int, [[], []], Time::from(0), [((), ()), ((), ())], true, &pk, (), ()).unwrap();
+    ///
+    ///     assert_eq!(alice.data.karma, Fr::from(4));
+    ///     assert_eq!(bob.data.karma, Fr::from(1));
+    ///
+    ///     // Both nullifiers are checked before either user is appended
+    ///     <DummyStore as UserBul<Fr, Data>>::verify_multi_interact_and_append::<(), Groth, 2, 0>(&mut DummyStore, exec_meth.new_objects, exec_meth.old_nullifiers, (), exec_meth.cb_com_lists, exec_meth.proof, None, &vk).unwrap();
+    /// }
+    /// ```
+    pub fn interact_multi<
+        H: FieldHash<F>,
+        PubArgs: Clone + std::fmt::Debug,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone + std::fmt::Debug,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+        CBArgs: Clone + std::fmt::Debug,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        Snark: SNARK<F, Error = SynthesisError>,
+        Bul: PublicUserBul<F, U>,
+        const NUMUSERS: usize,
+        const NUMCBS: usize,
+    >(
+        rng: &mut (impl CryptoRng + RngCore),
+        users: [&mut Self; NUMUSERS],
+        method: MultiInteraction<
+            F,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            NUMUSERS,
+            NUMCBS,
+        >,
+        rpks: [[Crypto::SigPK; NUMCBS]; NUMUSERS],
+        cur_time: Time<F>,
+        bul_data: [(Bul::MembershipPub, Bul::MembershipWitness); NUMUSERS],
+        is_memb_data_const: bool,
+        pk: &Snark::ProvingKey,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+    ) -> Result<MultiExecutedMethod<F, Snark, CBArgs, Crypto, NUMUSERS, NUMCBS>, UserError> {
+        let old_users: [Self; NUMUSERS] = core::array::from_fn(|i| users[i].clone());
+
+        // (A) update the user objects
+        let mut new_users = (method.meth.0)(&old_users, pub_args.clone(), priv_args.clone());
+
+        // (B) update each new user's zk fields, and issue its callbacks
+        let cb_tik_lists: [[(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS]; NUMUSERS] =
+            core::array::from_fn(|i| {
+                create_cbs_from_interaction(
+                    rng,
+                    &method.callbacks[i],
+                    rpks[i].clone(),
+                    cur_time,
+                    NUMCBS,
+                )
+            });
+
+        let mut service_tik_lists = cb_tik_lists.clone();
+        let mut cb_com_lists = [[F::zero(); NUMCBS]; NUMUSERS];
+
+        for (i, new_user) in new_users.iter_mut().enumerate() {
+            new_user.zk_fields.com_rand = rng.r#gen();
+            new_user.zk_fields.nul = derive_nul::<F, H>(new_user.zk_fields.com_rand);
+
+            (service_tik_lists[i], cb_com_lists[i]) =
+                callbacks_for_service::<F, H, CBArgs, Crypto, NUMCBS>(
+                    &cb_tik_lists[i],
+                    NUMCBS,
+                    false,
+                );
+
+            for (item, _) in cb_tik_lists[i].iter() {
+                let mut cb = Vec::new();
+                item.clone().serialize_compressed(&mut cb).unwrap();
+                new_user.callbacks.push(cb);
+
+                new_user.zk_fields.callback_hash = add_ticket_to_hc::<F, H, CBArgs, Crypto>(
+                    new_user.zk_fields.callback_hash,
+                    item.clone().cb_entry,
+                );
+            }
+
+            new_user.zk_fields.old_in_progress_callback_hash = new_user.zk_fields.callback_hash;
+        }
+
+        // (C) Generate proof of correctness
+        let out_commits: [Com<F>; NUMUSERS] = core::array::from_fn(|i| new_users[i].commit::<H>());
+        let out_nuls: [Nul<F>; NUMUSERS] = core::array::from_fn(|i| old_users[i].zk_fields.nul);
+
+        let (memb_pub, memb_wit): (Vec<_>, Vec<_>) = bul_data.into_iter().unzip();
+
+        let exec_method_circ: MultiExecMethodCircuit<
+            F,
+            H,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            Crypto,
+            Bul,
+            NUMUSERS,
+            NUMCBS,
+        > = MultiExecMethodCircuit {
+            priv_old_users: old_users,
+            priv_new_users: new_users.clone(),
+            priv_issued_callbacks: cb_tik_lists.map(|l| l.map(|(cb, _)| cb)),
+            priv_bul_membership_witnesses: core::array::from_fn(|i| memb_wit[i].clone()),
+            priv_args,
+
+            pub_new_coms: out_commits,
+            pub_old_nuls: out_nuls,
+            pub_issued_callback_coms: cb_com_lists,
+            pub_args,
+            pub_bul_membership_data: core::array::from_fn(|i| memb_pub[i].clone()),
+            bul_memb_is_const: is_memb_data_const,
+
+            associated_method: method,
+            _phantom_hash: core::marker::PhantomData,
+        };
+
+        #[cfg(debug_assertions)]
+        ensure_satisfied(exec_method_circ.clone(), false)?;
+
+        let proof = Snark::prove(pk, exec_method_circ, rng)?;
+
+        // (D) Update the current objects
+        for (user, new_user) in users.into_iter().zip(new_users) {
+            *user = new_user;
+        }
+
+        Ok(MultiExecutedMethod {
+            new_objects: out_commits,
+            old_nullifiers: out_nuls,
+            cb_tik_lists: service_tik_lists,
+            cb_com_lists,
+            cur_time,
+            proof,
+        })
+    }
+
+    /// Execute a chain of interactions on a user, proving it with a single proof.
+    ///
+    /// Each step of the [`ChainedInteraction`] is applied to the user in turn, and the callbacks
+    /// of every step are issued in order (with ticket identities `rpks`). The output is a single
+    /// [`ExecutedMethod`], with one nullifier and one new commitment, which is verified as usual
+    /// with the public arguments [`ChainedInteraction::pub_args`] and the keys from
+    /// [`ChainedInteraction::generate_keys`].
+    ///
+    /// Fails with [`UserError::CallbackCountMismatch`] if the chain does not issue exactly
+    /// `NUMCBS` callbacks.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::generic::user::{User, UserVar};
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use zk_callbacks::generic::interaction::{Callback, ChainedInteraction, Interaction};
+    /// # use zk_callbacks::generic::object::{Id, Time, TimeVar};
+    /// # use zk_callbacks::generic::bulletin::UserBul;
+    /// # use zk_callbacks::generic::scan::get_scan_interaction;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::impls::centralized::crypto::{FakeSigPubkey, NoSigOTP};
+    /// # use zk_callbacks::scannable_zk_object;
+    /// # type Groth = Groth16<E>;
+    /// #[scannable_zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub karma: Fr,
+    ///     pub posts: Fr,
+    /// }
+    ///
+    /// fn post<'a>(old_user: &'a User<Fr, Data>, _pub: Time<Fr>, _priv: ()) -> User<Fr, Data> {
+    ///     let mut new = old_user.clone();
+    ///     new.data.posts += Fr::from(1);
+    ///     new
+    /// }
+    ///
+    /// fn predicate<'a>(old_user: &'a UserVar<Fr, Data>, new_user: &'a UserVar<Fr, Data>, _pub: TimeVar<Fr>, _priv: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     let o1 = old_user.data.karma.is_eq(&new_user.data.karma)?;
+    ///     let o2 = new_user.data.posts.is_eq(&(&old_user.data.posts + FpVar::Constant(Fr::from(1))))?;
+    ///     Ok(o1 & o2)
+    /// }
+    ///
+    /// fn callback<'a>(old_user: &'a User<Fr, Data>, args: Fr) -> User<Fr, Data> {
+    ///     let mut u = old_user.clone();
+    ///     u.data.karma = args;
+    ///     u
+    /// }
+    ///
+    /// fn enforce_callback<'a>(old_user: &'a UserVar<Fr, Data>, args: FpVar<Fr>) -> Result<UserVar<Fr, Data>, SynthesisError> {
+    ///     let mut u = old_user.clone();
+    ///     u.data.karma = args;
+    ///     Ok(u)
+    /// }
+    ///
+    /// fn main () {
+    ///     let cb = Callback {
+    ///         method_id: Id::from(0),
+    ///         expirable: false,
+    ///         expiration: Time::from(10),
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
+    ///     };
+    ///
+    ///     let int = Interaction {
+    ///         meth: (Arc::new(post), Arc::new(predicate)),
+    ///         callbacks: [cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
+    ///     };
+    ///
+    ///     let mut rng = thread_rng();
+    ///
+    ///     let (pk, _vk) = int.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), Time::from(0), false);
+    ///
+    ///     let mut u = User::create(Data { karma: Fr::from(0), posts: Fr::from(0) }, &mut rng);
+    ///
+    ///     u.interact::<Poseidon<2>, Time<Fr>, TimeVar<Fr>, (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 1>(&mut rng, int.clone(), [FakeSigPubkey::pk()], Time::from(20), ((), ()), true, &pk, Time::from(20), (), false).unwrap();
+    ///
+    ///     // Scan the outstanding callback, and then post again, in a single proof
+    ///     let (ps, prs) = u.get_scan_arguments::<Fr, FpVar<Fr>, NoSigOTP<Fr>, DummyStore, 1>(&DummyStore, (true, true), Time::from(25), vec![cb.clone()]).unwrap();
+    ///
+    ///     let chain = ChainedInteraction::new()
+    ///         .then_scan(get_scan_interaction::<_, _, _, _, NoSigOTP<Fr>, DummyStore, Poseidon<2>, 1>(), ps, prs)
+    ///         .unwrap()
+    ///         .then(int.clone(), Time::from(25), ())
+    ///         .unwrap();
+    ///
+    ///     let (pkc, vkc) = chain.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore, 1>(&mut rng, Some(()));
+    ///
+    ///     let exec_meth = u.interact_chain::<Poseidon<2>, Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 1>(&mut rng, chain.clone(), [FakeSigPubkey::pk()], Time::from(25), ((), ()), true, &pkc).unwrap();
+    ///
+    ///     assert_eq!(u.data.posts, Fr::from(2));
+    ///     assert_eq!(u.num_outstanding_callbacks(), 2);
+    ///     assert!(u.zk_fields.scanned_since(Time::from(25)));
+    ///
+    ///     <DummyStore as UserBul<Fr, Data>>::verify_interact_and_append::<_, Groth, 1>(&mut DummyStore, exec_meth.new_object, exec_meth.old_nullifier, chain.pub_args(), exec_meth.cb_com_list, exec_meth.proof, None, &vkc).unwrap();
+    /// }
+    /// ```
+    pub fn interact_chain<
+        H: FieldHash<F>,
+        CBArgs: Clone + std::fmt::Debug,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        Snark: SNARK<F, Error = SynthesisError>,
+        Bul: PublicUserBul<F, U>,
+        const NUMCBS: usize,
+    >(
+        &mut self,
+        rng: &mut (impl CryptoRng + RngCore),
+        chain: ChainedInteraction<F, U, CBArgs, CBArgsVar>,
+        rpks: [Crypto::SigPK; NUMCBS],
+        cur_time: Time<F>,
+        bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
+        is_memb_data_const: bool,
+        pk: &Snark::ProvingKey,
+    ) -> Result<ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>, UserError> {
+        let callbacks = chain.callbacks();
+        if callbacks.len() != NUMCBS {
+            return Err(UserError::CallbackCountMismatch {
+                expected: NUMCBS,
+                found: callbacks.len(),
+            });
+        }
+
+        let cb_tik_list: [(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS] =
+            create_cbs_from_interaction(rng, &callbacks, rpks, cur_time, NUMCBS);
+
+        let issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] =
+            cb_tik_list.clone().map(|(cb, _)| cb);
+
+        let (service_tik_list, issued_cb_coms) =
+            callbacks_for_service::<F, H, CBArgs, Crypto, NUMCBS>(&cb_tik_list, NUMCBS, false);
+
+        // (A) apply each step in turn, issuing the callbacks of each step which is not a scan
+        let mut users = vec![self.clone()];
+        let mut to_issue = issued_callbacks.iter();
+        for step in &chain.steps {
+            let mut next = (step.method)(&users[users.len() - 1]);
+
+            if !step.is_scan {
+                for item in to_issue.by_ref().take(step.callbacks.len()) {
+                    let mut cb = Vec::new();
+                    item.clone().serialize_compressed(&mut cb).unwrap();
+                    next.callbacks.push(cb);
+
+                    next.zk_fields.callback_hash = add_ticket_to_hc::<F, H, CBArgs, Crypto>(
+                        next.zk_fields.callback_hash,
+                        item.clone().cb_entry,
+                    );
+                }
+
+                next.zk_fields.old_in_progress_callback_hash = next.zk_fields.callback_hash;
+            }
+
+            users.push(next);
+        }
+
+        // (B) only the final user is committed to, with a fresh nullifier
+        let mut new_user = users.pop().unwrap();
+        new_user.zk_fields.com_rand = rng.r#gen();
+        new_user.zk_fields.nul = derive_nul::<F, H>(new_user.zk_fields.com_rand);
+        users.push(new_user.clone());
+
+        // (C) Generate proof of correctness
+        let out_commit = new_user.commit::<H>();
+
+        let out_nul = self.zk_fields.nul;
+
+        let exec_method_circ: ChainedExecMethodCircuit<
+            F,
+            H,
+            U,
+            CBArgs,
+            CBArgsVar,
+            Crypto,
+            Bul,
+            NUMCBS,
+        > = ChainedExecMethodCircuit {
+            priv_users: users,
+            priv_issued_callbacks: issued_callbacks,
+            priv_bul_membership_witness: bul_data.1,
+
+            pub_new_com: out_commit,
+            pub_old_nul: out_nul,
+            pub_issued_callback_coms: issued_cb_coms,
+            pub_bul_membership_data: bul_data.0,
+            bul_memb_is_const: is_memb_data_const,
+
+            associated_chain: chain,
+            _phantom_hash: core::marker::PhantomData,
+        };
+
+        #[cfg(debug_assertions)]
+        ensure_satisfied(exec_method_circ.clone(), false)?;
+
+        let proof = Snark::prove(pk, exec_method_circ, rng)?;
+
+        // (D) Update current object
+        *self = new_user;
+
+        Ok(ExecutedMethod {
+            new_object: out_commit,
+            old_nullifier: out_nul,
+            cb_tik_list: service_tik_list,
+            cb_com_list: issued_cb_coms,
+            cur_time,
+            pseudonym: None,
+            proof,
+        })
+    }
+
+    /// Check that an interaction would succeed, without proving anything.
+    ///
+    /// This synthesizes the [`ExecMethodCircuit`] for the interaction into a test constraint
+    /// system and checks it is satisfied, which is much cheaper than running the prover. If a
+    /// constraint is unsatisfied, this returns [`UserError::PredicateUnsatisfied`]. With the
+    /// `trace` feature, it holds the namespace path of the first failing constraint (for example
+    /// `predicate` if the method predicate does not hold, or `bul_membership` if the user is not
+    /// in the bulletin).
+    ///
+    /// Note that [`User::interact`] only performs this check in debug builds; use this to check an
+    /// interaction ahead of time, or when the proving key is not at hand.
+    ///
+    /// **Note that this does not modify the user.**
+    ///
+    /// See [`User::interact`] for more documentation.
+    pub fn dry_run_interact<
+        H: FieldHash<F>,
+        PubArgs: Clone + std::fmt::Debug,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone + std::fmt::Debug,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+        CBArgs: Clone + std::fmt::Debug,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        Bul: PublicUserBul<F, U>,
+        const NUMCBS: usize,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        method: Interaction<
+            F,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            NUMCBS,
+        >,
+        rpks: [Crypto::SigPK; NUMCBS],
+        cur_time: Time<F>,
+        bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
+        is_memb_data_const: bool,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+        is_scan: bool,
+    ) -> Result<(), UserError> {
+        let exec_method_circ = self.circuit_interact::<H, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, Crypto, Bul, NUMCBS>(
+            rng,
+            method,
+            rpks,
+            cur_time,
+            bul_data,
+            is_memb_data_const,
+            pub_args,
+            priv_args,
+            is_scan,
+        )?;
+
+        dry_run_circuit(exec_method_circ)
+    }
+
+    /// Get the execute method circuit for an interaction.
+    ///
+    /// For advanced use only.
+    pub fn circuit_interact<
+        H: FieldHash<F>,
+        PubArgs: Clone + std::fmt::Debug,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone + std::fmt::Debug,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+        CBArgs: Clone + std::fmt::Debug,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        Bul: PublicUserBul<F, U>,
+        const NUMCBS: usize,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        method: Interaction<
+            F,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            NUMCBS,
+        >,
+        rpks: [Crypto::SigPK; NUMCBS],
+        cur_time: Time<F>,
+        bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
+        is_memb_data_const: bool,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+        is_scan: bool,
+    ) -> Result<
+        ExecMethodCircuit<
+            F,
+            H,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            Crypto,
+            Bul,
+            NUMCBS,
+        >,
+        UserError,
+    > {
+        // Steps:
+        // a) update user/self [ old user ] --> method(user) [ new user ]
+        // b) update user's zk fields properly (new nul, new comrand, proper cblist, etc)
+        // c) generate proof of correctness for
+        //      - a) the user was properly updated via the predicate
+        //      - b) the zk statements (nul == old nul, proper cblist, etc)
+
+        // (A) update the user object
+        // Create the new zk_object from the method
+        let mut new_user = (method.meth.0)(self, pub_args.clone(), priv_args.clone());
+
+        // (B) update the new users zk fields properly
+
+        new_user.zk_fields.com_rand = rng.r#gen();
+        new_user.zk_fields.nul = derive_nul::<F, H>(new_user.zk_fields.com_rand);
+
+        let cb_tik_list: [(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS] =
+            create_cbs_from_interaction(rng, &method.callbacks, rpks, cur_time, NUMCBS);
+
+        let issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] = cb_tik_list
+            .iter()
+            .map(|(x, _)| x.clone())
+            .collect::<Vec<CallbackCom<F, CBArgs, Crypto>>>()
+            .try_into()
+            .unwrap();
+
+        let (_, issued_cb_coms) = callbacks_for_service::<F, H, CBArgs, Crypto, NUMCBS>(
+            &cb_tik_list,
+            NUMCBS,
+            method.hidden_ids.is_some(),
+        );
+
+        for item in issued_callbacks.iter().take(NUMCBS) {
+            let mut cb = Vec::new();
+            item.clone().serialize_compressed(&mut cb).unwrap();
+            new_user.callbacks.push(cb);
+
+            new_user.zk_fields.callback_hash = add_ticket_to_hc::<F, H, CBArgs, Crypto>(
+                new_user.zk_fields.callback_hash,
+                item.clone().cb_entry,
+            );
+        }
+
+        if !is_scan {
+            new_user.zk_fields.old_in_progress_callback_hash = new_user.zk_fields.callback_hash;
+        }
+
+        // (C) Generate proof of correctness
+        // Extract the zk fields from the objects to do bookkeeping
+
+        let out_commit = new_user.commit::<H>();
+
+        let out_nul = self.zk_fields.nul;
+
+        let pseudonym = method
+            .pseudonym_scope
+            .map(|scope| derive_pseudonym::<F, H>(self.zk_fields.secret, scope));
+
+        let exec_method_circ: ExecMethodCircuit<
+            F,
+            H,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            Crypto,
+            Bul,
+            NUMCBS,
+        > = ExecMethodCircuit {
+            priv_old_user: self.clone(),
+            priv_new_user: new_user.clone(),
+            priv_issued_callbacks: issued_callbacks,
+            priv_bul_membership_witness: bul_data.1,
+            priv_args,
+
+            pub_new_com: out_commit,
+            pub_old_nul: out_nul,
+            pub_issued_callback_coms: issued_cb_coms,
+            pub_args,
+            pub_pseudonym: pseudonym.unwrap_or_default(),
+            pub_bul_membership_data: bul_data.0,
+            bul_memb_is_const: is_memb_data_const,
+
+            associated_method: method,
+            is_scan,
+            _phantom_hash: core::marker::PhantomData,
+        };
+
+        Ok(exec_method_circ)
+    }
+
+    /// Get the constraint system for an interaction.
+    ///
+    /// Useful for debugging.
+    ///
+    /// **Note that this does not modify the user, it modifies a cloned user internally.**
+    ///
+    /// See [`User::interact`] for more documentation.
+    pub fn constraint_interact<
+        H: FieldHash<F>,
+        PubArgs: Clone + std::fmt::Debug,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone + std::fmt::Debug,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+        CBArgs: Clone + std::fmt::Debug,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        Bul: PublicUserBul<F, U>,
+        const NUMCBS: usize,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        method: Interaction<
+            F,
+            U,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            CBArgs,
+            CBArgsVar,
+            NUMCBS,
+        >,
+        rpks: [Crypto::SigPK; NUMCBS],
+        cur_time: Time<F>,
+        bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
+        is_memb_data_const: bool,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+        is_scan: bool,
+    ) -> Result<ConstraintSystemRef<F>, UserError> {
+        // Steps:
+        // a) update user/self [ old user ] --> method(user) [ new user ]
+        // b) update user's zk fields properly (new nul, new comrand, proper cblist, etc)
+        // c) generate proof of correctness for
+        //      - a) the user was properly updated via the predicate
+        //      - b) the zk statements (nul == old nul, proper cblist, etc)
+
+        // (A) update the user object
+        // Create the new zk_object from the method
+        let mut new_user = (method.meth.0)(self, pub_args.clone(), priv_args.clone());
+
+        // (B) update the new users zk fields properly
+
+        new_user.zk_fields.com_rand = rng.r#gen();
+        new_user.zk_fields.nul = derive_nul::<F, H>(new_user.zk_fields.com_rand);
+
+        let cb_tik_list: [(CallbackCom<F, CBArgs, Crypto>, Crypto::Rand); NUMCBS] =
+            create_cbs_from_interaction(rng, &method.callbacks, rpks, cur_time, NUMCBS);
+
+        let issued_callbacks: [CallbackCom<F, CBArgs, Crypto>; NUMCBS] = cb_tik_list
+            .iter()
+            .map(|(x, _)| x.clone())
+            .collect::<Vec<CallbackCom<F, CBArgs, Crypto>>>()
+            .try_into()
+            .unwrap();
+
+        let (_, issued_cb_coms) = callbacks_for_service::<F, H, CBArgs, Crypto, NUMCBS>(
+            &cb_tik_list,
+            NUMCBS,
+            method.hidden_ids.is_some(),
+        );
+
+        for item in issued_callbacks.iter().take(NUMCBS) {
+            let mut cb = Vec::new();
+            item.clone().serialize_compressed(&mut cb).unwrap();
+            new_user.callbacks.push(cb);
+
+            new_user.zk_fields.callback_hash = add_ticket_to_hc::<F, H, CBArgs, Crypto>(
+                new_user.zk_fields.callback_hash,
+                item.clone().cb_entry,
+            );
+        }
+
+        if !is_scan {
+            new_user.zk_fields.old_in_progress_callback_hash = new_user.zk_fields.callback_hash;
+        }
+
+        // (C) Generate proof of correctness
+        // Extract the zk fields from the objects to do bookkeeping
+
+        let out_commit = new_user.commit::<H>();
+
+        let out_nul = self.zk_fields.nul;
+
+        let pseudonym = method
+            .pseudonym_scope
+            .map(|scope| derive_pseudonym::<F, H>(self.zk_fields.secret, scope));
 
         let exec_method_circ: ExecMethodCircuit<
             F,
@@ -1362,6 +2688,7 @@ where
             pub_old_nul: out_nul,
             pub_issued_callback_coms: issued_cb_coms,
             pub_args,
+            pub_pseudonym: pseudonym.unwrap_or_default(),
             pub_bul_membership_data: bul_data.0,
             bul_memb_is_const: is_memb_data_const,
 
@@ -1461,10 +2788,14 @@ where
         pk: &Snark::ProvingKey,
         pub_args: PubArgs,
         priv_args: PrivArgs,
-    ) -> Result<ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>, SynthesisError> {
-        assert!(self.scan_index.is_none());
+    ) -> Result<ExecutedMethod<F, Snark, CBArgs, Crypto, NUMCBS>, UserError> {
+        if self.scan_index.is_some() {
+            return Err(UserError::ScanInProgress);
+        }
 
-        let bul_data = bul.get_membership_data(self.commit::<H>()).unwrap();
+        let bul_data = bul
+            .get_membership_data(self.commit::<H>())
+            .ok_or(UserError::MissingMembershipData)?;
 
         self.interact::<H, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, Crypto, Snark, Bul, NUMCBS>(
             rng,
@@ -1518,10 +2849,14 @@ where
         is_memb_data_const: bool,
         pub_args: PubArgs,
         priv_args: PrivArgs,
-    ) -> Result<ConstraintSystemRef<F>, SynthesisError> {
-        assert!(self.scan_index.is_none());
+    ) -> Result<ConstraintSystemRef<F>, UserError> {
+        if self.scan_index.is_some() {
+            return Err(UserError::ScanInProgress);
+        }
 
-        let bul_data = bul.get_membership_data(self.commit::<H>()).unwrap();
+        let bul_data = bul
+            .get_membership_data(self.commit::<H>())
+            .ok_or(UserError::MissingMembershipData)?;
 
         self.constraint_interact::<H, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, Crypto, Bul, NUMCBS>(
             rng,
@@ -1585,11 +2920,15 @@ where
             Bul,
             NUMCBS,
         >,
-        SynthesisError,
+        UserError,
     > {
-        assert!(self.scan_index.is_none());
+        if self.scan_index.is_some() {
+            return Err(UserError::ScanInProgress);
+        }
 
-        let bul_data = bul.get_membership_data(self.commit::<H>()).unwrap();
+        let bul_data = bul
+            .get_membership_data(self.commit::<H>())
+            .ok_or(UserError::MissingMembershipData)?;
 
         self.circuit_interact::<H, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, CBArgs, CBArgsVar, Crypto, Bul, NUMCBS>(
             rng,
@@ -1664,6 +3003,7 @@ where
     ///
     /// # Example
     /// ```rust
+    /// # use std::sync::Arc;
     /// # use zk_callbacks::zk_object;
     /// # use zk_callbacks::generic::user::User;
     /// # use rand::thread_rng;
@@ -1742,15 +3082,18 @@ where
     ///         method_id: Id::from(0),
     ///         expirable: false,
     ///         expiration: Time::from(10),
-    ///         method: callback,
-    ///         predicate: enforce_callback
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
     ///     };
     ///
     ///     let cb_methods = vec![cb.clone()];
     ///
     ///     let int = Interaction {
-    ///         meth: (method, predicate),
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
     ///         callbacks: [cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
     ///     };
     ///
     ///     let ex: PubScan = PubScanArgs {
@@ -1779,11 +3122,11 @@ where
     /// }
     /// ```
     pub fn scan_callbacks<
-        H: FieldHash<F>,
-        CBArgs: Clone + std::fmt::Debug + PartialEq + Eq,
-        CBArgsVar: AllocVar<CBArgs, F> + Clone,
-        Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq,
-        CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone,
+        H: FieldHash<F> + 'static,
+        CBArgs: Clone + std::fmt::Debug + PartialEq + Eq + 'static,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+        Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq + 'static,
+        CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + 'static,
         Snark: SNARK<F, Error = SynthesisError>,
         Bul: PublicUserBul<F, U>,
         const NUMSCANS: usize,
@@ -1802,91 +3145,24 @@ where
             PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>,
             ExecutedMethod<F, Snark, CBArgs, Crypto, 0>,
         ),
-        SynthesisError,
+        UserError,
     >
     where
+        U: 'static,
         U::UserDataVar: CondSelectGadget<F> + EqGadget<F>,
         CBul::MembershipPub: std::fmt::Debug,
         CBul::NonMembershipPub: std::fmt::Debug,
     {
-        let start_ind = match self.scan_index {
-            Some(ind) => {
-                assert!(NUMSCANS + ind <= self.callbacks.len());
-                ind
-            }
-            None => {
-                assert!(NUMSCANS <= self.callbacks.len());
-                0
-            }
-        };
-
-        let bul_data = bul.get_membership_data(self.commit::<H>()).unwrap();
-
-        let mut vec_cbs = vec![];
-        let mut vec_memb_pub = vec![];
-        let mut vec_nmemb_pub = vec![];
-        let mut vec_memb_priv = vec![];
-        let mut vec_nmemb_priv = vec![];
-        let mut vec_enc = vec![];
-        let mut vec_times = vec![];
-
-        for i in 0..NUMSCANS {
-            let cb: CallbackCom<F, CBArgs, Crypto> = self.get_cb::<CBArgs, Crypto>(start_ind + i);
-            let data = cbul.get_membership_data(cb.get_ticket());
-            let if_in = cbul.verify_in(cb.get_ticket());
-            let (enc, sig, time) = match if_in {
-                Some((e, s, t)) => (e, s, t),
-                None => (
-                    Crypto::Ct::default(),
-                    Crypto::Sig::default(),
-                    Time::default(),
-                ),
-            };
-
-            assert!(
-                <Crypto as AECipherSigZK<F, CBArgs>>::SigPK::verify(
-                    &cb.get_ticket(),
-                    enc.clone(),
-                    sig
-                ) == true
-            );
+        let bul_data = bul
+            .get_membership_data(self.commit::<H>())
+            .ok_or(UserError::MissingMembershipData)?;
 
-            vec_enc.push(enc);
-            vec_times.push(time);
-            vec_cbs.push(cb);
-            vec_memb_pub.push(data.0);
-            vec_memb_priv.push(data.1);
-            vec_nmemb_pub.push(data.2);
-            vec_nmemb_priv.push(data.3);
-        }
-
-        let ps: PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS> = PubScanArgs {
-            memb_pub: vec_memb_pub
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            nmemb_pub: vec_nmemb_pub
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            bulletin: cbul.clone(),
-            is_memb_data_const: is_memb_nmemb_const.0,
-            is_nmemb_data_const: is_memb_nmemb_const.1,
+        let (ps, prs) = self.get_scan_arguments::<CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>(
+            cbul,
+            is_memb_nmemb_const,
             cur_time,
             cb_methods,
-        };
-
-        let prs: PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS> = PrivScanArgs {
-            priv_n_tickets: vec_cbs.try_into().unwrap(),
-            post_times: vec_times.try_into().unwrap(),
-            enc_args: vec_enc
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            memb_priv: vec_memb_priv
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            nmemb_priv: vec_nmemb_priv
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-        };
+        )?;
 
         let out = self.interact::<H, PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PubScanArgsVar<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS>, PrivScanArgsVar<F, CBArgs, Crypto, CBul, NUMSCANS>, CBArgs, CBArgsVar, Crypto, Snark, Bul, 0>(
             rng,
@@ -1904,6 +3180,51 @@ where
         Ok((ps, out))
     }
 
+    /// Check that scanning callbacks would succeed, without proving anything.
+    ///
+    /// This synthesizes the scan circuit into a test constraint system and checks it is
+    /// satisfied. If a constraint is unsatisfied, this returns [`UserError::PredicateUnsatisfied`]
+    /// (naming the first failing constraint with the `trace` feature).
+    ///
+    /// **Note that this does not modify the user.**
+    ///
+    /// See [`User::scan_callbacks`] and [`User::dry_run_interact`] for more documentation.
+    pub fn dry_run_scan_callbacks<
+        H: FieldHash<F> + 'static,
+        CBArgs: Clone + std::fmt::Debug + PartialEq + Eq + 'static,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+        Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq + 'static,
+        CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + 'static,
+        Bul: PublicUserBul<F, U>,
+        const NUMSCANS: usize,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        bul: &Bul,
+        is_memb_data_const: bool,
+        cbul: &CBul,
+        is_memb_nmemb_const: (bool, bool),
+        cur_time: Time<F>,
+        cb_methods: Vec<Callback<F, U, CBArgs, CBArgsVar>>,
+    ) -> Result<(), UserError>
+    where
+        U: 'static,
+        U::UserDataVar: CondSelectGadget<F> + EqGadget<F>,
+    {
+        let (_, exec_method_circ) = self
+            .circuit_scan_callbacks::<H, CBArgs, CBArgsVar, Crypto, CBul, Bul, NUMSCANS>(
+                rng,
+                bul,
+                is_memb_data_const,
+                cbul,
+                is_memb_nmemb_const,
+                cur_time,
+                cb_methods,
+            )?;
+
+        dry_run_circuit(exec_method_circ)
+    }
+
     /// Get the constraint system for scanning callbacks.
     ///
     /// Useful for debugging.
@@ -1912,11 +3233,11 @@ where
     ///
     /// See [`User::scan_callbacks`] for more documentation.
     pub fn constraint_scan_callbacks<
-        H: FieldHash<F>,
-        CBArgs: Clone + std::fmt::Debug + PartialEq + Eq,
-        CBArgsVar: AllocVar<CBArgs, F> + Clone,
-        Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq,
-        CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone,
+        H: FieldHash<F> + 'static,
+        CBArgs: Clone + std::fmt::Debug + PartialEq + Eq + 'static,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+        Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq + 'static,
+        CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + 'static,
         Bul: PublicUserBul<F, U>,
         const NUMSCANS: usize,
     >(
@@ -1933,89 +3254,22 @@ where
             PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>,
             ConstraintSystemRef<F>,
         ),
-        SynthesisError,
+        UserError,
     >
     where
-        U::UserDataVar: CondSelectGadget<F> + EqGadget<F>,
-    {
-        let start_ind = match self.scan_index {
-            Some(ind) => {
-                assert!(NUMSCANS + ind <= self.callbacks.len());
-                ind
-            }
-            None => {
-                assert!(NUMSCANS <= self.callbacks.len());
-                0
-            }
-        };
-
-        let bul_data = bul.get_membership_data(self.commit::<H>()).unwrap();
-
-        let mut vec_cbs = vec![];
-        let mut vec_memb_pub = vec![];
-        let mut vec_nmemb_pub = vec![];
-        let mut vec_memb_priv = vec![];
-        let mut vec_nmemb_priv = vec![];
-        let mut vec_enc = vec![];
-        let mut vec_times = vec![];
-
-        for i in 0..NUMSCANS {
-            let cb: CallbackCom<F, CBArgs, Crypto> = self.get_cb::<CBArgs, Crypto>(start_ind + i);
-            let data = cbul.get_membership_data(cb.get_ticket());
-            let if_in = cbul.verify_in(cb.get_ticket());
-            let (enc, sig, time) = match if_in {
-                Some((e, s, t)) => (e, s, t),
-                None => (
-                    Crypto::Ct::default(),
-                    Crypto::Sig::default(),
-                    Time::default(),
-                ),
-            };
-
-            assert!(
-                <Crypto as AECipherSigZK<F, CBArgs>>::SigPK::verify(
-                    &cb.get_ticket(),
-                    enc.clone(),
-                    sig
-                ) == true
-            );
-
-            vec_enc.push(enc);
-            vec_times.push(time);
-            vec_cbs.push(cb);
-            vec_memb_pub.push(data.0);
-            vec_memb_priv.push(data.1);
-            vec_nmemb_pub.push(data.2);
-            vec_nmemb_priv.push(data.3);
-        }
-
-        let ps: PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS> = PubScanArgs {
-            memb_pub: vec_memb_pub
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            nmemb_pub: vec_nmemb_pub
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            bulletin: cbul.clone(),
-            is_memb_data_const: is_memb_nmemb_const.0,
-            is_nmemb_data_const: is_memb_nmemb_const.1,
+        U: 'static,
+        U::UserDataVar: CondSelectGadget<F> + EqGadget<F>,
+    {
+        let bul_data = bul
+            .get_membership_data(self.commit::<H>())
+            .ok_or(UserError::MissingMembershipData)?;
+
+        let (ps, prs) = self.get_scan_arguments::<CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>(
+            cbul,
+            is_memb_nmemb_const,
             cur_time,
             cb_methods,
-        };
-
-        let prs: PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS> = PrivScanArgs {
-            priv_n_tickets: vec_cbs.try_into().unwrap(),
-            post_times: vec_times.try_into().unwrap(),
-            enc_args: vec_enc
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            memb_priv: vec_memb_priv
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            nmemb_priv: vec_nmemb_priv
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-        };
+        )?;
 
         let out = self.constraint_interact::<H, PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PubScanArgsVar<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS>, PrivScanArgsVar<F, CBArgs, Crypto, CBul, NUMSCANS>, CBArgs, CBArgsVar, Crypto, Bul, 0>(
             rng,
@@ -2036,11 +3290,11 @@ where
     ///
     /// For advanced use only.
     pub fn circuit_scan_callbacks<
-        H: FieldHash<F>,
-        CBArgs: Clone + std::fmt::Debug + PartialEq + Eq,
-        CBArgsVar: AllocVar<CBArgs, F> + Clone,
-        Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq,
-        CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone,
+        H: FieldHash<F> + 'static,
+        CBArgs: Clone + std::fmt::Debug + PartialEq + Eq + 'static,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone + 'static,
+        Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq + 'static,
+        CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone + 'static,
         Bul: PublicUserBul<F, U>,
         const NUMSCANS: usize,
     >(
@@ -2070,89 +3324,22 @@ where
                 0,
             >,
         ),
-        SynthesisError,
+        UserError,
     >
     where
+        U: 'static,
         U::UserDataVar: CondSelectGadget<F> + EqGadget<F>,
     {
-        let start_ind = match self.scan_index {
-            Some(ind) => {
-                assert!(NUMSCANS + ind <= self.callbacks.len());
-                ind
-            }
-            None => {
-                assert!(NUMSCANS <= self.callbacks.len());
-                0
-            }
-        };
-
-        let bul_data = bul.get_membership_data(self.commit::<H>()).unwrap();
-
-        let mut vec_cbs = vec![];
-        let mut vec_memb_pub = vec![];
-        let mut vec_nmemb_pub = vec![];
-        let mut vec_memb_priv = vec![];
-        let mut vec_nmemb_priv = vec![];
-        let mut vec_enc = vec![];
-        let mut vec_times = vec![];
-
-        for i in 0..NUMSCANS {
-            let cb: CallbackCom<F, CBArgs, Crypto> = self.get_cb::<CBArgs, Crypto>(start_ind + i);
-            let data = cbul.get_membership_data(cb.get_ticket());
-            let if_in = cbul.verify_in(cb.get_ticket());
-            let (enc, sig, time) = match if_in {
-                Some((e, s, t)) => (e, s, t),
-                None => (
-                    Crypto::Ct::default(),
-                    Crypto::Sig::default(),
-                    Time::default(),
-                ),
-            };
+        let bul_data = bul
+            .get_membership_data(self.commit::<H>())
+            .ok_or(UserError::MissingMembershipData)?;
 
-            assert!(
-                <Crypto as AECipherSigZK<F, CBArgs>>::SigPK::verify(
-                    &cb.get_ticket(),
-                    enc.clone(),
-                    sig
-                ) == true
-            );
-
-            vec_enc.push(enc);
-            vec_times.push(time);
-            vec_cbs.push(cb);
-            vec_memb_pub.push(data.0);
-            vec_memb_priv.push(data.1);
-            vec_nmemb_pub.push(data.2);
-            vec_nmemb_priv.push(data.3);
-        }
-
-        let ps: PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS> = PubScanArgs {
-            memb_pub: vec_memb_pub
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            nmemb_pub: vec_nmemb_pub
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            bulletin: cbul.clone(),
-            is_memb_data_const: is_memb_nmemb_const.0,
-            is_nmemb_data_const: is_memb_nmemb_const.1,
+        let (ps, prs) = self.get_scan_arguments::<CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>(
+            cbul,
+            is_memb_nmemb_const,
             cur_time,
             cb_methods,
-        };
-
-        let prs: PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS> = PrivScanArgs {
-            priv_n_tickets: vec_cbs.try_into().unwrap(),
-            post_times: vec_times.try_into().unwrap(),
-            enc_args: vec_enc
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            memb_priv: vec_memb_priv
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-            nmemb_priv: vec_nmemb_priv
-                .try_into()
-                .unwrap_or_else(|_| panic!("Unexpected failure.")),
-        };
+        )?;
 
         let out = self.circuit_interact::<H, PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PubScanArgsVar<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>, PrivScanArgs<F, CBArgs, Crypto, CBul, NUMSCANS>, PrivScanArgsVar<F, CBArgs, Crypto, CBul, NUMSCANS>, CBArgs, CBArgsVar, Crypto, Bul, 0>(
             rng,
@@ -2166,48 +3353,333 @@ where
             true,
         )?;
 
-        Ok((ps, out))
+        Ok((ps, out))
+    }
+
+    /// Scan all outstanding callbacks, using as few scan proofs as possible.
+    ///
+    /// Given keys for scans over different numbers of callbacks, this plans the fewest scans
+    /// which together cover the rest of the callback list (see [`plan_scans`]), and performs them
+    /// in order. If a scan is already in progress, only the remaining callbacks are scanned.
+    ///
+    /// Each scan proves membership of the user produced by the previous scan, so the scans depend
+    /// on each other and must be run in sequence. After each scan, the proof is passed to
+    /// `submit`, which should submit it to the user bulletin (verifying it with the public
+    /// arguments [`ScanProof::pub_args`]), and return the membership data of the new user once
+    /// it is accepted, or `None` if it is rejected. `bul_data` is the membership data of the user
+    /// before the first scan.
+    ///
+    /// If a scan fails or is rejected, the user is left as of the last accepted scan, and this
+    /// returns [`UserError::BulletinRejected`] for a rejected scan.
+    ///
+    /// Returns the scan proofs in order. Once this succeeds, the scan is complete and `self` is
+    /// the final user.
+    ///
+    /// See [`User::scan_callbacks`] for the remaining arguments.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::generic::user::User;
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use zk_callbacks::generic::interaction::Interaction;
+    /// # use zk_callbacks::generic::interaction::Callback;
+    /// # use zk_callbacks::generic::object::Id;
+    /// # use zk_callbacks::generic::object::Time;
+    /// # use zk_callbacks::generic::object::TimeVar;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use zk_callbacks::generic::user::UserVar;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use zk_callbacks::generic::bulletin::UserBul;
+    /// # use zk_callbacks::generic::scan::{PubScanArgs, ScanKey, get_scan_interaction};
+    /// # use zk_callbacks::impls::centralized::crypto::{FakeSigPubkey, NoSigOTP};
+    /// # use zk_callbacks::scannable_zk_object;
+    /// # type Groth = Groth16<E>;
+    /// #[scannable_zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub bad_rep: Fr,
+    /// }
+    ///
+    /// fn method<'a>(old_user: &'a User<Fr, Data>, _pub: (), _priv: ()) -> User<Fr, Data> {
+    ///     old_user.clone()
+    /// }
+    ///
+    /// fn predicate<'a>(old_user: &'a UserVar<Fr, Data>, new_user: &'a UserVar<Fr, Data>, _pub: (), _priv: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     old_user.data.bad_rep.is_eq(&new_user.data.bad_rep)
+    /// }
+    ///
+    /// fn callback<'a>(old_user: &'a User<Fr, Data>, args: Fr) -> User<Fr, Data> {
+    ///     let mut u = old_user.clone();
+    ///     u.data.bad_rep += args;
+    ///     u
+    /// }
+    ///
+    /// fn enforce_callback<'a>(old_user: &'a UserVar<Fr, Data>, args: FpVar<Fr>) -> Result<UserVar<Fr, Data>, SynthesisError> {
+    ///     let mut u = old_user.clone();
+    ///     u.data.bad_rep = u.data.bad_rep + args;
+    ///     Ok(u)
+    /// }
+    ///
+    /// fn main () {
+    ///     let cb = Callback {
+    ///         method_id: Id::from(0),
+    ///         expirable: false,
+    ///         expiration: Time::from(10),
+    ///         method: Arc::new(callback),
+    ///         predicate: Arc::new(enforce_callback)
+    ///     };
+    ///
+    ///     let int = Interaction {
+    ///         meth: (Arc::new(method), Arc::new(predicate)),
+    ///         callbacks: [cb.clone(), cb.clone(), cb.clone()],
+    ///         unchanged: None,
+    ///         hidden_ids: None,
+    ///         pseudonym_scope: None,
+    ///     };
+    ///
+    ///     let mut rng = thread_rng();
+    ///     let (pk, _) = int.generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), (), false);
+    ///
+    ///     let ex1: PubScanArgs<_, Data, _, _, NoSigOTP<Fr>, DummyStore, 1> = PubScanArgs {
+    ///         memb_pub: [(); 1],
+    ///         is_memb_data_const: true,
+    ///         nmemb_pub: [(); 1],
+    ///         is_nmemb_data_const: true,
+    ///         cur_time: Fr::from(0),
+    ///         bulletin: DummyStore,
+    ///         cb_methods: vec![cb.clone()],
+    ///     };
+    ///     let ex2: PubScanArgs<_, Data, _, _, NoSigOTP<Fr>, DummyStore, 2> = PubScanArgs {
+    ///         memb_pub: [(); 2],
+    ///         is_memb_data_const: true,
+    ///         nmemb_pub: [(); 2],
+    ///         is_nmemb_data_const: true,
+    ///         cur_time: Fr::from(0),
+    ///         bulletin: DummyStore,
+    ///         cb_methods: vec![cb.clone()],
+    ///     };
+    ///
+    ///     let (pk1, vk1) = get_scan_interaction::<_, _, _, _, _, _, Poseidon<2>, 1>().generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), ex1, true);
+    ///     let (pk2, vk2) = get_scan_interaction::<_, _, _, _, _, _, Poseidon<2>, 2>().generate_keys::<Poseidon<2>, Groth, NoSigOTP<Fr>, DummyStore>(&mut rng, Some(()), ex2, true);
+    ///
+    ///     let keys: Vec<ScanKey<'_, Fr, Data, Fr, FpVar<Fr>, NoSigOTP<Fr>, DummyStore, Groth, DummyStore>> = vec![
+    ///         ScanKey::new::<Poseidon<2>, 1>(&pk1),
+    ///         ScanKey::new::<Poseidon<2>, 2>(&pk2),
+    ///     ];
+    ///
+    ///     let mut u = User::create(Data { bad_rep: Fr::from(0) }, &mut rng);
+    ///
+    ///     u.interact::<Poseidon<2>, (), (), (), (), Fr, FpVar<Fr>, NoSigOTP<Fr>, Groth, DummyStore, 3>(&mut rng, int.clone(), [FakeSigPubkey::pk(), FakeSigPubkey::pk(), FakeSigPubkey::pk()], Time::from(0), ((), ()), true, &pk, (), (), false).unwrap();
+    ///
+    ///     let proofs = u.scan_all(&mut rng, ((), ()), true, &keys, &DummyStore, (true, true), Time::from(0), vec![cb.clone()], |proof| {
+    ///         let vk = if proof.num_scans == 2 { &vk2 } else { &vk1 };
+    ///         <DummyStore as UserBul<Fr, Data>>::verify_interact_and_append::<_, Groth, 0>(&mut DummyStore, proof.method.new_object, proof.method.old_nullifier, proof.pub_args.clone(), proof.method.cb_com_list, proof.method.proof.clone(), None, vk).ok()?;
+    ///         Some(((), ()))
+    ///     }).unwrap();
+    ///
+    ///     assert_eq!(proofs.iter().map(|p| p.num_scans).collect::<Vec<_>>(), vec![2, 1]);
+    ///     assert!(!u.is_scanning());
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn scan_all<
+        CBArgs: Clone,
+        CBArgsVar: AllocVar<CBArgs, F> + Clone,
+        Crypto: AECipherSigZK<F, CBArgs>,
+        CBul: PublicCallbackBul<F, CBArgs, Crypto>,
+        Snark: SNARK<F>,
+        Bul: PublicUserBul<F, U>,
+    >(
+        &mut self,
+        rng: &mut (impl CryptoRng + RngCore),
+        bul_data: (Bul::MembershipPub, Bul::MembershipWitness),
+        is_memb_data_const: bool,
+        keys: &[ScanKey<'_, F, U, CBArgs, CBArgsVar, Crypto, CBul, Snark, Bul>],
+        cbul: &CBul,
+        is_memb_nmemb_const: (bool, bool),
+        cur_time: Time<F>,
+        cb_methods: Vec<Callback<F, U, CBArgs, CBArgsVar>>,
+        mut submit: impl FnMut(
+            &ScanProof<F, Snark, CBArgs, Crypto>,
+        ) -> Option<(Bul::MembershipPub, Bul::MembershipWitness)>,
+    ) -> Result<Vec<ScanProof<F, Snark, CBArgs, Crypto>>, UserError> {
+        let outstanding = self.callbacks.len() - self.scan_index.unwrap_or(0);
+        let sizes: Vec<usize> = keys.iter().map(ScanKey::num_scans).collect();
+        let plan = plan_scans(outstanding, &sizes).ok_or(UserError::NoScanPlan { outstanding })?;
+
+        let mut proofs = vec![];
+        let mut bul_data = bul_data;
+        for size in plan {
+            let key = keys
+                .iter()
+                .find(|k| k.num_scans() == size)
+                .expect("planned scans use available keys");
+
+            let old_user = self.clone();
+            let proof = key.scan(
+                self,
+                rng,
+                bul_data,
+                is_memb_data_const,
+                cbul,
+                is_memb_nmemb_const,
+                cur_time,
+                cb_methods.clone(),
+            )?;
+
+            match submit(&proof) {
+                Some(data) => bul_data = data,
+                None => {
+                    *self = old_user;
+                    return Err(UserError::BulletinRejected);
+                }
+            }
+            proofs.push(proof);
+        }
+
+        Ok(proofs)
+    }
+
+    /// Prove a generic statement about the user with respect to a public user commitment.
+    ///
+    /// This function allows one to prove something about a user object with a public commitment.
+    /// Note that this *does not preserve anonymity* if the proof + result is given to a service,
+    /// as the user commitment is revealed (not the user, so privacy is not an issue).
+    ///
+    ///# Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::user::User;
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use ark_r1cs_std::cmp::CmpGadget;
+    /// # use zk_callbacks::generic::interaction::Interaction;
+    /// # use zk_callbacks::generic::interaction::Callback;
+    /// # use zk_callbacks::generic::object::Id;
+    /// # use zk_callbacks::generic::object::Time;
+    /// # use zk_callbacks::generic::object::TimeVar;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use zk_callbacks::generic::user::UserVar;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use ark_r1cs_std::prelude::UInt8;
+    /// # use zk_callbacks::impls::dummy::DummyStore;
+    /// # use ark_r1cs_std::select::CondSelectGadget;
+    /// # use ark_snark::SNARK;
+    /// # use ark_ff::ToConstraintField;
+    /// # use zk_callbacks::generic::interaction::generate_keys_for_statement;
+    /// # use zk_callbacks::impls::centralized::crypto::{NoSigOTP};
+    /// # type Groth = Groth16<E>;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub num_visits: Fr,
+    ///     pub bad_rep: u8,
+    ///     pub last_interacted_time: Time<Fr>,
+    /// }
+    ///
+    /// fn predicate<'a, 'b>(user: &'a UserVar<Fr, Data>, _com: &'b FpVar<Fr>, _pub_args: (), _priv_args: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     user.data.num_visits.is_eq(&FpVar::Constant(Fr::from(1)))
+    /// }
+    ///
+    /// fn main () {
+    ///
+    ///     let mut rng = thread_rng();
+    ///
+    ///     let (pk, vk) = generate_keys_for_statement::<Fr, Poseidon<2>, Data, _, _, _, _, Groth>(&mut rng, Arc::new(predicate), ());
+    ///
+    ///     let mut u = User::create(Data { bad_rep: 0, num_visits: Fr::from(1), last_interacted_time: Time::from(0) }, &mut rng);
+    ///
+    ///     let result = u.prove_statement::<Poseidon<2>, _, _, _, _, Groth>(&mut rng, Arc::new(predicate), &pk, (), ()).unwrap();
+    ///
+    ///     assert_eq!(result.object, u.commit::<Poseidon<2>>());
+    ///
+    ///     let mut pub_inputs = vec![];
+    ///
+    ///     pub_inputs.extend_from_slice(&result.object.to_field_elements().unwrap());
+    ///     pub_inputs.extend_from_slice(&().to_field_elements().unwrap());
+    ///
+    ///     let out = Groth::verify(&vk, &pub_inputs, &result.proof).unwrap();
+    ///
+    ///     assert!(out);
+    ///
+    /// }
+    /// ```
+    pub fn prove_statement<
+        H: FieldHash<F>,
+        PubArgs: Clone,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+        Snark: SNARK<F, Error = SynthesisError>,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        predicate: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+        pk: &Snark::ProvingKey,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+    ) -> Result<ProveResult<F, Snark>, UserError> {
+        let (result, _) = self
+            .prove_disclosed_statement::<H, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, Snark>(
+                rng,
+                predicate,
+                Disclosure::default(),
+                pk,
+                pub_args,
+                priv_args,
+            )?;
+
+        Ok(result)
     }
 
-    /// Prove a generic statement about the user with respect to a public user commitment.
+    /// Prove a generic statement about the user with respect to a public user commitment, while
+    /// revealing some fields of the user.
     ///
-    /// This function allows one to prove something about a user object with a public commitment.
-    /// Note that this *does not preserve anonymity* if the proof + result is given to a service,
-    /// as the user commitment is revealed (not the user, so privacy is not an issue).
+    /// This is the same as [`User::prove_statement`], except the fields of the user selected by
+    /// `disclosure` are revealed, bound to the proof. This returns the result of the proof along
+    /// with the revealed values, from which the verifier may read the fields with
+    /// [`Disclosure::get`] and [`Disclosure::get_hash`].
+    ///
+    /// Keys may be generated with
+    /// [`generate_keys_for_disclosed_statement`](`crate::generic::interaction::generate_keys_for_disclosed_statement`),
+    /// using the same disclosure. The public inputs to the proof are the commitment, `pub_args`
+    /// and then the revealed values.
     ///
     ///# Example
     /// ```rust
+    /// # use std::sync::Arc;
     /// # use zk_callbacks::zk_object;
     /// # use zk_callbacks::generic::user::User;
     /// # use rand::thread_rng;
     /// # use ark_bn254::{Bn254 as E, Fr};
     /// # use ark_r1cs_std::eq::EqGadget;
-    /// # use ark_r1cs_std::cmp::CmpGadget;
-    /// # use zk_callbacks::generic::interaction::Interaction;
-    /// # use zk_callbacks::generic::interaction::Callback;
-    /// # use zk_callbacks::generic::object::Id;
-    /// # use zk_callbacks::generic::object::Time;
-    /// # use zk_callbacks::generic::object::TimeVar;
     /// # use ark_relations::r1cs::SynthesisError;
     /// # use zk_callbacks::generic::user::UserVar;
     /// # use ark_r1cs_std::fields::fp::FpVar;
     /// # use ark_groth16::Groth16;
     /// # use ark_r1cs_std::prelude::Boolean;
     /// # use zk_callbacks::impls::hash::Poseidon;
-    /// # use ark_r1cs_std::prelude::UInt8;
-    /// # use zk_callbacks::impls::dummy::DummyStore;
-    /// # use ark_r1cs_std::select::CondSelectGadget;
     /// # use ark_snark::SNARK;
     /// # use ark_ff::ToConstraintField;
-    /// # use zk_callbacks::generic::interaction::generate_keys_for_statement;
-    /// # use zk_callbacks::impls::centralized::crypto::{NoSigOTP};
+    /// # use zk_callbacks::generic::interaction::{Disclosure, generate_keys_for_disclosed_statement};
     /// # type Groth = Groth16<E>;
     /// #[zk_object(Fr)]
     /// #[derive(Default)]
     /// struct Data {
     ///     pub num_visits: Fr,
-    ///     pub bad_rep: u8,
-    ///     pub last_interacted_time: Time<Fr>,
+    ///     pub tier: u8,
     /// }
     ///
     /// fn predicate<'a, 'b>(user: &'a UserVar<Fr, Data>, _com: &'b FpVar<Fr>, _pub_args: (), _priv_args: ()) -> Result<Boolean<Fr>, SynthesisError> {
@@ -2218,26 +3690,34 @@ where
     ///
     ///     let mut rng = thread_rng();
     ///
-    ///     let (pk, vk) = generate_keys_for_statement::<Fr, Poseidon<2>, Data, _, _, _, _, Groth>(&mut rng, predicate, None);
+    ///     // Reveal the tier, and a hash of the number of visits.
+    ///     let disclosure = Disclosure::new()
+    ///         .reveal(DataFields::tier())
+    ///         .reveal_hashed::<Poseidon<2>>(DataFields::num_visits());
     ///
-    ///     let mut u = User::create(Data { bad_rep: 0, num_visits: Fr::from(1), last_interacted_time: Time::from(0) }, &mut rng);
+    ///     let (pk, vk) = generate_keys_for_disclosed_statement::<Fr, Poseidon<2>, Data, _, _, _, _, Groth>(&mut rng, Arc::new(predicate), disclosure.clone(), ());
     ///
-    ///     let result = u.prove_statement::<Poseidon<2>, _, _, _, _, Groth>(&mut rng, predicate, &pk, (), ()).unwrap();
+    ///     let u = User::create(Data { num_visits: Fr::from(1), tier: 3 }, &mut rng);
     ///
-    ///     assert_eq!(result.object, u.commit::<Poseidon<2>>());
+    ///     let (result, revealed) = u.prove_disclosed_statement::<Poseidon<2>, _, _, _, _, Groth>(&mut rng, Arc::new(predicate), disclosure.clone(), &pk, (), ()).unwrap();
     ///
     ///     let mut pub_inputs = vec![];
     ///
     ///     pub_inputs.extend_from_slice(&result.object.to_field_elements().unwrap());
     ///     pub_inputs.extend_from_slice(&().to_field_elements().unwrap());
+    ///     pub_inputs.extend_from_slice(&revealed.to_field_elements().unwrap());
     ///
-    ///     let out = Groth::verify(&vk, &pub_inputs, &result.proof).unwrap();
-    ///
-    ///     assert!(out);
+    ///     assert!(Groth::verify(&vk, &pub_inputs, &result.proof).unwrap());
     ///
+    ///     // The verifier reads back the typed values.
+    ///     assert_eq!(disclosure.get::<u8>(&revealed, DataFields::tier()), Some(3));
+    ///     assert_eq!(
+    ///         disclosure.get_hash(&revealed, DataFields::num_visits()),
+    ///         Some(Disclosure::<Fr, Data>::hashed_value::<Poseidon<2>, _>(&Fr::from(1)))
+    ///     );
     /// }
     /// ```
-    pub fn prove_statement<
+    pub fn prove_disclosed_statement<
         H: FieldHash<F>,
         PubArgs: Clone,
         PubArgsVar: AllocVar<PubArgs, F> + Clone,
@@ -2248,10 +3728,12 @@ where
         &self,
         rng: &mut (impl CryptoRng + RngCore),
         predicate: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+        disclosure: Disclosure<F, U>,
         pk: &Snark::ProvingKey,
         pub_args: PubArgs,
         priv_args: PrivArgs,
-    ) -> Result<ProveResult<F, Snark>, SynthesisError> {
+    ) -> Result<(ProveResult<F, Snark>, Revealed<F>), UserError> {
+        let revealed = disclosure.values(self);
         let ppcirc: ProvePredicateCircuit<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> =
             ProvePredicateCircuit {
                 priv_user: self.clone(),
@@ -2259,19 +3741,92 @@ where
                 priv_args,
 
                 pub_args,
+                pub_revealed: revealed.clone(),
                 associated_method: predicate,
+                disclosure,
             };
 
-        let new_cs = ConstraintSystem::<F>::new_ref();
-        ppcirc.clone().generate_constraints(new_cs.clone())?;
-        new_cs.is_satisfied()?;
+        #[cfg(debug_assertions)]
+        ensure_satisfied(ppcirc.clone(), false)?;
 
         let proof = Snark::prove(pk, ppcirc, rng)?;
 
-        Ok(ProveResult {
-            object: self.commit::<H>(),
-            proof,
-        })
+        Ok((
+            ProveResult {
+                object: self.commit::<H>(),
+                proof,
+            },
+            revealed,
+        ))
+    }
+
+    /// Check that a statement holds for the user, without proving anything.
+    ///
+    /// This synthesizes the predicate circuit into a test constraint system and checks it is
+    /// satisfied. If the predicate does not hold, this returns
+    /// [`UserError::PredicateUnsatisfied`] (naming the first failing constraint with the `trace`
+    /// feature).
+    ///
+    /// See [`User::prove_statement`] and [`User::dry_run_interact`] for more documentation.
+    ///
+    ///# Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::user::{User, UserError, UserVar};
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::Fr;
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub num_visits: Fr,
+    /// }
+    ///
+    /// fn predicate<'a, 'b>(user: &'a UserVar<Fr, Data>, _com: &'b FpVar<Fr>, _pub_args: (), _priv_args: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     user.data.num_visits.is_eq(&FpVar::Constant(Fr::from(1)))
+    /// }
+    ///
+    /// fn main () {
+    ///     let mut rng = thread_rng();
+    ///
+    ///     let u = User::create(Data { num_visits: Fr::from(0) }, &mut rng);
+    ///
+    ///     // The statement is false, so no proof should be made
+    ///     let res = u.dry_run_prove_statement::<Poseidon<2>, (), (), (), ()>(Arc::new(predicate), (), ());
+    ///
+    ///     assert!(matches!(res, Err(UserError::PredicateUnsatisfied(_))));
+    /// }
+    /// ```
+    pub fn dry_run_prove_statement<
+        H: FieldHash<F>,
+        PubArgs: Clone,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+    >(
+        &self,
+        predicate: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+    ) -> Result<(), UserError> {
+        let ppcirc: ProvePredicateCircuit<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> =
+            ProvePredicateCircuit {
+                priv_user: self.clone(),
+                pub_com: self.commit::<H>(),
+                priv_args,
+
+                pub_args,
+                pub_revealed: Revealed::default(),
+                associated_method: predicate,
+                disclosure: Disclosure::default(),
+            };
+
+        dry_run_circuit(ppcirc)
     }
 
     /// Get the constraint system for proving a statement on a user.
@@ -2291,7 +3846,7 @@ where
         predicate: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
         pub_args: PubArgs,
         priv_args: PrivArgs,
-    ) -> Result<ConstraintSystemRef<F>, SynthesisError> {
+    ) -> Result<ConstraintSystemRef<F>, UserError> {
         let ppcirc: ProvePredicateCircuit<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> =
             ProvePredicateCircuit {
                 priv_user: self.clone(),
@@ -2299,7 +3854,9 @@ where
                 priv_args,
 
                 pub_args,
+                pub_revealed: Revealed::default(),
                 associated_method: predicate,
+                disclosure: Disclosure::default(),
             };
 
         let new_cs = ConstraintSystem::<F>::new_ref();
@@ -2323,10 +3880,8 @@ where
         predicate: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
         pub_args: PubArgs,
         priv_args: PrivArgs,
-    ) -> Result<
-        ProvePredicateCircuit<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar>,
-        SynthesisError,
-    > {
+    ) -> Result<ProvePredicateCircuit<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar>, UserError>
+    {
         let ppcirc: ProvePredicateCircuit<F, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> =
             ProvePredicateCircuit {
                 priv_user: self.clone(),
@@ -2334,12 +3889,64 @@ where
                 priv_args,
 
                 pub_args,
+                pub_revealed: Revealed::default(),
                 associated_method: predicate,
+                disclosure: Disclosure::default(),
             };
 
         Ok(ppcirc)
     }
 
+    /// Prove a generic statement about the user with respect to a public user commitment under
+    /// the commitment scheme `C`.
+    ///
+    /// This is the analogue of [`User::prove_statement`] for commitment schemes other than the
+    /// default hash commitment, such as [`Pedersen`](`crate::impls::pedersen::Pedersen`)
+    /// commitments. The proof also shows that the returned commitment opens to the user.
+    ///
+    /// The returned commitment is not tied to the user's bulletin entry, which is always a
+    /// [`HashCom`] commitment. In particular, the proof does not show the user is a member of any
+    /// bulletin.
+    ///
+    /// Keys may be generated with
+    /// [`generate_keys_for_committed_statement`](`crate::generic::interaction::generate_keys_for_committed_statement`).
+    /// The public inputs to the proof are the commitment followed by `pub_args`.
+    pub fn prove_committed_statement<
+        C: UserCommitment<F>,
+        PubArgs: Clone,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+        Snark: SNARK<F, Error = SynthesisError>,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        params: &C::Params,
+        predicate: SingularPredicate<F, UserVar<F, U>, C::ComVar, PubArgsVar, PrivArgsVar>,
+        pk: &Snark::ProvingKey,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+    ) -> Result<(C::Com, Snark::Proof), UserError> {
+        let com = self.commit_with::<C>(params);
+        let pccirc: ProveCommittedCircuit<F, C, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar> =
+            ProveCommittedCircuit {
+                priv_user: self.clone(),
+                pub_com: com.clone(),
+                priv_args,
+
+                pub_args,
+                params: params.clone(),
+                associated_method: predicate,
+            };
+
+        #[cfg(debug_assertions)]
+        ensure_satisfied(pccirc.clone(), false)?;
+
+        let proof = Snark::prove(pk, pccirc, rng)?;
+
+        Ok((com, proof))
+    }
+
     /// Prove a statement about the user object, along with membership in some bulletin.
     ///
     /// If a method update is not necessary, one can prove a statement about their user without
@@ -2361,6 +3968,7 @@ where
     ///
     ///# Example
     /// ```rust
+    /// # use std::sync::Arc;
     /// # use zk_callbacks::zk_object;
     /// # use zk_callbacks::generic::user::User;
     /// # use rand::thread_rng;
@@ -2405,13 +4013,13 @@ where
     ///
     ///     let mut obj_store = UOVObjStore::new(&mut rng);
     ///
-    ///     let (pk, vk) = generate_keys_for_statement_in::<Fr, Poseidon<2>, Data, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, predicate, Some(obj_store.get_pubkey()), None);
+    ///     let (pk, vk) = generate_keys_for_statement_in::<Fr, Poseidon<2>, Data, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, Arc::new(predicate), Some(obj_store.get_pubkey()), ());
     ///
     ///     let mut u = User::create(Data { bad_rep: 0, num_visits: Fr::from(1), last_interacted_time: Time::from(0) }, &mut rng);
     ///
     ///     <UOVObjStore<Fr> as JoinableBulletin<Fr, Data>>::join_bul(&mut obj_store, u.commit::<Poseidon<2>>(), ()).unwrap();
     ///
-    ///     let result = u.prove_statement_and_in::<Poseidon<2>, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, predicate, &pk, (obj_store.get_signature_of(&u.commit::<Poseidon<2>>()).unwrap(), obj_store.get_pubkey()), true, (), ()).unwrap();
+    ///     let result = u.prove_statement_and_in::<Poseidon<2>, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, Arc::new(predicate), &pk, (obj_store.get_signature_of(&u.commit::<Poseidon<2>>()).unwrap(), obj_store.get_pubkey()), true, (), ()).unwrap();
     ///
     /// }
     /// ```
@@ -2432,7 +4040,121 @@ where
         is_memb_data_const: bool,
         pub_args: PubArgs,
         priv_args: PrivArgs,
-    ) -> Result<Snark::Proof, SynthesisError> {
+    ) -> Result<Snark::Proof, UserError> {
+        let (proof, _) = self.prove_disclosed_statement_and_in::<
+            H,
+            PubArgs,
+            PubArgsVar,
+            PrivArgs,
+            PrivArgsVar,
+            Snark,
+            Bul,
+        >(
+            rng,
+            predicate,
+            Disclosure::default(),
+            pk,
+            memb_data,
+            is_memb_data_const,
+            pub_args,
+            priv_args,
+        )?;
+
+        Ok(proof)
+    }
+
+    /// Prove a statement about the user object, along with membership in some bulletin, while
+    /// revealing some fields of the user.
+    ///
+    /// This is the same as [`User::prove_statement_and_in`], except the fields of the user
+    /// selected by `disclosure` are revealed, bound to the proof. This returns the proof along
+    /// with the revealed values, from which the verifier may read the fields with
+    /// [`Disclosure::get`] and [`Disclosure::get_hash`]. Note that revealed fields may make the
+    /// user linkable across proofs; revealing a hash does not prevent this.
+    ///
+    /// Keys may be generated with
+    /// [`generate_keys_for_disclosed_statement_in`](`crate::generic::interaction::generate_keys_for_disclosed_statement_in`),
+    /// using the same disclosure. The public inputs to the proof are `pub_args`, the revealed
+    /// values, and then the membership data (if not constant).
+    ///
+    ///# Example
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// # use zk_callbacks::zk_object;
+    /// # use zk_callbacks::generic::user::User;
+    /// # use rand::thread_rng;
+    /// # use ark_bn254::{Bn254 as E, Fr};
+    /// # use ark_r1cs_std::eq::EqGadget;
+    /// # use ark_relations::r1cs::SynthesisError;
+    /// # use zk_callbacks::generic::user::UserVar;
+    /// # use ark_r1cs_std::fields::fp::FpVar;
+    /// # use ark_groth16::Groth16;
+    /// # use ark_r1cs_std::prelude::Boolean;
+    /// # use zk_callbacks::impls::hash::Poseidon;
+    /// # use ark_snark::SNARK;
+    /// # use ark_ff::ToConstraintField;
+    /// # use zk_callbacks::impls::centralized::ds::sigstore::UOVObjStore;
+    /// # use crate::zk_callbacks::generic::bulletin::JoinableBulletin;
+    /// # use zk_callbacks::generic::interaction::{Disclosure, generate_keys_for_disclosed_statement_in};
+    /// # type Groth = Groth16<E>;
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     pub num_visits: Fr,
+    ///     pub tier: u8,
+    /// }
+    ///
+    /// fn predicate<'a, 'b>(user: &'a UserVar<Fr, Data>, _com: &'b FpVar<Fr>, _pub_args: (), _priv_args: ()) -> Result<Boolean<Fr>, SynthesisError> {
+    ///     user.data.num_visits.is_eq(&FpVar::Constant(Fr::from(1)))
+    /// }
+    ///
+    /// fn main () {
+    ///
+    ///     let mut rng = thread_rng();
+    ///
+    ///     let mut obj_store = UOVObjStore::new(&mut rng);
+    ///
+    ///     let disclosure = Disclosure::new().reveal(DataFields::tier());
+    ///
+    ///     let (pk, vk) = generate_keys_for_disclosed_statement_in::<Fr, Poseidon<2>, Data, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, Arc::new(predicate), disclosure.clone(), Some(obj_store.get_pubkey()), ());
+    ///
+    ///     let u = User::create(Data { num_visits: Fr::from(1), tier: 2 }, &mut rng);
+    ///
+    ///     <UOVObjStore<Fr> as JoinableBulletin<Fr, Data>>::join_bul(&mut obj_store, u.commit::<Poseidon<2>>(), ()).unwrap();
+    ///
+    ///     let (proof, revealed) = u.prove_disclosed_statement_and_in::<Poseidon<2>, _, _, _, _, Groth, UOVObjStore<Fr>>(&mut rng, Arc::new(predicate), disclosure.clone(), &pk, (obj_store.get_signature_of(&u.commit::<Poseidon<2>>()).unwrap(), obj_store.get_pubkey()), true, (), ()).unwrap();
+    ///
+    ///     let mut pub_inputs = vec![];
+    ///
+    ///     pub_inputs.extend_from_slice(&().to_field_elements().unwrap());
+    ///     pub_inputs.extend_from_slice(&revealed.to_field_elements().unwrap());
+    ///
+    ///     assert!(Groth::verify(&vk, &pub_inputs, &proof).unwrap());
+    ///
+    ///     assert_eq!(disclosure.get::<u8>(&revealed, DataFields::tier()), Some(2));
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_disclosed_statement_and_in<
+        H: FieldHash<F>,
+        PubArgs: Clone,
+        PubArgsVar: AllocVar<PubArgs, F> + Clone,
+        PrivArgs: Clone,
+        PrivArgsVar: AllocVar<PrivArgs, F> + Clone,
+        Snark: SNARK<F, Error = SynthesisError>,
+        Bul: PublicUserBul<F, U>,
+    >(
+        &self,
+        rng: &mut (impl CryptoRng + RngCore),
+        predicate: SingularPredicate<F, UserVar<F, U>, ComVar<F>, PubArgsVar, PrivArgsVar>,
+        disclosure: Disclosure<F, U>,
+        pk: &Snark::ProvingKey,
+        memb_data: (Bul::MembershipWitness, Bul::MembershipPub),
+        is_memb_data_const: bool,
+        pub_args: PubArgs,
+        priv_args: PrivArgs,
+    ) -> Result<(Snark::Proof, Revealed<F>), UserError> {
+        let revealed = disclosure.values(self);
         let ppcirc: ProvePredInCircuit<F, H, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, Bul> =
             ProvePredInCircuit {
                 priv_user: self.clone(),
@@ -2441,18 +4163,19 @@ where
                 pub_extra_membership_data: memb_data.1,
                 bul_memb_is_const: is_memb_data_const,
                 pub_args,
+                pub_revealed: revealed.clone(),
                 associated_method: predicate,
+                disclosure,
 
                 _phantom_hash: core::marker::PhantomData,
             };
 
-        let new_cs = ConstraintSystem::<F>::new_ref();
-        ppcirc.clone().generate_constraints(new_cs.clone())?;
-        new_cs.is_satisfied()?;
+        #[cfg(debug_assertions)]
+        ensure_satisfied(ppcirc.clone(), false)?;
 
         let proof = Snark::prove(pk, ppcirc, rng)?;
 
-        Ok(proof)
+        Ok((proof, revealed))
     }
 
     /// Get the constraint system for proving a statement and membership on a user.
@@ -2474,7 +4197,7 @@ where
         is_memb_data_const: bool,
         pub_args: PubArgs,
         priv_args: PrivArgs,
-    ) -> Result<ConstraintSystemRef<F>, SynthesisError> {
+    ) -> Result<ConstraintSystemRef<F>, UserError> {
         let ppcirc: ProvePredInCircuit<F, H, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, Bul> =
             ProvePredInCircuit {
                 priv_user: self.clone(),
@@ -2483,7 +4206,9 @@ where
                 pub_extra_membership_data: memb_data.1,
                 bul_memb_is_const: is_memb_data_const,
                 pub_args,
+                pub_revealed: Revealed::default(),
                 associated_method: predicate,
+                disclosure: Disclosure::default(),
 
                 _phantom_hash: core::marker::PhantomData,
             };
@@ -2513,7 +4238,7 @@ where
         priv_args: PrivArgs,
     ) -> Result<
         ProvePredInCircuit<F, H, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, Bul>,
-        SynthesisError,
+        UserError,
     > {
         let ppcirc: ProvePredInCircuit<F, H, U, PubArgs, PubArgsVar, PrivArgs, PrivArgsVar, Bul> =
             ProvePredInCircuit {
@@ -2523,7 +4248,9 @@ where
                 pub_extra_membership_data: memb_data.1,
                 bul_memb_is_const: is_memb_data_const,
                 pub_args,
+                pub_revealed: Revealed::default(),
                 associated_method: predicate,
+                disclosure: Disclosure::default(),
 
                 _phantom_hash: core::marker::PhantomData,
             };
@@ -2541,7 +4268,7 @@ where
         Crypto: AECipherSigZK<F, CBArgs, AV = CBArgsVar> + PartialEq + Eq + Default,
         Bul: PublicUserBul<F, U>,
         CBul: PublicCallbackBul<F, CBArgs, Crypto> + Clone,
-        H: FieldHash<F>,
+        H: FieldHash<F> + 'static,
         Snark: SNARK<F, Error = SynthesisError>,
         const NUMSCANS: usize,
     >(
@@ -2556,17 +4283,20 @@ where
         cb_methods: Vec<Callback<F, U, CBArgs, CBArgsVar>>,
         pk: &Snark::ProvingKey,
         num_folds: usize,
-    ) -> (
+    ) -> Result<
         (
-            PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>,
-            Bul::MembershipPub,
+            (
+                PubScanArgs<F, U, CBArgs, CBArgsVar, Crypto, CBul, NUMSCANS>,
+                Bul::MembershipPub,
+            ),
+            Vec<F>,
+            Vec<FoldInput<F, U, CBArgs, CBArgsVar, Crypto, Bul, CBul, NUMSCANS>>,
+            (Com<F>, Nul<F>, Snark::Proof),
         ),
-        Vec<F>,
-        Vec<FoldInput<F, U, CBArgs, CBArgsVar, Crypto, Bul, CBul, NUMSCANS>>,
-        (Com<F>, Nul<F>, Snark::Proof),
-    )
+        UserError,
+    >
     where
-        U: Default,
+        U: Default + 'static,
         CBul::MembershipPub: Default,
         CBul::NonMembershipPub: Default,
         CBul::MembershipWitness: Default,
@@ -2576,6 +4306,7 @@ where
         assert!(is_memb_nmemb_const == (true, true));
         assert!(num_folds > 0);
         use crate::generic::{fold::rerandomize_predicate, scan::scan_method};
+        use std::sync::Arc;
 
         let mut v: Vec<FoldInput<F, U, CBArgs, CBArgsVar, Crypto, Bul, CBul, NUMSCANS>> = vec![];
 
@@ -2587,7 +4318,7 @@ where
 
         let old_nul = u.zk_fields.nul.clone();
 
-        let proof = self.prove_statement_and_in::<H, Rerand<F>, RerandVar<F>, ComRand<F>, ComRandVar<F>, Snark, Bul>(rng, rerandomize_predicate::<H, F, U>, pk, (bul_data.1.clone(), bul_data.0.clone()), is_bul_data_const, Rerand { com: commit, nul: u.zk_fields.nul.clone() } , u.zk_fields.com_rand).unwrap();
+        let proof = self.prove_statement_and_in::<H, Rerand<F>, RerandVar<F>, ComRand<F>, ComRandVar<F>, Snark, Bul>(rng, Arc::new(rerandomize_predicate::<H, F, U>), pk, (bul_data.1.clone(), bul_data.0.clone()), is_bul_data_const, Rerand { com: commit, nul: u.zk_fields.nul.clone() } , u.zk_fields.com_rand)?;
 
         let mut ps_ret = PubScanArgs {
             memb_pub: core::array::from_fn(|_| CBul::MembershipPub::default()),
@@ -2605,7 +4336,7 @@ where
                 is_memb_nmemb_const,
                 cur_time,
                 cb_methods.clone(),
-            );
+            )?;
 
             ps_ret = ps.clone();
 
@@ -2615,8 +4346,8 @@ where
                 prs.clone(),
             );
 
-            new_u.zk_fields.nul = rng.r#gen();
             new_u.zk_fields.com_rand = rng.r#gen();
+            new_u.zk_fields.nul = derive_nul::<F, H>(new_u.zk_fields.com_rand);
 
             v.push(FoldInput {
                 user: u.clone(),
@@ -2631,20 +4362,21 @@ where
 
         *self = u;
 
-        (
+        Ok((
             (ps_ret, bul_data.0),
             [commit, cur_time].to_vec(),
             v,
             (commit, old_nul, proof),
-        )
+        ))
     }
 }
 
 impl<F: PrimeField + Absorb, U: UserData<F>> User<F, U> {
     /// Produce a commitment to the user object.
     ///
-    /// Uses the hash `H` to produce a commitment to the user object. Note that the nonce is
-    /// already stored within the user.
+    /// Uses the hash `H` to produce a commitment to the user object in the
+    /// [`HashDomain::UserCom`](`crate::crypto::hash::HashDomain::UserCom`) domain. Note that the
+    /// nonce is already stored within the user.
     ///
     /// # Example
     ///
@@ -2667,20 +4399,72 @@ impl<F: PrimeField + Absorb, U: UserData<F>> User<F, U> {
     /// }
     /// ```
     pub fn commit<H: FieldHash<F>>(&self) -> Com<F> {
-        let ser_data = self.data.serialize_elements();
-        let ser_fields = self.zk_fields.serialize();
-        let full_dat = [ser_data.as_slice(), ser_fields.as_slice()].concat();
-        H::hash(&full_dat)
+        self.commit_with::<HashCom<H>>(&())
     }
 
     /// Produce a commitment of `user_var` in-circuit.
     pub fn commit_in_zk<H: FieldHash<F>>(
         user_var: UserVar<F, U>,
     ) -> Result<ComVar<F>, SynthesisError> {
+        Self::commit_with_in_zk::<HashCom<H>>(&(), user_var)
+    }
+
+    /// Produce a commitment to the user object with the commitment scheme `C`.
+    ///
+    /// The user data and the zero knowledge fields are committed to as a single vector, with the
+    /// user's commitment randomness. With [`HashCom`], this is exactly [`User::commit`].
+    ///
+    /// Note that bulletins and interactions always use [`User::commit`]. Commitments under other
+    /// schemes are separate from the user's bulletin entry, and may only be used with
+    /// [`User::prove_committed_statement`].
+    ///
+    /// # Example (Homomorphic Update)
+    ///
+    /// ```rust
+    /// # use zk_callbacks::zk_object;
+    /// # use ark_bn254::Fr;
+    /// # use rand::thread_rng;
+    /// # use zk_callbacks::generic::user::User;
+    /// # use zk_callbacks::crypto::com::HomomorphicCommitment;
+    /// # use zk_callbacks::impls::pedersen::{GrumpkinPedersen, PedersenParams};
+    /// #[zk_object(Fr)]
+    /// #[derive(Default)]
+    /// struct Data {
+    ///     karma: Fr,
+    /// }
+    ///
+    /// fn main () {
+    ///     let mut rng = thread_rng();
+    ///     let params = PedersenParams::new(8);
+    ///
+    ///     let mut u = User::create(Data { karma: Fr::from(3) }, &mut rng);
+    ///     let com = u.commit_with::<GrumpkinPedersen>(&params);
+    ///
+    ///     // The service adds one karma to the commitment, without knowing the user.
+    ///     let new_com = GrumpkinPedersen::add_to(&params, &com, 0, Fr::from(1)).unwrap();
+    ///
+    ///     u.data.karma += Fr::from(1);
+    ///     assert_eq!(new_com, u.commit_with::<GrumpkinPedersen>(&params));
+    /// }
+    /// ```
+    pub fn commit_with<C: UserCommitment<F>>(&self, params: &C::Params) -> C::Com {
+        let ser_data = self.data.serialize_elements();
+        let ser_fields = self.zk_fields.serialize();
+        let full_dat = [ser_data.as_slice(), ser_fields.as_slice()].concat();
+        C::commit(params, &full_dat, self.zk_fields.com_rand)
+    }
+
+    /// Produce a commitment of `user_var` in-circuit with the commitment scheme `C`.
+    ///
+    /// See [`User::commit_with`] for more details.
+    pub fn commit_with_in_zk<C: UserCommitment<F>>(
+        params: &C::ParamsVar,
+        user_var: UserVar<F, U>,
+    ) -> Result<C::ComVar, SynthesisError> {
         let ser_data = U::serialize_in_zk(user_var.data)?;
         let ser_fields = user_var.zk_fields.serialize()?;
         let full_dat = [ser_data.as_slice(), ser_fields.as_slice()].concat();
 
-        H::hash_in_zk(&full_dat)
+        C::commit_in_zk(params, &full_dat, &user_var.zk_fields.com_rand)
     }
 }
@@ -0,0 +1,230 @@
+use crate::{
+    crypto::keystore::{KeystoreError, KeystoreParams, decrypt_key, encrypt_key},
+    generic::user::{User, UserData},
+};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+use std::{fs, io::Write, path::Path};
+
+/// The default number of previous user states kept by a [`Wallet`].
+pub const DEFAULT_MAX_HISTORY: usize = 16;
+
+/// A wallet holding a user, along with a history of its previous states.
+///
+/// A user must be kept secret (it contains the nullifier and commitment randomness) and must not
+/// be lost (the user can not be recovered from the bulletin). A wallet stores the user encrypted
+/// at rest under a password, in the format of [`encrypt_key`].
+///
+/// Updates to the user should go through [`Wallet::transact`]: the update is made on a copy of
+/// the user, and is only committed to the wallet once it succeeds, for example once the service
+/// has accepted the interaction. If a committed state turns out to be unusable (for example if
+/// the bulletin later rejects it), [`Wallet::restore_previous`] recovers the previous state.
+///
+/// # Example
+/// ```rust
+/// # use ark_bn254::Fr;
+/// # use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+/// # use rand::thread_rng;
+/// # use zk_callbacks::zk_object;
+/// # use zk_callbacks::crypto::keystore::KeystoreParams;
+/// # use zk_callbacks::generic::user::User;
+/// # use zk_callbacks::generic::wallet::Wallet;
+/// #[zk_object(Fr)]
+/// #[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+/// struct Data {
+///     pub karma: Fr,
+/// }
+///
+/// let mut rng = thread_rng();
+/// let mut wallet = Wallet::new(User::create(Data { karma: Fr::from(0) }, &mut rng));
+///
+/// // A rejected update is never committed.
+/// let rejected: Result<(), &str> = wallet.transact(|u| {
+///     u.data.karma = Fr::from(1);
+///     Err("rejected by the service")
+/// });
+/// assert!(rejected.is_err());
+/// assert_eq!(wallet.user().data.karma, Fr::from(0));
+///
+/// // An accepted update is committed, and the old state is kept.
+/// wallet.transact(|u| {
+///     u.data.karma = Fr::from(2);
+///     Ok::<(), ()>(())
+/// }).unwrap();
+/// assert_eq!(wallet.user().data.karma, Fr::from(2));
+/// assert_eq!(wallet.history().len(), 1);
+///
+/// // The wallet is encrypted at rest.
+/// let params = KeystoreParams { m_cost: 8, t_cost: 1, p_cost: 1 };
+/// let bytes = wallet.encrypt(b"hunter2", params, &mut rng).unwrap();
+/// let opened: Wallet<Fr, Data> = Wallet::decrypt(&bytes, b"hunter2").unwrap();
+/// assert_eq!(opened, wallet);
+/// assert!(Wallet::<Fr, Data>::decrypt(&bytes, b"hunter3").is_err());
+///
+/// let path = std::env::temp_dir().join("zk_callbacks_wallet_example");
+/// wallet.save(&path, b"hunter2", params, &mut rng).unwrap();
+/// assert_eq!(Wallet::<Fr, Data>::load(&path, b"hunter2").unwrap(), wallet);
+/// # std::fs::remove_file(&path).unwrap();
+///
+/// // The previous state may be recovered.
+/// wallet.restore_previous();
+/// assert_eq!(wallet.user().data.karma, Fr::from(0));
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Wallet<F: PrimeField + Absorb, U: UserData<F>> {
+    current: User<F, U>,
+    history: Vec<User<F, U>>,
+    max_history: usize,
+}
+
+/// Only the size of the history is shown, as the users are secret.
+impl<F: PrimeField + Absorb, U: UserData<F>> std::fmt::Debug for Wallet<F, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wallet")
+            .field("history", &self.history.len())
+            .field("max_history", &self.max_history)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F>> Wallet<F, U> {
+    /// Create a wallet holding a user, with no history.
+    pub fn new(user: User<F, U>) -> Self {
+        Self {
+            current: user,
+            history: vec![],
+            max_history: DEFAULT_MAX_HISTORY,
+        }
+    }
+
+    /// Set the maximum number of previous states kept, dropping the oldest states beyond it.
+    pub fn with_max_history(mut self, max_history: usize) -> Self {
+        self.max_history = max_history;
+        self.trim_history();
+        self
+    }
+
+    /// The current user.
+    pub fn user(&self) -> &User<F, U> {
+        &self.current
+    }
+
+    /// The previous states of the user, from oldest to most recent.
+    pub fn history(&self) -> &[User<F, U>] {
+        &self.history
+    }
+
+    /// Commit a new state of the user, keeping the current state in the history.
+    ///
+    /// Prefer [`Wallet::transact`], which only commits the new state once an update succeeds.
+    pub fn commit(&mut self, user: User<F, U>) {
+        let old = std::mem::replace(&mut self.current, user);
+        self.history.push(old);
+        self.trim_history();
+    }
+
+    /// Update the user atomically.
+    ///
+    /// The function `f` is given a copy of the current user to update, for example by calling
+    /// [`User::interact`] and submitting the interaction to a service. If `f` succeeds, the
+    /// updated user is committed; otherwise the wallet is left unchanged.
+    pub fn transact<T, E>(
+        &mut self,
+        f: impl FnOnce(&mut User<F, U>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let mut user = self.current.clone();
+        let out = f(&mut user)?;
+        self.commit(user);
+        Ok(out)
+    }
+
+    /// Discard the current user, and restore the most recent previous state.
+    ///
+    /// Returns false (and does nothing) if there is no previous state.
+    pub fn restore_previous(&mut self) -> bool {
+        match self.history.pop() {
+            Some(user) => {
+                self.current = user;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn trim_history(&mut self) {
+        let excess = self.history.len().saturating_sub(self.max_history);
+        self.history.drain(..excess);
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F> + CanonicalSerialize> Wallet<F, U> {
+    /// Encrypt the wallet under a password.
+    ///
+    /// The user and its history are serialized, and encrypted as in [`encrypt_key`].
+    pub fn encrypt(
+        &self,
+        password: &[u8],
+        params: KeystoreParams,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Vec<u8>, KeystoreError> {
+        let contents = (
+            self.current.clone(),
+            self.history.clone(),
+            self.max_history as u64,
+        );
+        encrypt_key(&contents, password, params, rng)
+    }
+
+    /// Encrypt the wallet under a password, and write it to a file.
+    ///
+    /// The wallet is first written to a temporary file next to `path` and flushed to disk, which
+    /// then replaces `path`, so an interrupted write never leaves a partially written wallet. On
+    /// Unix, the parent directory is also flushed, so the replacement itself survives a crash.
+    pub fn save(
+        &self,
+        path: impl AsRef<Path>,
+        password: &[u8],
+        params: KeystoreParams,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<(), KeystoreError> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(&self.encrypt(password, params, rng)?)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, path)?;
+
+        #[cfg(unix)]
+        {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            fs::File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField + Absorb, U: UserData<F> + CanonicalDeserialize> Wallet<F, U> {
+    /// Decrypt a wallet encrypted with [`Wallet::encrypt`].
+    ///
+    /// Fails with [`KeystoreError::Decryption`] if the password is incorrect.
+    pub fn decrypt(wallet: &[u8], password: &[u8]) -> Result<Self, KeystoreError> {
+        let (current, history, max_history) =
+            decrypt_key::<(User<F, U>, Vec<User<F, U>>, u64)>(wallet, password)?;
+        Ok(Self {
+            current,
+            history,
+            max_history: max_history as usize,
+        })
+    }
+
+    /// Read a wallet written with [`Wallet::save`], and decrypt it with a password.
+    pub fn load(path: impl AsRef<Path>, password: &[u8]) -> Result<Self, KeystoreError> {
+        Self::decrypt(&fs::read(path)?, password)
+    }
+}
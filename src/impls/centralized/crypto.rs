@@ -1,11 +1,15 @@
-use crate::crypto::{
-    enc::{AECipherSigZK, CPACipher},
-    rr::{RRSigner, RRVerifier},
+use crate::{
+    crypto::{
+        enc::{AECipherSigZK, CPACipher},
+        rr::{RRSigner, RRVerifier},
+    },
+    generic::callbacks::{is_release_time, release_time},
 };
 use ark_ff::PrimeField;
 use ark_r1cs_std::{
+    eq::EqGadget,
     fields::fp::FpVar,
-    prelude::{AllocVar, AllocationMode},
+    prelude::{AllocVar, AllocationMode, Boolean},
 };
 use ark_relations::{
     ns,
@@ -216,6 +220,22 @@ where
     type EncKeyVar = OTPEncKeyVar<F>;
 
     type Rand = F;
+
+    fn release_ct(tag: F) -> F {
+        tag
+    }
+
+    fn is_release(tag: F, ct: &F, _time: F) -> bool {
+        *ct == tag
+    }
+
+    fn is_release_in_zk(
+        tag: &FpVar<F>,
+        ct: &FpVar<F>,
+        _time: &FpVar<F>,
+    ) -> Result<Boolean<F>, SynthesisError> {
+        ct.is_eq(tag)
+    }
 }
 
 /// A cipher which does no encryption. This is for centralized settings when encryption is not
@@ -383,4 +403,22 @@ where
     type EncKeyVar = NoEnc<F, A, AVar>;
 
     type Rand = F;
+
+    // The arguments are posted in the clear, so the ciphertext can not carry the release tag. As
+    // tickets are not signed either, a release is told apart by its post time alone.
+    fn release_ct(_tag: F) -> A {
+        A::default()
+    }
+
+    fn is_release(_tag: F, _ct: &A, time: F) -> bool {
+        is_release_time(&time)
+    }
+
+    fn is_release_in_zk(
+        _tag: &FpVar<F>,
+        _ct: &AVar,
+        time: &FpVar<F>,
+    ) -> Result<Boolean<F>, SynthesisError> {
+        time.is_eq(&FpVar::Constant(release_time()))
+    }
 }
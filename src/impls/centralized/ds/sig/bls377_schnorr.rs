@@ -33,6 +33,7 @@ use ark_relations::{
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::Rng;
 use std::borrow::Borrow;
+use zeroize::Zeroize;
 
 type BlsFrV = FpVar<BlsFr>;
 
@@ -51,9 +52,22 @@ where
 type EProjFr = <EProj as PrimeGroup>::ScalarField;
 
 /// A private twisted edwards BLS Schnorr signing key.
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Default, Debug)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Default)]
 pub struct BLS377SchnorrPrivkey(EProjFr);
 
+impl std::fmt::Debug for BLS377SchnorrPrivkey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BLS377SchnorrPrivkey")
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for BLS377SchnorrPrivkey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// A public twisted edwards BLS Schnorr verification key.
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Default, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BLS377SchnorrPubkey(EProj);
@@ -0,0 +1,744 @@
+//! Implements EdDSA-Poseidon signatures over Baby Jubjub, compatible with circomlib.
+//!
+//! Signatures produced here verify under circomlib's `EdDSAPoseidonVerifier` template (and
+//! `verifyPoseidon` in circomlibjs), and vice versa. Both use the circom form of Baby Jubjub
+//! (`a = 168700`, `d = 168696`) with the generator `Base8`, and the circom Poseidon hash on the
+//! five elements `(R8.x, R8.y, A.x, A.y, msg)`.
+//!
+//! Key derivation and nonces follow circomlibjs as well, using Blake-512: a 32 byte seed produces
+//! the same key and the same (deterministic) signatures as `prv2pub` and `signPoseidon`.
+//!
+//! # Membership signatures
+//!
+//! A [`SigObjStore`](crate::impls::centralized::ds::sigstore::SigObjStore) does not sign user
+//! commitments directly, but the membership message
+//! [`SigObjStore::memb_message`](crate::impls::centralized::ds::sigstore::SigObjStore::memb_message):
+//! the commitment hashed with [`Poseidon<2>`](crate::impls::hash::Poseidon) in the
+//! [`ObjectMembership`](crate::crypto::hash::HashDomain::ObjectMembership) domain. This message is
+//! part of the wire format, so an external signer issuing membership signatures must sign
+//! `memb_message(com)` rather than `com`. Note that this hash is this crate's Poseidon, not the
+//! circomlib one, so the signer must compute it with this crate (or a port of it).
+//!
+//! For example, the commitment `1` has the membership message
+//! `5720288849464820317721216840131651062329576676078436087606336270803369004218`, which the key
+//! from the seed `[0, 1, ..., 9, 0, 1, ...]` signs as
+//!
+//! ```text
+//! R8.x = 17180484372720817468100826288749091635105180888158772473905061011909355420147
+//! R8.y = 1967542752276547236653714462176320328238856932831647454729971580101361448049
+//! S    = 1607325566480414756104251015250419867748539799852744371744424355556912719747
+//! ```
+
+use crate::impls::centralized::ds::sig::{Privkey, Pubkey, Signature};
+use ark_bn254::Fr as F;
+use ark_ec::{
+    AffineRepr, CurveConfig, CurveGroup, PrimeGroup,
+    twisted_edwards::{Affine, MontCurveConfig, Projective, TECurveConfig},
+};
+use ark_ed_on_bn254::Fr as BabyJubJubFr;
+use ark_ff::{AdditiveGroup, BigInteger, Field, MontFp, PrimeField};
+use ark_r1cs_std::{
+    R1CSVar,
+    alloc::{AllocVar, AllocationMode},
+    boolean::Boolean,
+    convert::{ToBitsGadget, ToConstraintFieldGadget},
+    eq::EqGadget,
+    fields::{FieldVar, fp::FpVar},
+    groups::{CurveVar, curves::twisted_edwards::AffineVar},
+    select::CondSelectGadget,
+};
+use ark_relations::{
+    ns,
+    r1cs::{ConstraintSystemRef, Namespace, SynthesisError},
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use circom_poseidon::get_poseidon_params;
+use rand::{CryptoRng, RngCore};
+use std::borrow::Borrow;
+use zeroize::Zeroize;
+
+/// The initial chaining value of Blake-512 (that of SHA-512).
+const BLAKE512_IV: [u64; 8] = [
+    0x6A09E667F3BCC908,
+    0xBB67AE8584CAA73B,
+    0x3C6EF372FE94F82B,
+    0xA54FF53A5F1D36F1,
+    0x510E527FADE682D1,
+    0x9B05688C2B3E6C1F,
+    0x1F83D9ABFB41BD6B,
+    0x5BE0CD19137E2179,
+];
+
+/// The round constants of Blake-512 (the leading digits of pi).
+const BLAKE512_C: [u64; 16] = [
+    0x243F6A8885A308D3,
+    0x13198A2E03707344,
+    0xA4093822299F31D0,
+    0x082EFA98EC4E6C89,
+    0x452821E638D01377,
+    0xBE5466CF34E90C6C,
+    0xC0AC29B7C97C50DD,
+    0x3F84D5B5B5470917,
+    0x9216D5D98979FB1B,
+    0xD1310BA698DFB5AC,
+    0x2FFD72DBD01ADFB7,
+    0xB8E1AFED6A267E96,
+    0xBA7C9045F12C7F99,
+    0x24A19947B3916CF7,
+    0x0801F2E2858EFC16,
+    0x636920D871574E69,
+];
+
+/// The message word permutations of Blake-512.
+const BLAKE512_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Compresses a single 128 byte block into the chaining value, with the number of message bits
+/// hashed so far as the counter.
+fn blake512_compress(h: &mut [u64; 8], block: &[u8], counter: u128) {
+    let m: [u64; 16] =
+        core::array::from_fn(|i| u64::from_be_bytes(block[8 * i..8 * i + 8].try_into().unwrap()));
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE512_C[..8]);
+    v[12] ^= counter as u64;
+    v[13] ^= counter as u64;
+    v[14] ^= (counter >> 64) as u64;
+    v[15] ^= (counter >> 64) as u64;
+
+    const G: [[usize; 4]; 8] = [
+        [0, 4, 8, 12],
+        [1, 5, 9, 13],
+        [2, 6, 10, 14],
+        [3, 7, 11, 15],
+        [0, 5, 10, 15],
+        [1, 6, 11, 12],
+        [2, 7, 8, 13],
+        [3, 4, 9, 14],
+    ];
+    for round in 0..16 {
+        let sigma = &BLAKE512_SIGMA[round % 10];
+        for (i, &[a, b, c, d]) in G.iter().enumerate() {
+            let (x, y) = (sigma[2 * i], sigma[2 * i + 1]);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[x] ^ BLAKE512_C[y]);
+            v[d] = (v[d] ^ v[a]).rotate_right(32);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(25);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[y] ^ BLAKE512_C[x]);
+            v[d] = (v[d] ^ v[a]).rotate_right(16);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(11);
+        }
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// The Blake-512 hash (with an all-zero salt), as used by circomlibjs for EdDSA.
+///
+/// Note this is the original SHA-3 finalist Blake, and not Blake2b.
+fn blake512(msg: &[u8]) -> [u8; 64] {
+    let bits = 8 * msg.len() as u128;
+
+    // Pad with a 1 bit, zeros, a 1 bit and the 128 bit length, to a multiple of 128 bytes.
+    let mut data = msg.to_vec();
+    data.push(0x80);
+    while data.len() % 128 != 112 {
+        data.push(0);
+    }
+    let last = data.len() - 1;
+    data[last] |= 0x01;
+    data.extend_from_slice(&bits.to_be_bytes());
+
+    let mut h = BLAKE512_IV;
+    for (i, block) in data.chunks(128).enumerate() {
+        // Blocks with no message bits are compressed with a zero counter.
+        let start = 1024 * i as u128;
+        let counter = if start < bits {
+            bits.min(start + 1024)
+        } else {
+            0
+        };
+        blake512_compress(&mut h, block, counter);
+    }
+    data.zeroize();
+
+    let mut out = [0u8; 64];
+    for (o, w) in out.chunks_mut(8).zip(h) {
+        o.copy_from_slice(&w.to_be_bytes());
+    }
+    out
+}
+
+/// The circom form of the Baby Jubjub curve, defined over the BN254 scalar field.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct BabyJubJubConfig;
+
+impl CurveConfig for BabyJubJubConfig {
+    type BaseField = F;
+    type ScalarField = BabyJubJubFr;
+
+    const COFACTOR: &'static [u64] = &[8];
+
+    const COFACTOR_INV: BabyJubJubFr =
+        MontFp!("2394026564107420727433200628387514462817212225638746351800188703329891451411");
+}
+
+impl TECurveConfig for BabyJubJubConfig {
+    const COEFF_A: F = MontFp!("168700");
+
+    const COEFF_D: F = MontFp!("168696");
+
+    // This is `Base8` in circomlib.
+    const GENERATOR: Affine<Self> = Affine::new_unchecked(
+        MontFp!("5299619240641551281634865583518297030282874472190772894086521144482721001553"),
+        MontFp!("16950150798460657717958625567821834550301663161624707787222815936182638968203"),
+    );
+
+    type MontCurveConfig = BabyJubJubConfig;
+}
+
+impl MontCurveConfig for BabyJubJubConfig {
+    const COEFF_A: F = MontFp!("168698");
+
+    const COEFF_B: F = MontFp!("1");
+
+    type TECurveConfig = BabyJubJubConfig;
+}
+
+/// The circom Baby Jubjub curve.
+pub type BabyJubJub = Projective<BabyJubJubConfig>;
+
+type BabyJubJubVar = AffineVar<BabyJubJubConfig, FpVar<F>>;
+
+/// The circom Poseidon hash, which outputs the first element of the state.
+fn circom_poseidon(inputs: &[F]) -> F {
+    let params = get_poseidon_params::<F>(inputs.len());
+    let half_full = params.full_rounds / 2;
+
+    let mut state = vec![F::ZERO];
+    state.extend_from_slice(inputs);
+
+    for r in 0..(params.full_rounds + params.partial_rounds) {
+        for (s, c) in state.iter_mut().zip(&params.ark[r]) {
+            *s += c;
+        }
+        if r < half_full || r >= half_full + params.partial_rounds {
+            for s in state.iter_mut() {
+                *s = s.pow([params.alpha]);
+            }
+        } else {
+            state[0] = state[0].pow([params.alpha]);
+        }
+        state = params
+            .mds
+            .iter()
+            .map(|row| row.iter().zip(&state).map(|(m, s)| *m * s).sum())
+            .collect();
+    }
+
+    state[0]
+}
+
+/// The circom Poseidon hash in-circuit.
+fn circom_poseidon_zk(inputs: &[FpVar<F>]) -> Result<FpVar<F>, SynthesisError> {
+    let params = get_poseidon_params::<F>(inputs.len());
+    let half_full = params.full_rounds / 2;
+
+    let mut state = vec![FpVar::zero()];
+    state.extend_from_slice(inputs);
+
+    for r in 0..(params.full_rounds + params.partial_rounds) {
+        for (s, c) in state.iter_mut().zip(&params.ark[r]) {
+            *s += *c;
+        }
+        if r < half_full || r >= half_full + params.partial_rounds {
+            for s in state.iter_mut() {
+                *s = s.pow_by_constant([params.alpha])?;
+            }
+        } else {
+            state[0] = state[0].pow_by_constant([params.alpha])?;
+        }
+        state = params
+            .mds
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&state)
+                    .fold(FpVar::zero(), |acc, (m, s)| acc + s * *m)
+            })
+            .collect();
+    }
+
+    Ok(state[0].clone())
+}
+
+/// Reduces a base field element into a scalar, as an integer modulo the subgroup order.
+fn fq_to_fr(x: F) -> BabyJubJubFr {
+    BabyJubJubFr::from_le_bytes_mod_order(&x.into_bigint().to_bytes_le())
+}
+
+/// Lifts a scalar into the base field (which is larger, so this is injective).
+fn fr_to_fq(x: BabyJubJubFr) -> F {
+    F::from_le_bytes_mod_order(&x.into_bigint().to_bytes_le())
+}
+
+/// A compressed EdDSA-Poseidon private key. This is a 32 byte seed, as in circomlibjs.
+#[derive(Clone, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EdDSACompressedPrivkey([u8; 32]);
+
+impl Drop for EdDSACompressedPrivkey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// An EdDSA-Poseidon private signing key.
+#[derive(Clone, Default, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EdDSAPrivkey {
+    /// The secret scalar; the public key is `sk * Base8`.
+    sk: BabyJubJubFr,
+    /// The secret prefix used to derive nonces.
+    prefix: [u8; 32],
+}
+
+impl Drop for EdDSAPrivkey {
+    fn drop(&mut self) {
+        self.sk.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+/// An EdDSA-Poseidon public verification key.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, CanonicalSerialize, CanonicalDeserialize)]
+pub struct EdDSAPubkey(Affine<BabyJubJubConfig>);
+
+/// An EdDSA-Poseidon public verification key in-circuit.
+#[derive(Clone)]
+pub struct EdDSAPubkeyVar(BabyJubJubVar);
+
+impl Default for EdDSAPubkeyVar {
+    fn default() -> Self {
+        Self(BabyJubJubVar::new(
+            FpVar::Constant(F::ZERO),
+            FpVar::Constant(F::ONE),
+        ))
+    }
+}
+
+/// An EdDSA-Poseidon signature. This is the pair `(R8, S)` from circomlib.
+#[derive(Debug, Clone, Default, CanonicalSerialize, CanonicalDeserialize, PartialEq, Eq)]
+pub struct EdDSASignature {
+    /// The nonce commitment.
+    r8: Affine<BabyJubJubConfig>,
+    /// The response.
+    s: BabyJubJubFr,
+}
+
+/// An EdDSA-Poseidon signature in-circuit.
+#[derive(Clone)]
+pub struct EdDSASignatureVar {
+    /// The nonce commitment.
+    r8: BabyJubJubVar,
+    /// The response, lifted into the base field.
+    s: FpVar<F>,
+}
+
+impl AllocVar<EdDSASignature, F> for EdDSASignatureVar {
+    fn new_variable<T: Borrow<EdDSASignature>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        let res = f();
+        res.and_then(|sig| {
+            let sig = sig.borrow();
+
+            let r8 = <BabyJubJubVar as AllocVar<Affine<BabyJubJubConfig>, _>>::new_variable(
+                ns!(cs, "r8"),
+                || Ok(sig.r8),
+                mode,
+            )?;
+            let s = FpVar::new_variable(ns!(cs, "s"), || Ok(fr_to_fq(sig.s)), mode)?;
+
+            Ok(EdDSASignatureVar { r8, s })
+        })
+    }
+}
+
+impl EdDSAPrivkey {
+    /// Derives the key as in circomlibjs: the first half of `Blake512(seed)` is pruned into the
+    /// scalar `s`, with secret key `s >> 3`, and the second half is the nonce prefix.
+    fn from_seed(seed: &EdDSACompressedPrivkey) -> Self {
+        let mut h = blake512(&seed.0);
+        h[0] &= 0xF8;
+        h[31] &= 0x7F;
+        h[31] |= 0x40;
+        // The low bits of `s` are cleared, so `s >> 3` is `s / 8`.
+        let sk = BabyJubJubFr::from_le_bytes_mod_order(&h[..32]) * BabyJubJubConfig::COFACTOR_INV;
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&h[32..]);
+        h.zeroize();
+        Self { sk, prefix }
+    }
+
+    /// Signs `msg` deterministically. The nonce is derived from the secret prefix and the message.
+    fn sign(&self, msg: &F) -> EdDSASignature {
+        let pubkey = self.get_pubkey();
+
+        let mut preimage = self.prefix.to_vec();
+        preimage.extend_from_slice(&msg.into_bigint().to_bytes_le());
+        let r = BabyJubJubFr::from_le_bytes_mod_order(&blake512(&preimage));
+        preimage.zeroize();
+
+        let r8 = (BabyJubJub::generator() * r).into_affine();
+
+        let hm = circom_poseidon(&[r8.x, r8.y, pubkey.0.x, pubkey.0.y, *msg]);
+
+        // S = r + 8 * hm * sk, since circomlib's secret scalars are multiples of 8.
+        let s = r + BabyJubJubFr::from(8u64) * fq_to_fr(hm) * self.sk;
+
+        EdDSASignature { r8, s }
+    }
+}
+
+impl EdDSAPubkey {
+    fn verify(&self, msg: &F, sig: &EdDSASignature) -> bool {
+        if self.0.x == F::ZERO {
+            return false;
+        }
+
+        let hm = circom_poseidon(&[sig.r8.x, sig.r8.y, self.0.x, self.0.y, *msg]);
+
+        // S * Base8 == R8 + (8 * hm) * A
+        let lhs = BabyJubJub::generator() * sig.s;
+        let a8 = self.0.into_group().double().double().double();
+        let rhs = sig.r8.into_group() + a8 * fq_to_fr(hm);
+
+        lhs == rhs
+    }
+}
+
+impl Pubkey<F> for EdDSAPubkey {
+    type PubkeyVar = EdDSAPubkeyVar;
+
+    type Sig = EdDSASignature;
+
+    type SigVar = EdDSASignatureVar;
+
+    fn verify(&self, signature: Self::Sig, msg: F) -> bool {
+        EdDSAPubkey::verify(self, &msg, &signature)
+    }
+
+    fn verify_zk(
+        pubkey: Self::PubkeyVar,
+        signature: Self::SigVar,
+        msg: FpVar<F>,
+    ) -> Result<Boolean<F>, SynthesisError> {
+        EdDSAPubkeyVar::verify(&pubkey, &msg, &signature)
+    }
+}
+
+impl Privkey<F> for EdDSAPrivkey {
+    type CompressedPrivKey = EdDSACompressedPrivkey;
+
+    type Sig = EdDSASignature;
+
+    type Pubkey = EdDSAPubkey;
+
+    fn gen_ckey(rng: &mut (impl CryptoRng + RngCore)) -> Self::CompressedPrivKey {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        EdDSACompressedPrivkey(seed)
+    }
+
+    fn into_key(c: Self::CompressedPrivKey) -> Self {
+        Self::from_seed(&c)
+    }
+
+    fn gen_key(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        Self::into_key(Self::gen_ckey(rng))
+    }
+
+    fn get_pubkey(&self) -> Self::Pubkey {
+        EdDSAPubkey((BabyJubJub::generator() * self.sk).into_affine())
+    }
+
+    fn sign(&self, _rng: &mut (impl CryptoRng + RngCore), msg: F) -> Option<Self::Sig> {
+        Some(EdDSAPrivkey::sign(self, &msg))
+    }
+}
+
+impl ark_ff::ToConstraintField<F> for EdDSAPubkey {
+    fn to_field_elements(&self) -> Option<Vec<F>> {
+        Some(vec![self.0.x, self.0.y])
+    }
+}
+
+impl ToConstraintFieldGadget<F> for EdDSAPubkeyVar {
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        self.0.to_constraint_field()
+    }
+}
+
+impl R1CSVar<F> for EdDSAPubkeyVar {
+    type Value = EdDSAPubkey;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.0.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        Ok(EdDSAPubkey(self.0.value()?.into_affine()))
+    }
+}
+
+impl AllocVar<EdDSAPubkey, F> for EdDSAPubkeyVar {
+    fn new_variable<T: Borrow<EdDSAPubkey>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let res = f();
+
+        res.and_then(|pk| {
+            let pk = pk.borrow();
+
+            let v = <BabyJubJubVar as AllocVar<Affine<BabyJubJubConfig>, _>>::new_variable(
+                ns!(cs, "entry"),
+                || Ok(pk.0),
+                mode,
+            )?;
+
+            Ok(Self(v))
+        })
+    }
+}
+
+impl EdDSAPubkeyVar {
+    /// Verifies the given (message, signature) pair under the public key in zero knowledge.
+    ///
+    /// This enforces the same relation as circomlib's `EdDSAPoseidonVerifier`, but outputs the
+    /// result as a boolean rather than asserting it.
+    fn verify(
+        &self,
+        msg: &FpVar<F>,
+        sig: &EdDSASignatureVar,
+    ) -> Result<Boolean<F>, SynthesisError> {
+        let cs = self.0.cs().or(msg.cs()).or(sig.s.cs());
+
+        let EdDSASignatureVar { r8, s } = sig;
+
+        // S must be a canonical scalar, and A must not have x = 0.
+        let s_bits = s.to_bits_le()?;
+        let mut s_is_canonical = Boolean::FALSE;
+        for (b, c) in s_bits.iter().zip(BabyJubJubFr::MODULUS.to_bits_le()) {
+            // Whether the low bits of S are less than the low bits of the subgroup order.
+            s_is_canonical = if c {
+                !b | s_is_canonical
+            } else {
+                !b & s_is_canonical
+            };
+        }
+        let pk_nonzero = self.0.x.is_neq(&FpVar::zero())?;
+
+        let hm = circom_poseidon_zk(&[
+            r8.x.clone(),
+            r8.y.clone(),
+            self.0.x.clone(),
+            self.0.y.clone(),
+            msg.clone(),
+        ])?;
+
+        // S * Base8 == R8 + (8 * hm) * A
+        let gv = BabyJubJubVar::new_constant(ns!(cs, "base8"), BabyJubJub::generator())?;
+        let lhs = gv.scalar_mul_le(s_bits.iter())?;
+        let a8 = self.0.double()?.double()?.double()?;
+        let rhs = r8.clone() + a8.scalar_mul_le(hm.to_bits_le()?.iter())?;
+
+        let is_valid = lhs.is_eq(&rhs)?;
+
+        Ok(is_valid & s_is_canonical & pk_nonzero)
+    }
+}
+
+impl CondSelectGadget<F> for EdDSAPubkeyVar {
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let selected = BabyJubJubVar::conditionally_select(cond, &true_value.0, &false_value.0)?;
+        Ok(EdDSAPubkeyVar(selected))
+    }
+}
+
+/// The EdDSA-Poseidon signature scheme over Baby Jubjub, compatible with circomlib. Implements
+/// [`Signature`].
+#[derive(Clone, Default, Debug)]
+pub struct EdDSAPoseidon;
+
+impl Signature<F> for EdDSAPoseidon {
+    type SigVar = EdDSASignatureVar;
+
+    type Sig = EdDSASignature;
+
+    type Pubkey = EdDSAPubkey;
+
+    type PubkeyVar = EdDSAPubkeyVar;
+
+    type CPrivkey = EdDSACompressedPrivkey;
+
+    type Privkey = EdDSAPrivkey;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::impls::centralized::ds::sigstore::SigObjStore;
+    use ark_ff::UniformRand;
+    use ark_relations::r1cs::ConstraintSystem;
+    use rand::thread_rng;
+
+    // The Blake-512 test vectors from the Blake specification.
+    #[test]
+    fn blake512_vector() {
+        let hex = |h: [u8; 64]| h.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!(
+            hex(blake512(&[])),
+            "a8cfbbd73726062df0c6864dda65defe58ef0cc52a5625090fa17601e1eecd1b\
+             628e94f396ae402a00acc9eab77b4d4c2e852aaaa25a636d80af3fc7913ef5b8"
+        );
+        assert_eq!(
+            hex(blake512(&[0])),
+            "97961587f6d970faba6d2478045de6d1fabd09b61ae50932054d52bc29d31be4\
+             ff9102b9f69e2bbdb83be13d4b9c06091e5fa0b48bd081b634058be0ec49beb3"
+        );
+        assert_eq!(
+            hex(blake512(&[0; 144])),
+            "313717d608e9cf758dcb1eb0f0c3cf9fc150b2d500fb33f51c52afc99d358a2f\
+             1374b8a38bba7974e7f6ef79cab16f22ce1e649d6e01ad9589c213045d545dde"
+        );
+    }
+
+    // The "Sign (using Poseidon) a single 10 bytes from 0 to 9" test from circomlib.
+    #[test]
+    fn eddsa_circomlib_vector() {
+        let seed: [u8; 32] = core::array::from_fn(|i| (i % 10) as u8);
+        let privkey = EdDSAPrivkey::into_key(EdDSACompressedPrivkey(seed));
+        let pubkey = privkey.get_pubkey();
+        let msg = F::from_le_bytes_mod_order(&core::array::from_fn::<u8, 10, _>(|i| i as u8));
+
+        let sig = EdDSAPrivkey::sign(&privkey, &msg);
+
+        const AX: F = MontFp!(
+            "13277427435165878497778222415993513565335242147425444199013288855685581939618"
+        );
+        const AY: F = MontFp!(
+            "13622229784656158136036771217484571176836296686641868549125388198837476602820"
+        );
+        const R8X: F = MontFp!(
+            "11384336176656855268977457483345535180380036354188103142384839473266348197733"
+        );
+        const R8Y: F = MontFp!(
+            "15383486972088797283337779941324724402501462225528836549661220478783371668959"
+        );
+        const S: BabyJubJubFr =
+            MontFp!("1672775540645840396591609181675628451599263765380031905495115170613215233181");
+
+        assert_eq!((pubkey.0.x, pubkey.0.y), (AX, AY));
+        assert_eq!((sig.r8.x, sig.r8.y), (R8X, R8Y));
+        assert_eq!(sig.s, S);
+        assert!(pubkey.verify(&msg, &sig));
+    }
+
+    // The membership signature test vector from the module documentation.
+    #[test]
+    fn eddsa_membership_vector() {
+        let seed: [u8; 32] = core::array::from_fn(|i| (i % 10) as u8);
+        let privkey = EdDSAPrivkey::into_key(EdDSACompressedPrivkey(seed));
+        let msg = SigObjStore::<F, EdDSAPoseidon>::memb_message(F::from(1));
+
+        let sig = EdDSAPrivkey::sign(&privkey, &msg);
+
+        const MSG: F =
+            MontFp!("5720288849464820317721216840131651062329576676078436087606336270803369004218");
+        const R8X: F = MontFp!(
+            "17180484372720817468100826288749091635105180888158772473905061011909355420147"
+        );
+        const R8Y: F =
+            MontFp!("1967542752276547236653714462176320328238856932831647454729971580101361448049");
+        const S: BabyJubJubFr =
+            MontFp!("1607325566480414756104251015250419867748539799852744371744424355556912719747");
+
+        assert_eq!(msg, MSG);
+        assert_eq!((sig.r8.x, sig.r8.y), (R8X, R8Y));
+        assert_eq!(sig.s, S);
+        assert!(privkey.get_pubkey().verify(&msg, &sig));
+    }
+
+    // Tests that the verification circuit is satisfied iff the signature is valid
+    #[test]
+    fn eddsa_verify() -> Result<(), SynthesisError> {
+        let mut rng = thread_rng();
+
+        for _ in 0..5 {
+            let cs = ConstraintSystem::<F>::new_ref();
+
+            let privkey = EdDSAPrivkey::gen_key(&mut rng);
+            let pubkey = privkey.get_pubkey();
+            let msg = F::rand(&mut rng);
+            let sig = EdDSAPrivkey::sign(&privkey, &msg);
+
+            let msg_var = FpVar::new_input(cs.clone(), || Ok(msg))?;
+            let sig_var = EdDSASignatureVar::new_witness(cs.clone(), || Ok(&sig))?;
+            let pubkey_var = EdDSAPubkeyVar::new_input(cs.clone(), || Ok(&pubkey))?;
+
+            let success = pubkey_var.verify(&msg_var, &sig_var)?;
+            success.enforce_equal(&Boolean::TRUE)?;
+            assert!(cs.is_satisfied()?);
+            assert!(pubkey.verify(&msg, &sig));
+
+            // A different message must not verify.
+            let bad_msg = msg + F::ONE;
+            assert!(!pubkey.verify(&bad_msg, &sig));
+
+            let cs = ConstraintSystem::<F>::new_ref();
+            let msg_var = FpVar::new_input(cs.clone(), || Ok(bad_msg))?;
+            let sig_var = EdDSASignatureVar::new_witness(cs.clone(), || Ok(&sig))?;
+            let pubkey_var = EdDSAPubkeyVar::new_input(cs.clone(), || Ok(&pubkey))?;
+            assert!(!pubkey_var.verify(&msg_var, &sig_var)?.value()?);
+
+            // A non-canonical S (offset by the subgroup order) must not verify.
+            let cs = ConstraintSystem::<F>::new_ref();
+            let msg_var = FpVar::new_input(cs.clone(), || Ok(msg))?;
+            let mut sig_var = EdDSASignatureVar::new_witness(cs.clone(), || Ok(&sig))?;
+            sig_var.s = FpVar::new_witness(cs.clone(), || {
+                Ok(fr_to_fq(sig.s)
+                    + F::from_le_bytes_mod_order(&BabyJubJubFr::MODULUS.to_bytes_le()))
+            })?;
+            let pubkey_var = EdDSAPubkeyVar::new_input(cs.clone(), || Ok(&pubkey))?;
+            assert!(!pubkey_var.verify(&msg_var, &sig_var)?.value()?);
+        }
+        Ok(())
+    }
+}
@@ -31,15 +31,28 @@ use ark_relations::{
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::Rng;
 use std::borrow::Borrow;
+use zeroize::Zeroize;
 
 type FV = FpVar<Fq>;
 
 const SCHNORR_HASH_SEPARATOR: u8 = 0x03;
 
 /// A private Grumpkin BN254 Schnorr signing key.
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Default, Debug)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Default)]
 pub struct GRSchnorrPrivkey(F);
 
+impl std::fmt::Debug for GRSchnorrPrivkey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GRSchnorrPrivkey").finish_non_exhaustive()
+    }
+}
+
+impl Drop for GRSchnorrPrivkey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// A public Grumpkin BN254 Schnorr verification key.
 #[derive(Debug, Eq, PartialEq, Clone, Copy, CanonicalSerialize, CanonicalDeserialize)]
 pub struct GRSchnorrPubkey(G);
@@ -33,6 +33,7 @@ use ark_relations::{
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::Rng;
 use std::borrow::Borrow;
+use zeroize::Zeroize;
 
 type BlsFrV = FpVar<BlsFr>;
 
@@ -51,9 +52,21 @@ where
 type JubjubFr = <Jubjub as PrimeGroup>::ScalarField;
 
 /// A private Jubjub BLS Schnorr signing key.
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Default, Debug)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Default)]
 pub struct JJSchnorrPrivkey(JubjubFr);
 
+impl std::fmt::Debug for JJSchnorrPrivkey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JJSchnorrPrivkey").finish_non_exhaustive()
+    }
+}
+
+impl Drop for JJSchnorrPrivkey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// A public Jubjub BLS Schnorr verification key.
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Default, CanonicalSerialize, CanonicalDeserialize)]
 pub struct JJSchnorrPubkey(Jubjub);
@@ -69,10 +69,15 @@ pub trait Signature<F: PrimeField>: Clone {
     /// The public verification key in-circuit.
     type PubkeyVar: Clone + AllocVar<Self::Pubkey, F>;
     /// The compressed private key.
-    type CPrivkey: Clone;
+    type CPrivkey: Clone + CanonicalSerialize + CanonicalDeserialize;
     /// The private key.
+    ///
+    /// Private keys may be serialized, and should be zeroized when dropped. To store a private key
+    /// on disk, see [`keystore`](`crate::crypto::keystore`).
     type Privkey: Privkey<F, CompressedPrivKey = Self::CPrivkey, Pubkey = Self::Pubkey, Sig = Self::Sig>
-        + Clone;
+        + Clone
+        + CanonicalSerialize
+        + CanonicalDeserialize;
 
     /// Generate a compressed private key.
     fn gen_ckey(rng: &mut (impl CryptoRng + RngCore)) -> Self::CPrivkey {
@@ -125,3 +130,9 @@ pub mod bls377_schnorr;
 
 /// Schnorr signatures over Grumpkin and bn254 for in-circuit verification.
 pub mod gr_schnorr;
+
+/// EdDSA-Poseidon signatures over Baby Jubjub and bn254, compatible with circomlib.
+#[cfg(feature = "circposeidon")]
+#[cfg(any(feature = "circposeidon", doc))]
+#[doc(cfg(feature = "circposeidon"))]
+pub mod eddsa_poseidon;
@@ -1,19 +1,28 @@
 use std::marker::PhantomData;
 
 use crate::{
-    crypto::hash::FieldHash,
+    crypto::hash::{FieldHash, HashDomain, hash_with_domain, hash_with_domain_in_zk},
     impls::{
         centralized::ds::sig::{Privkey, Pubkey, Signature},
         hash::Poseidon,
     },
 };
+use ark_bls12_381::Fr as BlsFr;
+use ark_bn254::Fr as BnFr;
 use ark_crypto_primitives::sponge::Absorb;
 use ark_ff::{PrimeField, ToConstraintField};
 use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget, fields::fp::FpVar, prelude::Boolean};
-use ark_relations::{ns, r1cs::SynthesisError};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_relations::{
+    ns,
+    r1cs::{ConstraintSystem, SynthesisError},
+};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use nalgebra::{DMatrix, DVector};
 use rand::{Rng, distributions::Standard, prelude::Distribution};
+use zeroize::Zeroize;
 
 /// A UOV signature.
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, PartialEq, Eq)]
@@ -176,7 +185,7 @@ impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> Pubkey<F>
         let mut check = true;
         let mut t = DVector::from_element(M, F::ZERO);
 
-        t[0] = H::hash(&[msg]);
+        t[0] = hash_with_domain::<F, H>(HashDomain::UOVSign, &[msg]);
         for i in 1..M {
             t[i] = H::hash(&[t[i - 1]]);
         }
@@ -198,7 +207,7 @@ impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> Pubkey<F>
         msg: FpVar<F>,
     ) -> Result<Boolean<F>, SynthesisError> {
         let mut t = vec![];
-        t.push(H::hash_in_zk(&[msg])?);
+        t.push(hash_with_domain_in_zk::<F, H>(HashDomain::UOVSign, &[msg])?);
         for i in 1..M {
             t.push(H::hash_in_zk(&t[(i - 1)..i])?);
         }
@@ -226,8 +235,14 @@ impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> Pubkey<F>
 }
 
 /// A UOV private signing key.
-#[derive(Clone, Default, Debug)]
+///
+/// The public matrices `P1` and `P2` are expanded from a public seed (see
+/// [`UOVCompressedPubkey`]), while signing is deterministic, with the vinegar variables derived
+/// from a secret signing seed and the message.
+#[derive(Clone, Default)]
 pub struct UOVPrivkey<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> {
+    pk_seed: F,
+    sign_seed: F,
     o: DMatrix<F>,
     s_i: Vec<DMatrix<F>>,
     p1s: Vec<DMatrix<F>>,
@@ -238,95 +253,362 @@ pub struct UOVPrivkey<F: PrimeField, H: FieldHash<F>, const N: usize, const M: u
     h: PhantomData<H>,
 }
 
+impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> std::fmt::Debug
+    for UOVPrivkey<F, H, N, M>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UOVPrivkey").finish_non_exhaustive()
+    }
+}
+
+impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> Drop
+    for UOVPrivkey<F, H, N, M>
+{
+    fn drop(&mut self) {
+        self.sign_seed.zeroize();
+        let mats = std::iter::once(&mut self.o)
+            .chain(self.s_i.iter_mut())
+            .chain(self.p1s.iter_mut())
+            .chain(self.p2s.iter_mut())
+            .chain(self.p3s.iter_mut());
+        for mat in mats {
+            mat.iter_mut().for_each(|x| x.zeroize());
+        }
+    }
+}
+
+/// Deserializes a matrix with the given dimensions, stored as its entries in column-major order.
+fn deserialize_matrix<F: PrimeField, R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+    (rows, cols): (usize, usize),
+) -> Result<DMatrix<F>, SerializationError> {
+    let entries = <Vec<F>>::deserialize_with_mode(&mut reader, compress, validate)?;
+    if entries.len() != rows * cols {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(DMatrix::from_vec(rows, cols, entries))
+}
+
+/// Deserializes `M` matrices with the given dimensions.
+fn deserialize_matrices<F: PrimeField, R: Read, const M: usize>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+    dims: (usize, usize),
+) -> Result<Vec<DMatrix<F>>, SerializationError> {
+    (0..M)
+        .map(|_| deserialize_matrix(&mut reader, compress, validate, dims))
+        .collect()
+}
+
+impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> CanonicalSerialize
+    for UOVPrivkey<F, H, N, M>
+{
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.pk_seed.serialize_with_mode(&mut writer, compress)?;
+        self.sign_seed.serialize_with_mode(&mut writer, compress)?;
+        self.o
+            .as_slice()
+            .serialize_with_mode(&mut writer, compress)?;
+        for mats in [&self.s_i, &self.p1s, &self.p2s, &self.p3s] {
+            for mat in mats {
+                mat.as_slice().serialize_with_mode(&mut writer, compress)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        let mut size = self.pk_seed.serialized_size(compress)
+            + self.sign_seed.serialized_size(compress)
+            + self.o.as_slice().serialized_size(compress);
+        for mats in [&self.s_i, &self.p1s, &self.p2s, &self.p3s] {
+            for mat in mats {
+                size += mat.as_slice().serialized_size(compress);
+            }
+        }
+        size
+    }
+}
+
+impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> Valid
+    for UOVPrivkey<F, H, N, M>
+{
+    /// Checks the dimensions of the matrices, and that the public matrices and `S` are consistent
+    /// with the public seed and the oil space.
+    fn check(&self) -> Result<(), SerializationError> {
+        let dims_ok = self.o.shape() == (N - M, M)
+            && [&self.s_i, &self.p1s, &self.p2s, &self.p3s]
+                .iter()
+                .all(|mats| mats.len() == M)
+            && self.s_i.iter().all(|x| x.shape() == (N - M, M))
+            && self.p1s.iter().all(|x| x.shape() == (N - M, N - M))
+            && self.p2s.iter().all(|x| x.shape() == (N - M, M))
+            && self.p3s.iter().all(|x| x.shape() == (M, M));
+        if !dims_ok {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let (p1s, p2s) = expand_public_matrices::<F, H, N, M>(self.pk_seed);
+        if p1s != self.p1s || p2s != self.p2s {
+            return Err(SerializationError::InvalidData);
+        }
+        for k in 0..M {
+            let (p3, s) = derive_p3_and_s::<F, M>(&self.o, &self.p1s[k], &self.p2s[k]);
+            if p3 != self.p3s[k] || s != self.s_i[k] {
+                return Err(SerializationError::InvalidData);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> CanonicalDeserialize
+    for UOVPrivkey<F, H, N, M>
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let pk_seed = F::deserialize_with_mode(&mut reader, compress, validate)?;
+        let sign_seed = F::deserialize_with_mode(&mut reader, compress, validate)?;
+        let o = deserialize_matrix(&mut reader, compress, validate, (N - M, M))?;
+        let s_i = deserialize_matrices::<F, _, M>(&mut reader, compress, validate, (N - M, M))?;
+        let p1s = deserialize_matrices::<F, _, M>(&mut reader, compress, validate, (N - M, N - M))?;
+        let p2s = deserialize_matrices::<F, _, M>(&mut reader, compress, validate, (N - M, M))?;
+        let p3s = deserialize_matrices::<F, _, M>(&mut reader, compress, validate, (M, M))?;
+        let key = Self {
+            pk_seed,
+            sign_seed,
+            o,
+            s_i,
+            p1s,
+            p2s,
+            p3s,
+            n: PhantomData,
+            m: PhantomData,
+            h: PhantomData,
+        };
+        if validate == Validate::Yes {
+            key.check()?;
+        }
+        Ok(key)
+    }
+}
+
 /// A compressed UOV private key.
-#[derive(Clone, Default, Debug, CanonicalSerialize, CanonicalDeserialize)]
+///
+/// Compressed keys from earlier versions expand to different keys; see
+/// [`Privkey::into_key`].
+#[derive(Clone, Default, CanonicalSerialize, CanonicalDeserialize)]
 pub struct UOVCompressedPrivkey<F: PrimeField, const N: usize, const M: usize> {
     seed: Vec<F>,
     n: PhantomData<[(); N]>,
     m: PhantomData<[(); M]>,
 }
 
-impl<F: PrimeField + Absorb, H: FieldHash<F>, const N: usize, const M: usize> Privkey<F>
-    for UOVPrivkey<F, H, N, M>
-where
-    Standard: Distribution<F>,
+impl<F: PrimeField, const N: usize, const M: usize> std::fmt::Debug
+    for UOVCompressedPrivkey<F, N, M>
 {
-    type CompressedPrivKey = UOVCompressedPrivkey<F, N, M>;
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UOVCompressedPrivkey")
+            .finish_non_exhaustive()
+    }
+}
 
-    type Sig = UOVSig<F, N, M>;
+impl<F: PrimeField, const N: usize, const M: usize> Drop for UOVCompressedPrivkey<F, N, M> {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+    }
+}
 
-    type Pubkey = UOVPubkey<F, H, N, M>;
+/// The maximum number of vinegar assignments tried when signing, before giving up.
+const MAX_SIGN_ATTEMPTS: u64 = 256;
+
+/// Expands the public matrices `P1` and `P2` for each of the `M` equations from a public seed.
+///
+/// Each entry is `H(UOVExpand, seed, index)`, where `index` counts up over all entries (in
+/// column-major order, for `P1` then `P2` of each equation).
+fn expand_public_matrices<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize>(
+    seed: F,
+) -> (Vec<DMatrix<F>>, Vec<DMatrix<F>>) {
+    let mut index = 0u64;
+    let mut next = || {
+        index += 1;
+        hash_with_domain::<F, H>(HashDomain::UOVExpand, &[seed, F::from(index)])
+    };
+
+    let mut p1s = vec![];
+    let mut p2s = vec![];
+    for _ in 0..M {
+        p1s.push(DMatrix::from_fn(N - M, N - M, |_, _| next()));
+        p2s.push(DMatrix::from_fn(N - M, M, |_, _| next()));
+    }
+    (p1s, p2s)
+}
 
-    fn gen_ckey(rng: &mut (impl rand::CryptoRng + rand::RngCore)) -> Self::CompressedPrivKey {
-        let mut out = vec![];
-        for _ in 0..32 {
-            out.push(rng.r#gen());
-        }
-        Self::CompressedPrivKey {
-            seed: out,
-            n: PhantomData,
-            m: PhantomData,
+/// Computes the public matrix `P3` (in upper triangular form) and the secret linear map `S` for an
+/// equation, from the secret oil space `O` and the public matrices `P1` and `P2`.
+fn derive_p3_and_s<F: PrimeField, const M: usize>(
+    o: &DMatrix<F>,
+    p1: &DMatrix<F>,
+    p2: &DMatrix<F>,
+) -> (DMatrix<F>, DMatrix<F>) {
+    let mut p3 = -(o.transpose() * p1 * o) - o.transpose() * p2;
+
+    for c in 0..M {
+        for r in 0..c {
+            p3[(r, c)] = p3[(r, c)] + p3[(c, r)];
+            p3[(c, r)] = F::ZERO;
         }
     }
 
-    fn gen_key(rng: &mut (impl rand::CryptoRng + rand::RngCore)) -> Self {
-        let mut o = DMatrix::from_element(N - M, M, F::ZERO);
+    let s = (p1 + p1.transpose()) * o + p2;
+    (p3, s)
+}
 
-        for j in 0..M {
+/// Lays out the public matrices as the data of a [`UOVPubkey`].
+///
+/// For each equation, this is the matrix `[[P1, P2], [0, P3]]`, row by row.
+fn pubkey_data<F: PrimeField, const N: usize, const M: usize>(
+    p1s: &[DMatrix<F>],
+    p2s: &[DMatrix<F>],
+    p3s: &[DMatrix<F>],
+) -> Vec<F> {
+    let mut pk = vec![];
+    for k in 0..M {
+        for r in 0..(N - M) {
             for i in 0..(N - M) {
-                o[(i, j)] = rng.r#gen();
+                pk.push(p1s[k][(r, i)]);
+            }
+
+            for i in 0..(M) {
+                pk.push(p2s[k][(r, i)]);
             }
         }
 
-        let mut p1s = vec![];
-        let mut p2s = vec![];
-        let mut p3s = vec![];
-        let mut s_i = vec![];
+        for r in 0..(M) {
+            for _ in 0..(N - M) {
+                pk.push(F::ZERO);
+            }
+            for i in 0..(M) {
+                pk.push(p3s[k][(r, i)]);
+            }
+        }
+    }
+    pk
+}
 
-        for _ in 0..(M) {
-            let mut p1 = DMatrix::from_element(N - M, N - M, F::ZERO);
+/// A compressed UOV public verification key.
+///
+/// Consists of the public seed from which `P1` and `P2` are expanded, along with the upper
+/// triangular parts of each `P3`. This is `M^3 / 2` elements rather than the `M * N^2` of the full
+/// [`UOVPubkey`]; use [`UOVCompressedPubkey::decompress`] to recover the full key.
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, PartialEq, Eq)]
+pub struct UOVCompressedPubkey<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> {
+    seed: F,
+    p3s: Vec<F>,
+    n: PhantomData<[(); N]>,
+    m: PhantomData<[(); M]>,
+    h: PhantomData<H>,
+}
 
-            for j in 0..(N - M) {
-                for i in 0..(N - M) {
-                    p1[(i, j)] = rng.r#gen();
-                }
-            }
-            p1s.push(p1.clone());
+impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize>
+    UOVCompressedPubkey<F, H, N, M>
+{
+    /// Expand the compressed key into the full public key.
+    ///
+    /// Returns None if the compressed key is malformed.
+    pub fn decompress(&self) -> Option<UOVPubkey<F, H, N, M>> {
+        if self.p3s.len() != M * M * (M + 1) / 2 {
+            return None;
+        }
 
-            let mut p2 = DMatrix::from_element(N - M, M, F::ZERO);
+        let (p1s, p2s) = expand_public_matrices::<F, H, N, M>(self.seed);
 
-            for j in 0..M {
-                for i in 0..(N - M) {
-                    p2[(i, j)] = rng.r#gen();
+        let mut entries = self.p3s.iter();
+        let mut p3s = vec![];
+        for _ in 0..M {
+            let mut p3 = DMatrix::from_element(M, M, F::ZERO);
+            for r in 0..M {
+                for c in r..M {
+                    p3[(r, c)] = *entries.next()?;
                 }
             }
-            p2s.push(p2.clone());
+            p3s.push(p3);
+        }
 
-            let mut p3 = -(o.transpose() * &p1 * &o) - &o.transpose() * &p2;
+        Some(UOVPubkey {
+            data: pubkey_data::<F, N, M>(&p1s, &p2s, &p3s),
+            n: PhantomData,
+            m: PhantomData,
+            h: PhantomData,
+        })
+    }
+}
 
-            for c in 0..M {
-                for r in 0..c {
-                    p3[(r, c)] = p3[(r, c)] + p3[(c, r)];
-                    p3[(c, r)] = F::ZERO;
+impl<F: PrimeField, H: FieldHash<F>, const N: usize, const M: usize> UOVPrivkey<F, H, N, M> {
+    /// Get the compressed public verification key from the private signing key.
+    pub fn get_compressed_pubkey(&self) -> UOVCompressedPubkey<F, H, N, M> {
+        let mut p3s = vec![];
+        for p3 in &self.p3s {
+            for r in 0..M {
+                for c in r..M {
+                    p3s.push(p3[(r, c)]);
                 }
             }
-            p3s.push(p3);
-
-            let si = (&p1 + p1.transpose()) * &o + p2;
-            s_i.push(si);
         }
-        Self {
-            o,
-            s_i,
-            p1s,
-            p2s,
+
+        UOVCompressedPubkey {
+            seed: self.pk_seed,
             p3s,
             n: PhantomData,
             m: PhantomData,
             h: PhantomData,
         }
     }
+}
 
+impl<F: PrimeField + Absorb, H: FieldHash<F>, const N: usize, const M: usize> Privkey<F>
+    for UOVPrivkey<F, H, N, M>
+where
+    Standard: Distribution<F>,
+{
+    type CompressedPrivKey = UOVCompressedPrivkey<F, N, M>;
+
+    type Sig = UOVSig<F, N, M>;
+
+    type Pubkey = UOVPubkey<F, H, N, M>;
+
+    fn gen_ckey(rng: &mut (impl rand::CryptoRng + rand::RngCore)) -> Self::CompressedPrivKey {
+        let mut out = vec![];
+        for _ in 0..32 {
+            out.push(rng.r#gen());
+        }
+        Self::CompressedPrivKey {
+            seed: out,
+            n: PhantomData,
+            m: PhantomData,
+        }
+    }
+
+    fn gen_key(rng: &mut (impl rand::CryptoRng + rand::RngCore)) -> Self {
+        Self::into_key(Self::gen_ckey(rng))
+    }
+
+    /// Expand a compressed private key.
+    ///
+    /// Note that the expansion changed when the public matrices started being derived from a
+    /// public seed (see [`UOVCompressedPubkey`]): a [`UOVCompressedPrivkey`] created with an earlier
+    /// version expands to a different key, with a different public key. Signatures made before the
+    /// signed digest was domain separated (in [`HashDomain::UOVSign`]) no longer verify either.
     fn into_key(c: Self::CompressedPrivKey) -> Self {
         let mut state = c.seed.clone();
 
@@ -345,44 +627,24 @@ where
             }
         }
 
-        let mut p1s = vec![];
-        let mut p2s = vec![];
-        let mut p3s = vec![];
-        let mut s_i = vec![];
+        let pk_seed = update_state::<F, H>(&mut state);
+        let sign_seed = update_state::<F, H>(&mut state);
+        state.zeroize();
 
-        for _ in 0..(M) {
-            let mut p1 = DMatrix::from_element(N - M, N - M, F::ZERO);
+        let (p1s, p2s) = expand_public_matrices::<F, H, N, M>(pk_seed);
 
-            for j in 0..(N - M) {
-                for i in 0..(N - M) {
-                    p1[(i, j)] = update_state::<F, H>(&mut state);
-                }
-            }
-            p1s.push(p1.clone());
-
-            let mut p2 = DMatrix::from_element(N - M, M, F::ZERO);
-
-            for j in 0..M {
-                for i in 0..(N - M) {
-                    p2[(i, j)] = update_state::<F, H>(&mut state);
-                }
-            }
-            p2s.push(p2.clone());
-
-            let mut p3 = -(o.transpose() * &p1 * &o) - &o.transpose() * &p2;
+        let mut p3s = vec![];
+        let mut s_i = vec![];
 
-            for c in 0..M {
-                for r in 0..c {
-                    p3[(r, c)] = p3[(r, c)] + p3[(c, r)];
-                    p3[(c, r)] = F::ZERO;
-                }
-            }
+        for (p1, p2) in p1s.iter().zip(&p2s) {
+            let (p3, si) = derive_p3_and_s::<F, M>(&o, p1, p2);
             p3s.push(p3);
-
-            let si = (&p1 + p1.transpose()) * &o + p2;
             s_i.push(si);
         }
+
         Self {
+            pk_seed,
+            sign_seed,
             o,
             s_i,
             p1s,
@@ -395,72 +657,57 @@ where
     }
 
     fn get_pubkey(&self) -> Self::Pubkey {
-        let mut pk = vec![];
-        for k in 0..M {
-            for r in 0..(N - M) {
-                for i in 0..(N - M) {
-                    pk.push(self.p1s[k][(r, i)]);
-                }
-
-                for i in 0..(M) {
-                    pk.push(self.p2s[k][(r, i)]);
-                }
-            }
-
-            for r in 0..(M) {
-                for _ in 0..(N - M) {
-                    pk.push(F::ZERO);
-                }
-                for i in 0..(M) {
-                    pk.push(self.p3s[k][(r, i)]);
-                }
-            }
-        }
-
         Self::Pubkey {
-            data: pk,
+            data: pubkey_data::<F, N, M>(&self.p1s, &self.p2s, &self.p3s),
             n: PhantomData,
             m: PhantomData,
             h: PhantomData,
         }
     }
 
-    fn sign(&self, rng: &mut (impl rand::CryptoRng + rand::RngCore), msg: F) -> Option<Self::Sig> {
+    /// Sign a message.
+    ///
+    /// Signing is deterministic: the vinegar variables are derived from the secret signing seed and
+    /// the message, so `rng` is unused, and signing a message twice gives the same signature.
+    fn sign(&self, _rng: &mut (impl rand::CryptoRng + rand::RngCore), msg: F) -> Option<Self::Sig> {
         let mut t = DVector::from_element(M, F::ZERO);
 
-        t[0] = H::hash(&[msg]);
+        t[0] = hash_with_domain::<F, H>(HashDomain::UOVSign, &[msg]);
         for i in 1..M {
             t[i] = H::hash(&[t[i - 1]]);
         }
 
-        let mut v = DVector::from_element(N - M, F::ZERO);
+        for attempt in 0..MAX_SIGN_ATTEMPTS {
+            let mut v = DVector::from_element(N - M, F::ZERO);
 
-        for i in 0..(N - M) {
-            v[i] = rng.r#gen();
-        }
+            for i in 0..(N - M) {
+                v[i] = hash_with_domain::<F, H>(
+                    HashDomain::UOVSign,
+                    &[self.sign_seed, msg, F::from(attempt), F::from(i as u64)],
+                );
+            }
 
-        let mut ml = DMatrix::from_element(M, M, F::ZERO);
+            let mut ml = DMatrix::from_element(M, M, F::ZERO);
 
-        for (i, mut row) in ml.row_iter_mut().enumerate() {
-            row.copy_from(&(v.transpose() * &self.s_i[i]));
-        }
+            for (i, mut row) in ml.row_iter_mut().enumerate() {
+                row.copy_from(&(v.transpose() * &self.s_i[i]));
+            }
 
-        let mut y: DVector<F> = DVector::from_element(M, F::ZERO);
+            let mut y: DVector<F> = DVector::from_element(M, F::ZERO);
 
-        for i in 0..M {
-            y[i] = (v.transpose() * &self.p1s[i] * &v)[0];
-        }
-
-        let mut ml = ml.insert_column(M, F::ZERO);
+            for i in 0..M {
+                y[i] = (v.transpose() * &self.p1s[i] * &v)[0];
+            }
 
-        for i in 0..M {
-            ml[(i, M)] = t[i] - y[i];
-        }
+            let mut ml = ml.insert_column(M, F::ZERO);
 
-        let out = rref_solve(&ml);
+            for i in 0..M {
+                ml[(i, M)] = t[i] - y[i];
+            }
 
-        match out {
-            Some(x) => {
+            // The linear system is singular with probability roughly M / |F|; in that case, retry
+            // with the next vinegar assignment.
+            if let Some(x) = rref_solve(&ml) {
                 let mut baro = DMatrix::from_element(N, M, F::ZERO);
                 baro.index_mut((0..(N - M), 0..M)).copy_from(&self.o);
 
@@ -477,14 +724,15 @@ where
                     output.push(s[i]);
                 }
 
-                Some(Self::Sig {
+                return Some(Self::Sig {
                     preimage: output,
                     n: PhantomData,
                     m: PhantomData,
-                })
+                });
             }
-            None => None,
         }
+
+        None
     }
 }
 
@@ -578,12 +826,175 @@ where
     type Privkey = UOVPrivkey<F, H, N, M>;
 }
 
+impl<F: PrimeField + Absorb, H: FieldHash<F>, const N: usize, const M: usize> UOV<F, H, N, M>
+where
+    Standard: Distribution<F>,
+{
+    /// The number of constraints used by [`Signature::verify_zk`] for these parameters, with the
+    /// public key and signature allocated as witnesses.
+    ///
+    /// Verification is dominated by the `M` quadratic forms, each of which costs `N^2 + N`
+    /// multiplications, along with the `M` hashes used to expand the message.
+    pub fn verify_zk_constraints() -> Result<usize, SynthesisError> {
+        let cs = ConstraintSystem::<F>::new_ref();
+        let pubkey =
+            UOVPubkeyVar::new_witness(
+                ns!(cs, "pubkey"),
+                || Ok(UOVPubkey::<F, H, N, M>::default()),
+            )?;
+        let sig = UOVSigVar::new_witness(ns!(cs, "sig"), || Ok(UOVSig::<F, N, M>::default()))?;
+        let msg = FpVar::new_witness(ns!(cs, "msg"), || Ok(F::ZERO))?;
+        let _ = Self::verify_zk(pubkey, sig, msg)?;
+        Ok(cs.num_constraints())
+    }
+}
+
+/// A named UOV parameter set.
+///
+/// Since the base field is large (roughly `2^254` for both BN254 and BLS12-381), the
+/// Kipnis-Shamir and reconciliation attacks cost at least `|F|^(N - 2M - 1)` or so, and
+/// exhaustive guessing in the hybrid attack is infeasible. The security estimates are therefore
+/// taken against the direct attack: after the Thomae-Wolf reduction, forging amounts to solving a
+/// square system of `M - floor(N / M) + 1` quadratic equations, and the Groebner basis cost is
+/// estimated as `binom(2m' + 2, m' + 1)^2` (with linear algebra constant 2, which is
+/// conservative).
+///
+/// The security levels are hand estimates from these formulas, not the output of an estimator,
+/// and are not a formal proof of security. See Thomae and Wolf, "Solving Underdetermined Systems
+/// of Multivariate Quadratic Equations Revisited" (PKC 2012) for the reduction, and Beullens et
+/// al., "Oil and Vinegar: Modern Parameters and Implementations" (TCHES 2023) for the attacks
+/// considered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UOVParameterSet {
+    /// The name of the parameter set.
+    pub name: &'static str,
+    /// The total number of variables.
+    pub n: usize,
+    /// The number of equations (and oil variables).
+    pub m: usize,
+    /// Estimated security against the best known attack, in bits.
+    pub security_bits: u32,
+}
+
+/// Parameters for roughly 128 bits of security; see [`Bn254UOV128`] and [`Bls381UOV128`].
+pub const UOV_128: UOVParameterSet = UOVParameterSet {
+    name: "UOV-128",
+    n: 80,
+    m: 35,
+    security_bits: 131,
+};
+
+/// Parameters for roughly 192 bits of security; see [`Bn254UOV192`] and [`Bls381UOV192`].
+pub const UOV_192: UOVParameterSet = UOVParameterSet {
+    name: "UOV-192",
+    n: 128,
+    m: 52,
+    security_bits: 198,
+};
+
 /// Testing setting for UOV signatures.
 pub type TestUOV<F> = UOV<F, Poseidon<2>, 15, 6>;
-/// Bleeding edge security.
-pub type BleedingUOV<F> = UOV<F, Poseidon<2>, 80, 35>;
+/// Bleeding edge security. These are the [`UOV_128`] parameters.
+pub type BleedingUOV<F> = UOV<F, Poseidon<2>, { UOV_128.n }, { UOV_128.m }>;
 /// Standard L1 security. Note that while the L1 parameters are identical to UOV, the base field is
 /// much larger.
 pub type L1UOV<F> = UOV<F, Poseidon<2>, 112, 44>;
 /// Standard L2 security.
 pub type L2UOV<F> = UOV<F, Poseidon<2>, 160, 64>;
+
+/// UOV over the BN254 scalar field with the [`UOV_128`] parameters.
+pub type Bn254UOV128 = UOV<BnFr, Poseidon<2>, { UOV_128.n }, { UOV_128.m }>;
+/// UOV over the BN254 scalar field with the [`UOV_192`] parameters.
+pub type Bn254UOV192 = UOV<BnFr, Poseidon<2>, { UOV_192.n }, { UOV_192.m }>;
+/// UOV over the BLS12-381 scalar field with the [`UOV_128`] parameters.
+pub type Bls381UOV128 = UOV<BlsFr, Poseidon<2>, { UOV_128.n }, { UOV_128.m }>;
+/// UOV over the BLS12-381 scalar field with the [`UOV_192`] parameters.
+pub type Bls381UOV192 = UOV<BlsFr, Poseidon<2>, { UOV_192.n }, { UOV_192.m }>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ark_r1cs_std::R1CSVar;
+    use rand::thread_rng;
+
+    type F = BlsFr;
+    type Test = TestUOV<F>;
+
+    #[test]
+    fn uov_deterministic_sign() {
+        let mut rng = thread_rng();
+        let sk = Test::gen_key(&mut rng);
+        let pk = Test::get_pubkey(&sk);
+        let msg = F::from(1234);
+
+        let sig = Test::sign(&sk, &mut rng, msg).unwrap();
+        assert_eq!(Test::sign(&sk, &mut rng, msg).unwrap(), sig);
+        assert!(Test::verify(pk.clone(), sig, msg));
+        assert!(!Test::verify(
+            pk,
+            Test::sign(&sk, &mut rng, msg).unwrap(),
+            F::from(4321)
+        ));
+    }
+
+    #[test]
+    fn uov_compressed_pubkey() {
+        let mut rng = thread_rng();
+        let sk = Test::gen_key(&mut rng);
+        let pk = Test::get_pubkey(&sk);
+        let cpk = sk.get_compressed_pubkey();
+
+        assert_eq!(cpk.decompress().unwrap().data, pk.data);
+        assert!(cpk.p3s.len() < pk.data.len());
+    }
+
+    #[test]
+    fn uov_privkey_validated_on_deserialize() {
+        let mut rng = thread_rng();
+        let mut sk = Test::gen_key(&mut rng);
+
+        let mut bytes = vec![];
+        sk.serialize_compressed(&mut bytes).unwrap();
+        let opened = <Test as Signature<F>>::Privkey::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(Test::get_pubkey(&opened).data, Test::get_pubkey(&sk).data);
+
+        sk.p3s[0][(0, 0)] += F::from(1);
+        let mut bytes = vec![];
+        sk.serialize_compressed(&mut bytes).unwrap();
+        assert!(<Test as Signature<F>>::Privkey::deserialize_compressed(&bytes[..]).is_err());
+        assert!(
+            <Test as Signature<F>>::Privkey::deserialize_compressed_unchecked(&bytes[..]).is_ok()
+        );
+    }
+
+    // Tests that the verification circuit agrees with native verification
+    #[test]
+    fn uov_verify_zk() -> Result<(), SynthesisError> {
+        let mut rng = thread_rng();
+        let sk = Test::gen_key(&mut rng);
+        let pk = Test::get_pubkey(&sk);
+        let msg = F::from(1234);
+        let sig = Test::sign(&sk, &mut rng, msg).unwrap();
+
+        for (m, valid) in [(msg, true), (F::from(4321), false)] {
+            assert_eq!(Test::verify(pk.clone(), sig.clone(), m), valid);
+
+            let cs = ConstraintSystem::<F>::new_ref();
+            let pk_var = UOVPubkeyVar::new_input(ns!(cs, "pubkey"), || Ok(pk.clone()))?;
+            let sig_var = UOVSigVar::new_witness(ns!(cs, "sig"), || Ok(sig.clone()))?;
+            let msg_var = FpVar::new_input(ns!(cs, "msg"), || Ok(m))?;
+            let out = UOVPubkey::<F, Poseidon<2>, 15, 6>::verify_zk(pk_var, sig_var, msg_var)?;
+            assert_eq!(out.value()?, valid);
+            assert!(cs.is_satisfied()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn uov_verify_constraints() {
+        let (n, m) = (15, 6);
+        let constraints = Test::verify_zk_constraints().unwrap();
+        assert!(constraints >= m * (n * n + n));
+    }
+}
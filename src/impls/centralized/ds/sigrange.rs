@@ -1,7 +1,10 @@
 use std::cmp::Ordering;
 
 use crate::{
-    crypto::hash::HasherZK,
+    crypto::{
+        hash::{HashDomain, hash_with_domain, hash_with_domain_in_zk},
+        keystore::{KeystoreError, KeystoreParams, encrypt_key},
+    },
     impls::{
         centralized::{
             crypto::{FakeSigPubkey, FakeSigPubkeyVar},
@@ -15,7 +18,7 @@ use ark_ff::PrimeField;
 use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar};
 use ark_relations::{ns, r1cs::SynthesisError};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use rand::{distributions::Standard, prelude::Distribution, thread_rng};
+use rand::{CryptoRng, RngCore, distributions::Standard, prelude::Distribution, thread_rng};
 
 use crate::impls::centralized::ds::sigstore::NonmembStore;
 
@@ -117,6 +120,25 @@ where
         self.pubkey.clone()
     }
 
+    /// Export the private range signing key, for example to store it with
+    /// [`encrypt_key`](`crate::crypto::keystore::encrypt_key`). The store may be recreated with
+    /// the key using `from`.
+    pub fn export_key(&self) -> S::Privkey {
+        self.privkey.clone()
+    }
+
+    /// Export the private range signing key, encrypted under a password.
+    ///
+    /// See [`encrypt_key`](`crate::crypto::keystore::encrypt_key`) for the format.
+    pub fn export_encrypted_key(
+        &self,
+        password: &[u8],
+        params: KeystoreParams,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Vec<u8>, KeystoreError> {
+        encrypt_key(&self.privkey, password, params, rng)
+    }
+
     /// Get the database of signed ranges.
     pub fn get_db(&self) -> Vec<SignedRange<F, S>> {
         self.ncalled_cbs.clone()
@@ -134,7 +156,10 @@ where
             let sig = S::sign(
                 &self.privkey,
                 &mut rng,
-                <Poseidon<2>>::hash(&[range.range.0, range.range.1, range.epoch]),
+                hash_with_domain::<F, Poseidon<2>>(
+                    HashDomain::SigRange,
+                    &[range.range.0, range.range.1, range.epoch],
+                ),
             );
             match sig {
                 Some(s) => {
@@ -178,7 +203,10 @@ where
         let sig = S::sign(
             &sk,
             rng,
-            <Poseidon<2>>::hash(&[init_range.0, init_range.1, F::ZERO]),
+            hash_with_domain::<F, Poseidon<2>>(
+                HashDomain::SigRange,
+                &[init_range.0, init_range.1, F::ZERO],
+            ),
         )
         .unwrap();
         let first_range = SignedRange {
@@ -240,7 +268,10 @@ where
             let sig = S::sign(
                 &self.privkey,
                 rng,
-                <Poseidon<2>>::hash(&[range.0, range.1, self.epoch]),
+                hash_with_domain::<F, Poseidon<2>>(
+                    HashDomain::SigRange,
+                    &[range.0, range.1, self.epoch],
+                ),
             )
             .unwrap();
             sv.push(SignedRange {
@@ -258,7 +289,10 @@ where
             let sig = S::sign(
                 &self.privkey,
                 rng,
-                <Poseidon<2>>::hash(&[init_range.0, init_range.1, self.epoch]),
+                hash_with_domain::<F, Poseidon<2>>(
+                    HashDomain::SigRange,
+                    &[init_range.0, init_range.1, self.epoch],
+                ),
             )
             .unwrap();
             let first_range = SignedRange {
@@ -310,11 +344,14 @@ where
         let c2 = S::verify_zk(
             extra_pub,
             extra_witness.sig,
-            <Poseidon<2>>::hash_in_zk(&[
-                extra_witness.range.0,
-                extra_witness.range.1,
-                extra_witness.epoch,
-            ])?,
+            hash_with_domain_in_zk::<F, Poseidon<2>>(
+                HashDomain::SigRange,
+                &[
+                    extra_witness.range.0,
+                    extra_witness.range.1,
+                    extra_witness.epoch,
+                ],
+            )?,
         )?;
 
         Ok(range_correct & c2)
@@ -1,5 +1,12 @@
+#[cfg(feature = "circposeidon")]
+#[cfg(any(feature = "circposeidon", doc))]
+#[doc(cfg(feature = "circposeidon"))]
+use crate::impls::centralized::ds::sig::eddsa_poseidon::EdDSAPoseidon;
 use crate::{
-    crypto::hash::HasherZK,
+    crypto::{
+        hash::{HashDomain, hash_with_domain, hash_with_domain_in_zk},
+        keystore::{KeystoreError, KeystoreParams, encrypt_key},
+    },
     generic::{
         bulletin::{CallbackBul, JoinableBulletin, PublicCallbackBul, PublicUserBul, UserBul},
         callbacks::CallbackCom,
@@ -103,6 +110,25 @@ impl<F: PrimeField + Absorb, S: Signature<F>> SigObjStore<F, S> {
         self.pubkey.clone()
     }
 
+    /// Export the private signing key, for example to store it with
+    /// [`encrypt_key`](`crate::crypto::keystore::encrypt_key`). The store may be recreated with
+    /// the key using `from`.
+    pub fn export_key(&self) -> S::Privkey {
+        self.privkey.clone()
+    }
+
+    /// Export the private signing key, encrypted under a password.
+    ///
+    /// See [`encrypt_key`](`crate::crypto::keystore::encrypt_key`) for the format.
+    pub fn export_encrypted_key(
+        &self,
+        password: &[u8],
+        params: KeystoreParams,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Vec<u8>, KeystoreError> {
+        encrypt_key(&self.privkey, password, params, rng)
+    }
+
     /// Get the full database.
     pub fn get_db(&self) -> Vec<(Com<F>, Nul<F>, Vec<Com<F>>, S::Sig)> {
         (0..(self.coms.len()))
@@ -124,7 +150,7 @@ impl<F: PrimeField + Absorb, S: Signature<F>> SigObjStore<F, S> {
         let mut rng = thread_rng();
         let mut v = vec![];
         for i in 0..self.coms.len() {
-            let out = S::sign(&self.privkey, &mut rng, self.coms[i]);
+            let out = S::sign(&self.privkey, &mut rng, Self::memb_message(self.coms[i]));
             match out {
                 Some(x) => {
                     v.push(x);
@@ -138,6 +164,18 @@ impl<F: PrimeField + Absorb, S: Signature<F>> SigObjStore<F, S> {
         Ok(())
     }
 
+    /// The message signed to attest membership of an object commitment.
+    ///
+    /// This is the hash of the commitment in the [`HashDomain::ObjectMembership`] domain, so a
+    /// signature of the store can not be reused as a signature on any other message.
+    ///
+    /// This is part of the wire format: external signers must sign this message, not the
+    /// commitment (see the [`eddsa_poseidon`](crate::impls::centralized::ds::sig::eddsa_poseidon)
+    /// module for a test vector).
+    pub fn memb_message(object: Com<F>) -> F {
+        hash_with_domain::<F, Poseidon<2>>(HashDomain::ObjectMembership, &[object])
+    }
+
     /// Get the signature of a specific object. Returns None if the object is not contained in the
     /// bulletin.
     pub fn get_signature_of(&self, obj: &Com<F>) -> Option<S::Sig> {
@@ -192,7 +230,11 @@ impl<F: PrimeField + Absorb, U: UserData<F>, S: Signature<F>> PublicUserBul<F, U
         extra_witness: Self::MembershipWitnessVar,
         extra_pub: Self::MembershipPubVar,
     ) -> Result<Boolean<F>, SynthesisError> {
-        S::verify_zk(extra_pub, extra_witness, data_var)
+        S::verify_zk(
+            extra_pub,
+            extra_witness,
+            hash_with_domain_in_zk::<F, Poseidon<2>>(HashDomain::ObjectMembership, &[data_var])?,
+        )
     }
 }
 
@@ -219,7 +261,7 @@ impl<F: PrimeField + Absorb, U: UserData<F>, S: Signature<F>> UserBul<F, U> for
         _verif_key: &Snark::VerifyingKey,
     ) -> Result<(), Self::Error> {
         let mut rng = thread_rng();
-        let out = S::sign(&self.privkey, &mut rng, object);
+        let out = S::sign(&self.privkey, &mut rng, Self::memb_message(object));
         match out {
             Some(x) => {
                 self.coms.push(object);
@@ -231,6 +273,43 @@ impl<F: PrimeField + Absorb, U: UserData<F>, S: Signature<F>> UserBul<F, U> for
             None => Err(()),
         }
     }
+
+    fn append_multi_value<
+        PubArgs: ToConstraintField<F> + Clone,
+        Snark: ark_snark::SNARK<F>,
+        const NUMUSERS: usize,
+        const NUMCBS: usize,
+    >(
+        &mut self,
+        objects: [Com<F>; NUMUSERS],
+        old_nuls: [Nul<F>; NUMUSERS],
+        cb_com_lists: [[Com<F>; NUMCBS]; NUMUSERS],
+        _args: PubArgs,
+        _proof: Snark::Proof,
+        _memb_data: Option<[Self::MembershipPub; NUMUSERS]>,
+        _verif_key: &Snark::VerifyingKey,
+    ) -> Result<(), Self::Error> {
+        // Sign every object before storing any, so a failed signature leaves the store unchanged.
+        let mut rng = thread_rng();
+        let sigs = objects
+            .iter()
+            .map(|object| S::sign(&self.privkey, &mut rng, Self::memb_message(*object)))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(())?;
+
+        for (((object, old_nul), cb_com_list), sig) in objects
+            .into_iter()
+            .zip(old_nuls)
+            .zip(cb_com_lists)
+            .zip(sigs)
+        {
+            self.coms.push(object);
+            self.old_nuls.push(old_nul);
+            self.cb_com_lists.push(cb_com_list.into());
+            self.sigs.push(sig);
+        }
+        Ok(())
+    }
 }
 
 impl<F: PrimeField + Absorb, U: UserData<F>, S: Signature<F>> JoinableBulletin<F, U>
@@ -246,7 +325,7 @@ where
         _pub_data: (),
     ) -> Result<(), Self::Error> {
         let mut rng = thread_rng();
-        let out = S::sign(&self.privkey, &mut rng, object);
+        let out = S::sign(&self.privkey, &mut rng, Self::memb_message(object));
         match out {
             Some(x) => {
                 self.coms.push(object);
@@ -420,6 +499,25 @@ where
         self.pubkey.clone()
     }
 
+    /// Export the private membership signing key, for example to store it with
+    /// [`encrypt_key`](`crate::crypto::keystore::encrypt_key`). The store may be recreated with
+    /// the key using `from`.
+    pub fn export_key(&self) -> S::Privkey {
+        self.privkey.clone()
+    }
+
+    /// Export the private membership signing key, encrypted under a password.
+    ///
+    /// See [`encrypt_key`](`crate::crypto::keystore::encrypt_key`) for the format.
+    pub fn export_encrypted_key(
+        &self,
+        password: &[u8],
+        params: KeystoreParams,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Vec<u8>, KeystoreError> {
+        encrypt_key(&self.privkey, password, params, rng)
+    }
+
     /// Get the database (this is the membership database).
     pub fn get_db(&self) -> Vec<(FakeSigPubkey<F>, Args, Time<F>, S::Sig)> {
         (0..(self.memb_called_cbs.len()))
@@ -441,11 +539,12 @@ where
         let mut rng = thread_rng();
         let mut v = vec![];
         for i in 0..self.memb_called_cbs.len() {
-            let mut v2 = vec![];
-            v2.push(self.memb_called_cbs[i].0.to());
-            v2.extend_from_slice(&self.memb_called_cbs[i].1.to_field_elements().unwrap());
-            v2.push(self.memb_called_cbs[i].2);
-            let out = S::sign(&self.privkey, &mut rng, <Poseidon<2>>::hash(&v2));
+            let (tik, args, time) = &self.memb_called_cbs[i];
+            let out = S::sign(
+                &self.privkey,
+                &mut rng,
+                Self::memb_message(tik, args, *time),
+            );
 
             match out {
                 Some(x) => {
@@ -462,6 +561,17 @@ where
         Ok(())
     }
 
+    /// The message signed to attest membership of a called ticket.
+    ///
+    /// This is the hash of the ticket, its arguments and the call time in the
+    /// [`HashDomain::CallbackMembership`] domain.
+    pub fn memb_message(tik: &FakeSigPubkey<F>, args: &Args, time: Time<F>) -> F {
+        let mut v = vec![tik.to()];
+        v.extend_from_slice(&args.to_field_elements().unwrap());
+        v.push(time);
+        hash_with_domain::<F, Poseidon<2>>(HashDomain::CallbackMembership, &v)
+    }
+
     /// Get a membership witness (a signature) for a specific ticket. If the ticket is not in the
     /// bulletin, this should return None.
     pub fn get_memb_witness(&self, tik: &FakeSigPubkey<F>) -> Option<S::Sig> {
@@ -552,6 +662,23 @@ where
         }
     }
 
+    fn get_membership_data_unchecked(
+        &self,
+        _tik: FakeSigPubkey<F>,
+    ) -> (
+        S::Pubkey,
+        S::Sig,
+        B::NonMembershipPub,
+        B::NonMembershipWitness,
+    ) {
+        (
+            self.get_pubkey(),
+            S::Sig::default(),
+            self.nmemb_bul.get_nmemb_pub(),
+            B::NonMembershipWitness::default(),
+        )
+    }
+
     fn enforce_membership_of(
         tikvar: (FakeSigPubkeyVar<F>, FpVar<F>, TimeVar<F>),
         extra_witness: Self::MembershipWitnessVar,
@@ -560,7 +687,10 @@ where
         S::verify_zk(
             extra_pub,
             extra_witness,
-            <Poseidon<2>>::hash_in_zk(&[tikvar.0.0, tikvar.1, tikvar.2])?,
+            hash_with_domain_in_zk::<F, Poseidon<2>>(
+                HashDomain::CallbackMembership,
+                &[tikvar.0.0, tikvar.1, tikvar.2],
+            )?,
         )
     }
 
@@ -633,6 +763,23 @@ where
         }
     }
 
+    fn get_membership_data_unchecked(
+        &self,
+        _tik: FakeSigPubkey<F>,
+    ) -> (
+        S::Pubkey,
+        S::Sig,
+        B::NonMembershipPub,
+        B::NonMembershipWitness,
+    ) {
+        (
+            self.get_pubkey(),
+            S::Sig::default(),
+            self.nmemb_bul.get_nmemb_pub(),
+            B::NonMembershipWitness::default(),
+        )
+    }
+
     fn enforce_membership_of(
         tikvar: (FakeSigPubkeyVar<F>, AVar, TimeVar<F>),
         extra_witness: Self::MembershipWitnessVar,
@@ -644,7 +791,11 @@ where
 
         v.push(tikvar.2);
 
-        S::verify_zk(extra_pub, extra_witness, <Poseidon<2>>::hash_in_zk(&v)?)
+        S::verify_zk(
+            extra_pub,
+            extra_witness,
+            hash_with_domain_in_zk::<F, Poseidon<2>>(HashDomain::CallbackMembership, &v)?,
+        )
     }
 
     fn enforce_nonmembership_of(
@@ -680,8 +831,11 @@ where
         time: Time<F>,
     ) -> Result<(), Self::Error> {
         let mut rng = thread_rng();
-        let v2 = vec![tik.to(), enc_args, time];
-        let out = S::sign(&self.privkey, &mut rng, <Poseidon<2>>::hash(&v2));
+        let out = S::sign(
+            &self.privkey,
+            &mut rng,
+            Self::memb_message(&tik, &enc_args, time),
+        );
 
         match out {
             Some(x) => {
@@ -723,11 +877,11 @@ where
         time: Time<F>,
     ) -> Result<(), Self::Error> {
         let mut rng = thread_rng();
-        let mut v2 = vec![];
-        v2.push(tik.to());
-        v2.extend_from_slice(&enc_args.to_field_elements().unwrap());
-        v2.push(time);
-        let out = S::sign(&self.privkey, &mut rng, <Poseidon<2>>::hash(&v2));
+        let out = S::sign(
+            &self.privkey,
+            &mut rng,
+            Self::memb_message(&tik, &enc_args, time),
+        );
 
         match out {
             Some(x) => {
@@ -796,7 +950,7 @@ where
         data: u64,
     ) -> Result<(), Self::Error> {
         self.interaction_ids.push(data);
-        let tiks = interaction.cb_tik_list.to_vec();
+        let tiks = interaction.issued_tickets();
         let mut v = vec![];
         for tik in tiks {
             let mut ser = vec![];
@@ -879,7 +1033,7 @@ where
         data: u64,
     ) -> Result<(), Self::Error> {
         self.interaction_ids.push(data);
-        let tiks = interaction.cb_tik_list.to_vec();
+        let tiks = interaction.issued_tickets();
         let mut v = vec![];
         for tik in tiks {
             let mut ser = vec![];
@@ -994,3 +1148,22 @@ pub type GRSchnorrCallbackStore<A> =
 /// A central storage system which uses Grumpkin BN254 Schnorr signatures.
 pub type GRSchnorrStore<A> =
     CentralStore<BnFr, GrumpkinSchnorr, SigRangeStore<BnFr, GrumpkinSchnorr>, A>;
+
+/// A user object store which uses circomlib compatible EdDSA-Poseidon signatures.
+#[cfg(feature = "circposeidon")]
+#[cfg(any(feature = "circposeidon", doc))]
+#[doc(cfg(feature = "circposeidon"))]
+pub type EdDSAObjStore = SigObjStore<BnFr, EdDSAPoseidon>;
+
+/// A callback storage system which uses circomlib compatible EdDSA-Poseidon signatures.
+#[cfg(feature = "circposeidon")]
+#[cfg(any(feature = "circposeidon", doc))]
+#[doc(cfg(feature = "circposeidon"))]
+pub type EdDSACallbackStore<A> =
+    CallbackStore<BnFr, EdDSAPoseidon, SigRangeStore<BnFr, EdDSAPoseidon>, A>;
+
+/// A central storage system which uses circomlib compatible EdDSA-Poseidon signatures.
+#[cfg(feature = "circposeidon")]
+#[cfg(any(feature = "circposeidon", doc))]
+#[doc(cfg(feature = "circposeidon"))]
+pub type EdDSAStore<A> = CentralStore<BnFr, EdDSAPoseidon, SigRangeStore<BnFr, EdDSAPoseidon>, A>;
@@ -6,13 +6,15 @@ use ark_crypto_primitives::sponge::{
 use ark_ec::CurveGroup;
 use ark_ff::{Field, PrimeField, ToConstraintField};
 use ark_r1cs_std::{
-    R1CSVar, alloc::AllocVar, convert::ToConstraintFieldGadget, fields::fp::FpVar, uint8::UInt8,
+    R1CSVar, alloc::AllocVar, boolean::Boolean, convert::ToConstraintFieldGadget, eq::EqGadget,
+    fields::fp::FpVar, uint8::UInt8,
 };
 use ark_relations::{ns, r1cs::SynthesisError};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use blake2::{Blake2s256 as Blake, Digest};
 use rand::{CryptoRng, Rng, RngCore, distributions::Standard, prelude::Distribution, thread_rng};
 use std::marker::PhantomData;
+use zeroize::Zeroize;
 
 use crate::{
     crypto::{
@@ -174,10 +176,19 @@ where
 }
 
 /// A Schnorr signing key. Implements [`RRSigner`].
+///
+/// The secret scalar is zeroized on drop.
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SchnorrPrivkey<E: CurveGroup> {
     sk: E::ScalarField,
 }
 
+impl<E: CurveGroup> Drop for SchnorrPrivkey<E> {
+    fn drop(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
 /// A Schnorr verification key. Implements [`RRVerifier`].
 #[derive(Default, Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SchnorrPubkey<E: CurveGroup> {
@@ -355,4 +366,24 @@ where
     type SigPKV = SchnorrPubkeyVar<F>;
 
     type SigSK = SchnorrPrivkey<E>;
+
+    fn release_ct(tag: F) -> Self::Ct {
+        Ciphertext([tag; N + 1])
+    }
+
+    fn is_release(tag: F, ct: &Self::Ct, _time: F) -> bool {
+        ct.0.iter().all(|c| *c == tag)
+    }
+
+    fn is_release_in_zk(
+        tag: &FpVar<F>,
+        ct: &CiphertextVar<F, { N + 1 }>,
+        _time: &FpVar<F>,
+    ) -> Result<Boolean<F>, SynthesisError> {
+        let mut out = Boolean::TRUE;
+        for c in ct.0.iter() {
+            out &= c.is_eq(tag)?;
+        }
+        Ok(out)
+    }
 }
@@ -73,6 +73,24 @@ impl<F: PrimeField + Absorb, U: UserData<F>> UserBul<F, U> for DummyStore {
     ) -> Result<(), Self::Error> {
         Ok(())
     }
+
+    fn append_multi_value<
+        Args: ark_ff::ToConstraintField<F> + Clone,
+        Snark: ark_snark::SNARK<F>,
+        const NUMUSERS: usize,
+        const NUMCBS: usize,
+    >(
+        &mut self,
+        _objects: [crate::generic::object::Com<F>; NUMUSERS],
+        _old_nuls: [crate::generic::object::Nul<F>; NUMUSERS],
+        _cb_com_lists: [[crate::generic::object::Com<F>; NUMCBS]; NUMUSERS],
+        _args: Args,
+        _proof: Snark::Proof,
+        _memb_data: Option<[Self::MembershipPub; NUMUSERS]>,
+        _verif_key: &Snark::VerifyingKey,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
 impl<F: PrimeField + Absorb, U: UserData<F>> JoinableBulletin<F, U> for DummyStore {
@@ -16,5 +16,8 @@ pub mod decentralized;
 pub mod dummy;
 /// Objects that implement [`HasherZK`](`super::crypto::hash::HasherZK`).
 pub mod hash;
+/// Pedersen vector commitments, which implement
+/// [`UserCommitment`](`super::crypto::com::UserCommitment`).
+pub mod pedersen;
 #[doc(hidden)]
 pub mod userdata;
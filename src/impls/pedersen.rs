@@ -0,0 +1,521 @@
+use crate::{
+    crypto::com::{ComError, HomomorphicCommitment, UserCommitment},
+    generic::object::{ComRand, ComRandVar, Ser, SerVar},
+};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{AdditiveGroup, BigInteger, PrimeField, ToConstraintField};
+use ark_grumpkin::{Projective as Grumpkin, constraints::GVar as GrumpkinVar};
+use ark_r1cs_std::{
+    alloc::{AllocVar, AllocationMode},
+    convert::{ToBitsGadget, ToConstraintFieldGadget},
+    eq::EqGadget,
+    fields::fp::FpVar,
+    groups::CurveVar,
+    prelude::Boolean,
+};
+use ark_relations::{
+    ns,
+    r1cs::{Namespace, SynthesisError},
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use blake2::{Blake2b512, Digest};
+use rand::{CryptoRng, RngCore};
+use std::{borrow::Borrow, marker::PhantomData};
+
+/// Converts a base field element into a scalar of the curve, as an integer. This matches the
+/// in-circuit scalar multiplication by the bits of the element.
+///
+/// This is injective only if the scalar field is larger than the base field (see
+/// [`scalar_field_covers_base_field`]).
+fn to_scalar<C: CurveGroup>(x: C::BaseField) -> C::ScalarField
+where
+    C::BaseField: PrimeField,
+{
+    C::ScalarField::from_le_bytes_mod_order(&x.into_bigint().to_bytes_le())
+}
+
+/// Returns whether every element of the base field of `C` is a distinct scalar of `C`, i.e. the
+/// scalar field modulus is at least the base field modulus.
+///
+/// Otherwise, elements `m` and `m + r` (where `r` is the group order) would commit to the same
+/// point, and so Pedersen commitments over `C` would not be binding.
+pub fn scalar_field_covers_base_field<C: CurveGroup>() -> bool
+where
+    C::BaseField: PrimeField,
+{
+    let scalar = C::ScalarField::MODULUS.to_bytes_le();
+    let base = C::BaseField::MODULUS.to_bytes_le();
+    let len = scalar.len().max(base.len());
+    let byte = |v: &[u8], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..len)
+        .rev()
+        .map(|i| byte(&scalar, i).cmp(&byte(&base, i)))
+        .find(|o| o.is_ne())
+        .is_none_or(|o| o.is_gt())
+}
+
+/// The domain separator from which [`PedersenParams::new`] derives generators.
+pub const PEDERSEN_GENERATOR_DOMAIN: &[u8] = b"zk-callbacks/pedersen/generator/v1";
+
+/// Hash to a point of the prime order subgroup of `C`, by try-and-increment.
+///
+/// Hashes the domain separator, `index` and a counter with Blake2b-512 until the output is the
+/// x coordinate of a point, and then clears the cofactor. Nobody knows the discrete logarithm of
+/// the output with respect to any other output.
+fn hash_to_curve<C: CurveGroup>(index: u64) -> C::Affine {
+    (0u64..)
+        .filter_map(|counter| {
+            let bytes = Blake2b512::new()
+                .chain_update(PEDERSEN_GENERATOR_DOMAIN)
+                .chain_update(index.to_le_bytes())
+                .chain_update(counter.to_le_bytes())
+                .finalize();
+            C::Affine::from_random_bytes(&bytes)
+                .map(|p| p.clear_cofactor())
+                .filter(|p| !p.is_zero())
+        })
+        .next()
+        .unwrap()
+}
+
+/// Public parameters for Pedersen vector commitments.
+///
+/// Consists of one generator per committed element, along with a generator for the blinding
+/// factor. The commitment is only binding if nobody knows a relation between the generators; use
+/// [`PedersenParams::new`] to derive them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenParams<C: CurveGroup> {
+    /// The generators for each element of the committed vector.
+    pub generators: Vec<C::Affine>,
+    /// The generator for the blinding factor.
+    pub blinding: C::Affine,
+}
+
+impl<C: CurveGroup> PedersenParams<C> {
+    /// Derive parameters to commit to vectors of at most `len` elements.
+    ///
+    /// The generators are hashed to the curve from [`PEDERSEN_GENERATOR_DOMAIN`], so the
+    /// parameters are the same for everyone, and there is no trapdoor with which to open a
+    /// commitment to a different vector. The blinding generator comes first, so parameters for
+    /// different lengths agree on their common generators.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar field of `C` is smaller than its base field, as commitments over `C`
+    /// would not be binding (see [`scalar_field_covers_base_field`]).
+    pub fn new(len: usize) -> Self
+    where
+        C::BaseField: PrimeField,
+    {
+        assert!(
+            scalar_field_covers_base_field::<C>(),
+            "Scalar field is smaller than the base field."
+        );
+        Self {
+            generators: (1..=len as u64).map(hash_to_curve::<C>).collect(),
+            blinding: hash_to_curve::<C>(0),
+        }
+    }
+
+    /// Sample parameters to commit to vectors of at most `len` elements with a trusted setup.
+    ///
+    /// Whoever runs the setup knows the discrete logarithms of the generators, and may open any
+    /// commitment to any vector. Only use this if the party running the setup is trusted, and
+    /// prefer [`PedersenParams::new`] otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar field of `C` is smaller than its base field (see
+    /// [`scalar_field_covers_base_field`]).
+    pub fn trusted_setup(rng: &mut (impl CryptoRng + RngCore), len: usize) -> Self
+    where
+        C::BaseField: PrimeField,
+    {
+        assert!(
+            scalar_field_covers_base_field::<C>(),
+            "Scalar field is smaller than the base field."
+        );
+        let generators = (0..len).map(|_| C::rand(rng).into_affine()).collect();
+        let blinding = C::rand(rng).into_affine();
+        Self {
+            generators,
+            blinding,
+        }
+    }
+}
+
+/// Public parameters for Pedersen vector commitments in-circuit.
+#[derive(Clone)]
+pub struct PedersenParamsVar<C: CurveGroup, CV: CurveVar<C, C::BaseField>>
+where
+    C::BaseField: PrimeField,
+{
+    /// The generators for each element of the committed vector.
+    pub generators: Vec<CV>,
+    /// The generator for the blinding factor.
+    pub blinding: CV,
+    _phantom_curve: PhantomData<C>,
+}
+
+impl<C: CurveGroup, CV: CurveVar<C, C::BaseField>> AllocVar<PedersenParams<C>, C::BaseField>
+    for PedersenParamsVar<C, CV>
+where
+    C::BaseField: PrimeField,
+{
+    fn new_variable<T: Borrow<PedersenParams<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let res = f();
+        res.and_then(|rec| {
+            let rec = rec.borrow();
+            let generators = rec
+                .generators
+                .iter()
+                .map(|g| {
+                    <CV as AllocVar<C, _>>::new_variable(
+                        ns!(cs, "generator"),
+                        || Ok(g.into_group()),
+                        mode,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let blinding = <CV as AllocVar<C, _>>::new_variable(
+                ns!(cs, "blinding"),
+                || Ok(rec.blinding.into_group()),
+                mode,
+            )?;
+            Ok(PedersenParamsVar {
+                generators,
+                blinding,
+                _phantom_curve: PhantomData,
+            })
+        })
+    }
+}
+
+/// A Pedersen commitment.
+///
+/// Note that the commitment is exposed as the affine coordinates of the committed point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PedersenCom<C: CurveGroup> {
+    /// The committed point.
+    pub point: C::Affine,
+}
+
+impl<C: CurveGroup> Default for PedersenCom<C> {
+    fn default() -> Self {
+        Self {
+            point: C::Affine::zero(),
+        }
+    }
+}
+
+impl<C: CurveGroup> ToConstraintField<C::BaseField> for PedersenCom<C>
+where
+    C::BaseField: PrimeField,
+{
+    fn to_field_elements(&self) -> Option<Vec<C::BaseField>> {
+        let (x, y) = self
+            .point
+            .xy()
+            .unwrap_or((C::BaseField::ZERO, C::BaseField::ZERO));
+        Some(vec![x, y])
+    }
+}
+
+/// A Pedersen commitment in-circuit, represented by the affine coordinates of the point.
+#[derive(Clone)]
+pub struct PedersenComVar<C: CurveGroup>
+where
+    C::BaseField: PrimeField,
+{
+    /// The x coordinate.
+    pub x: FpVar<C::BaseField>,
+    /// The y coordinate.
+    pub y: FpVar<C::BaseField>,
+    _phantom_curve: PhantomData<C>,
+}
+
+impl<C: CurveGroup> AllocVar<PedersenCom<C>, C::BaseField> for PedersenComVar<C>
+where
+    C::BaseField: PrimeField,
+{
+    fn new_variable<T: Borrow<PedersenCom<C>>>(
+        cs: impl Into<Namespace<C::BaseField>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let res = f();
+        res.and_then(|rec| {
+            let coords = rec.borrow().to_field_elements().unwrap();
+            let x = FpVar::new_variable(ns!(cs, "x"), || Ok(coords[0]), mode)?;
+            let y = FpVar::new_variable(ns!(cs, "y"), || Ok(coords[1]), mode)?;
+            Ok(PedersenComVar {
+                x,
+                y,
+                _phantom_curve: PhantomData,
+            })
+        })
+    }
+}
+
+impl<C: CurveGroup> EqGadget<C::BaseField> for PedersenComVar<C>
+where
+    C::BaseField: PrimeField,
+{
+    fn is_eq(&self, other: &Self) -> Result<Boolean<C::BaseField>, SynthesisError> {
+        Ok(self.x.is_eq(&other.x)? & self.y.is_eq(&other.y)?)
+    }
+}
+
+/// Pedersen vector commitments over a curve embedded in the field `F`.
+///
+/// Commits to a vector `(m_0, ..., m_n)` with randomness `r` as `r * B + sum m_i * G_i`, where the
+/// `G_i` and `B` are the generators from [`PedersenParams`]. Elements are interpreted as integers
+/// below the order of the curve.
+///
+/// This commitment is additively homomorphic (see [`HomomorphicCommitment`]). Updates act on the
+/// elements as integers, and do not wrap around the modulus of `F`: an update which takes an
+/// element past the modulus, or below zero, produces a commitment which can not be opened
+/// in-circuit.
+///
+/// The curve `C` must have base field `F`, so that the commitment may be computed efficiently
+/// in-circuit, and a scalar field at least as large as `F`, so that the commitment is binding;
+/// for example, Grumpkin for BN254. Curves such as Jubjub, whose scalar field is smaller than the
+/// base field, are refused.
+///
+/// Note these commitments are not used by bulletins, interactions or scans, which always commit
+/// with [`HashCom`](`crate::crypto::com::HashCom`). A homomorphic update can therefore not be
+/// applied to the commitment of a user in a bulletin; see [`UserCommitment`] for where these
+/// commitments may be used.
+#[derive(Clone, Debug)]
+pub struct Pedersen<C, CV> {
+    _phantom_curve: PhantomData<fn() -> (C, CV)>,
+}
+
+impl<F: PrimeField, C: CurveGroup<BaseField = F>, CV: CurveVar<C, F> + ToConstraintFieldGadget<F>>
+    UserCommitment<F> for Pedersen<C, CV>
+{
+    type Params = PedersenParams<C>;
+    type ParamsVar = PedersenParamsVar<C, CV>;
+    type Com = PedersenCom<C>;
+    type ComVar = PedersenComVar<C>;
+
+    /// # Panics
+    ///
+    /// Panics if there are fewer generators than elements, or if the scalar field of `C` is
+    /// smaller than `F`.
+    fn commit(params: &PedersenParams<C>, data: &[Ser<F>], rand: ComRand<F>) -> PedersenCom<C> {
+        assert!(
+            scalar_field_covers_base_field::<C>(),
+            "Scalar field is smaller than the base field."
+        );
+        assert!(
+            data.len() <= params.generators.len(),
+            "Too few generators for commitment."
+        );
+        let mut point = params.blinding * to_scalar::<C>(rand);
+        for (g, m) in params.generators.iter().zip(data) {
+            point += *g * to_scalar::<C>(*m);
+        }
+        PedersenCom {
+            point: point.into_affine(),
+        }
+    }
+
+    fn commit_in_zk(
+        params: &PedersenParamsVar<C, CV>,
+        data: &[SerVar<F>],
+        rand: &ComRandVar<F>,
+    ) -> Result<PedersenComVar<C>, SynthesisError> {
+        if data.len() > params.generators.len() || !scalar_field_covers_base_field::<C>() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut point = params.blinding.scalar_mul_le(rand.to_bits_le()?.iter())?;
+        for (g, m) in params.generators.iter().zip(data) {
+            point += g.scalar_mul_le(m.to_bits_le()?.iter())?;
+        }
+        let coords = point.to_constraint_field()?;
+        Ok(PedersenComVar {
+            x: coords[0].clone(),
+            y: coords[1].clone(),
+            _phantom_curve: PhantomData,
+        })
+    }
+}
+
+impl<F: PrimeField, C: CurveGroup<BaseField = F>, CV: CurveVar<C, F> + ToConstraintFieldGadget<F>>
+    HomomorphicCommitment<F> for Pedersen<C, CV>
+{
+    fn add_to(
+        params: &PedersenParams<C>,
+        com: &PedersenCom<C>,
+        index: usize,
+        delta: F,
+    ) -> Result<PedersenCom<C>, ComError> {
+        let g = generator(params, index)?;
+        Ok(PedersenCom {
+            point: (com.point.into_group() + g * to_scalar::<C>(delta)).into_affine(),
+        })
+    }
+
+    fn sub_from(
+        params: &PedersenParams<C>,
+        com: &PedersenCom<C>,
+        index: usize,
+        delta: F,
+    ) -> Result<PedersenCom<C>, ComError> {
+        let g = generator(params, index)?;
+        Ok(PedersenCom {
+            point: (com.point.into_group() - g * to_scalar::<C>(delta)).into_affine(),
+        })
+    }
+}
+
+/// Get the generator for the element at position `index`.
+fn generator<C: CurveGroup>(
+    params: &PedersenParams<C>,
+    index: usize,
+) -> Result<C::Affine, ComError> {
+    params
+        .generators
+        .get(index)
+        .copied()
+        .ok_or(ComError::IndexOutOfRange {
+            index,
+            len: params.generators.len(),
+        })
+}
+
+/// Pedersen commitments over Grumpkin, for use with BN254.
+pub type GrumpkinPedersen = Pedersen<Grumpkin, GrumpkinVar>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ark_bn254::Fr;
+    use ark_ed_on_bls12_381::{EdwardsProjective as Jubjub, constraints::EdwardsVar as JubjubVar};
+    use ark_ff::{One, UniformRand};
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use rand::thread_rng;
+
+    type JubjubPedersen = Pedersen<Jubjub, JubjubVar>;
+
+    #[test]
+    fn pedersen_native_matches_gadget() {
+        let mut rng = thread_rng();
+        let params = PedersenParams::<Grumpkin>::new(4);
+        let data = vec![Fr::from(7), -Fr::one(), Fr::rand(&mut rng)];
+        let rand = Fr::rand(&mut rng);
+        let com = GrumpkinPedersen::commit(&params, &data, rand);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let params_var =
+            PedersenParamsVar::<Grumpkin, GrumpkinVar>::new_constant(ns!(cs, "params"), &params)
+                .unwrap();
+        let data_var = Vec::<FpVar<Fr>>::new_witness(ns!(cs, "data"), || Ok(data.clone())).unwrap();
+        let rand_var = FpVar::new_witness(ns!(cs, "rand"), || Ok(rand)).unwrap();
+        let com_var = GrumpkinPedersen::commit_in_zk(&params_var, &data_var, &rand_var).unwrap();
+        let expected = PedersenComVar::new_input(ns!(cs, "com"), || Ok(com)).unwrap();
+        com_var.enforce_equal(&expected).unwrap();
+
+        let coords = com.to_field_elements().unwrap();
+        assert_eq!(com_var.x.value().unwrap(), coords[0]);
+        assert_eq!(com_var.y.value().unwrap(), coords[1]);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn pedersen_binding_at_field_boundary() {
+        let mut rng = thread_rng();
+        let params = PedersenParams::<Grumpkin>::new(1);
+        let rand = Fr::rand(&mut rng);
+
+        // The largest element does not wrap around the group order.
+        let max = -Fr::one();
+        assert_ne!(to_scalar::<Grumpkin>(max), to_scalar::<Grumpkin>(Fr::ZERO));
+        assert_ne!(
+            GrumpkinPedersen::commit(&params, &[max], rand),
+            GrumpkinPedersen::commit(&params, &[Fr::ZERO], rand)
+        );
+
+        // Homomorphic updates do not wrap around the field modulus, so adding one to the largest
+        // element does not commit to zero.
+        let com = GrumpkinPedersen::commit(&params, &[max], rand);
+        let updated = GrumpkinPedersen::add_to(&params, &com, 0, Fr::one()).unwrap();
+        let expected = GrumpkinPedersen::commit(&params, &[Fr::ZERO], rand);
+        assert_ne!(updated, expected);
+        assert_eq!(
+            GrumpkinPedersen::sub_from(&params, &updated, 0, Fr::one()).unwrap(),
+            com
+        );
+    }
+
+    #[test]
+    fn pedersen_generators_are_derived() {
+        let params = PedersenParams::<Grumpkin>::new(3);
+        assert_eq!(params, PedersenParams::<Grumpkin>::new(3));
+        assert_eq!(
+            params.generators[..2],
+            PedersenParams::<Grumpkin>::new(2).generators
+        );
+
+        let mut all = params.generators.clone();
+        all.push(params.blinding);
+        for (i, g) in all.iter().enumerate() {
+            assert!(!g.is_zero());
+            assert!(g.is_in_correct_subgroup_assuming_on_curve());
+            assert!(all[i + 1..].iter().all(|h| h != g));
+        }
+    }
+
+    #[test]
+    fn pedersen_refuses_small_scalar_field() {
+        assert!(scalar_field_covers_base_field::<Grumpkin>());
+        assert!(!scalar_field_covers_base_field::<Jubjub>());
+
+        // Over Jubjub, `m` and `m + r` would collide.
+        let r = <Jubjub as ark_ec::PrimeGroup>::ScalarField::MODULUS;
+        let m = <Jubjub as CurveGroup>::BaseField::from(3u64);
+        let wrapped =
+            m + <Jubjub as CurveGroup>::BaseField::from_le_bytes_mod_order(&r.to_bytes_le());
+        assert_eq!(to_scalar::<Jubjub>(m), to_scalar::<Jubjub>(wrapped));
+
+        let result = std::panic::catch_unwind(|| {
+            PedersenParams::<Jubjub>::new(1);
+        });
+        assert!(result.is_err());
+        let result = std::panic::catch_unwind(|| {
+            PedersenParams::<Jubjub>::trusted_setup(&mut thread_rng(), 1);
+        });
+        assert!(result.is_err());
+        let params = PedersenParams::<Jubjub> {
+            generators: vec![Default::default()],
+            blinding: Default::default(),
+        };
+        assert!(std::panic::catch_unwind(|| JubjubPedersen::commit(&params, &[m], m)).is_err());
+    }
+
+    #[test]
+    fn pedersen_update_out_of_range() {
+        let mut rng = thread_rng();
+        let params = PedersenParams::<Grumpkin>::new(2);
+        let com = GrumpkinPedersen::commit(&params, &[Fr::one()], Fr::rand(&mut rng));
+        assert_eq!(
+            GrumpkinPedersen::add_to(&params, &com, 2, Fr::one()),
+            Err(ComError::IndexOutOfRange { index: 2, len: 2 })
+        );
+        assert_eq!(
+            GrumpkinPedersen::sub_from(&params, &com, 5, Fr::one()),
+            Err(ComError::IndexOutOfRange { index: 5, len: 2 })
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use crate::generic::user::UserData;
+use crate::generic::{interaction::Revealable, object::Ser, user::UserData};
 use ark_crypto_primitives::sponge::Absorb;
-use ark_ff::{Fp, FpConfig, PrimeField, ToConstraintField};
+use ark_ff::{BigInteger, Fp, FpConfig, PrimeField, ToConstraintField};
 use ark_r1cs_std::{
     boolean::Boolean,
     convert::{ToBytesGadget, ToConstraintFieldGadget},
@@ -146,3 +146,52 @@ impl<G: PrimeField + Absorb, T: UserData<G>, const N: usize> UserData<G> for [T;
         Ok(buf)
     }
 }
+
+impl<P: FpConfig<N>, const N: usize> Revealable<Fp<P, N>> for Fp<P, N> {
+    fn from_revealed(elems: &[Ser<Self>]) -> Option<Self> {
+        let [x] = elems else {
+            return None;
+        };
+        Some(*x)
+    }
+}
+
+impl<G: PrimeField + Absorb> Revealable<G> for bool {
+    fn from_revealed(elems: &[Ser<G>]) -> Option<Self> {
+        match elems {
+            [x] if *x == G::from(0u8) => Some(false),
+            [x] if *x == G::from(1u8) => Some(true),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_revealable_uint {
+    ( $x:ty ) => {
+        impl<G: PrimeField + Absorb> Revealable<G> for $x {
+            fn from_revealed(elems: &[Ser<G>]) -> Option<Self> {
+                let [x] = elems else {
+                    return None;
+                };
+                let bytes = x.into_bigint().to_bytes_le();
+                let (low, high) = bytes.split_at(std::mem::size_of::<$x>());
+                if high.iter().any(|b| *b != 0) {
+                    return None;
+                }
+                Some(<$x>::from_le_bytes(low.try_into().ok()?))
+            }
+        }
+    };
+}
+
+impl_revealable_uint!(u8);
+impl_revealable_uint!(u16);
+impl_revealable_uint!(u32);
+impl_revealable_uint!(u64);
+impl_revealable_uint!(u128);
+
+impl<G: PrimeField + Absorb> Revealable<G> for () {
+    fn from_revealed(elems: &[Ser<G>]) -> Option<Self> {
+        elems.is_empty().then_some(())
+    }
+}
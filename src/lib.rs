@@ -128,6 +128,8 @@ pub mod util;
 ///         })
 ///     }
 /// }
+///
+/// assert_eq!(DataFields::reputation().name, "reputation");
 /// ```
 pub use zk_object::scannable_zk_object;
 
@@ -141,6 +143,10 @@ pub use zk_object::scannable_zk_object;
 /// flexibility with types, these objects will not necessarily implement conditional selection or
 /// equality, and therefore they cannot be used when calling `scan_callbacks`.
 ///
+/// Both macros also generate a struct `DataFields` (for a structure `Data`), with a reference to
+/// each field for building predicates (see [`UserFields`](generic::interaction::UserFields)).
+/// These are not methods of `Data`, so they never collide with its own methods.
+///
 /// ```rust
 /// use ark_bls12_381::Fr;
 /// use zk_callbacks::zk_object;
@@ -152,6 +158,14 @@ pub use zk_object::scannable_zk_object;
 ///     is_banned: bool,
 ///     reputation: u8,
 /// }
+///
+/// impl Data {
+///     fn karma(&self) -> Fr {
+///         self.karma
+///     }
+/// }
+///
+/// assert_eq!(DataFields::karma().name, "karma");
 /// ```
 ///
 /// If an in-circuit representation already exists, one may use the additional argument to pass
@@ -211,5 +225,7 @@ pub use zk_object::scannable_zk_object;
 ///         })
 ///     }
 /// }
+///
+/// assert_eq!(DataFields::reputation().name, "reputation");
 /// ```
 pub use zk_object::zk_object;
@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::{
     parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
-    Fields, GenericParam, Generics, Ident, Type,
+    Fields, GenericParam, Generics, Ident, Type, Visibility,
 };
 
 fn add_trait_bounds(mut generics: Generics, field_type: &TokenStream) -> Generics {
@@ -125,6 +125,74 @@ fn derive_userdata_and_zk(
     }
 }
 
+fn derive_field_refs(
+    data: &Data,
+    ft: &TokenStream,
+    name: &Ident,
+    var_type: &TokenStream,
+    vis: &Visibility,
+    generics: &Generics,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return quote! {},
+        },
+        _ => return quote! {},
+    };
+
+    let mut fields_struct_name = name.to_string();
+    fields_struct_name.push_str("Fields");
+    let fields_name = Ident::new(&fields_struct_name, name.span());
+    let struct_doc = proc_macro2::Literal::string(&format!(
+        " References to the fields of [`{}`], for use in a [`UserPredicate`](zk_callbacks::generic::interaction::UserPredicate).",
+        name
+    ));
+
+    let accessors = fields.iter().map(|f| {
+        let field_name = &f.ident;
+        let ty = &f.ty;
+        let lit = proc_macro2::Literal::string(&(field_name.clone()).unwrap().to_string());
+        let doc = proc_macro2::Literal::string(&format!(
+            " The `{}` field, for use in a [`UserPredicate`](zk_callbacks::generic::interaction::UserPredicate).",
+            field_name.clone().unwrap()
+        ));
+        quote_spanned! {f.span() =>
+            #[doc = #doc]
+            pub fn #field_name() -> zk_callbacks::generic::interaction::UserField<#ft, #name #ty_generics> {
+                zk_callbacks::generic::interaction::UserField {
+                    name: #lit,
+                    native: |data: &#name #ty_generics| <#ty as zk_callbacks::generic::user::UserData<#ft>>::serialize_elements(&data.#field_name),
+                    in_zk: |data: &#var_type| <#ty as zk_callbacks::generic::user::UserData<#ft>>::serialize_in_zk(data.#field_name.clone()),
+                }
+            }
+        }
+    });
+
+    let field_names = fields.iter().map(|f| {
+        let field_name = &f.ident;
+        quote_spanned! {f.span() => #fields_name::#field_name() }
+    });
+
+    quote! {
+        #[doc = #struct_doc]
+        #vis struct #fields_name;
+
+        impl #fields_name {
+            #(#accessors)*
+        }
+
+        impl #impl_generics zk_callbacks::generic::interaction::UserFields<#ft> for #name #ty_generics #where_clause {
+            type Fields = #fields_name;
+
+            fn fields() -> Vec<zk_callbacks::generic::interaction::UserField<#ft, Self>> {
+                vec![#(#field_names, )*]
+            }
+        }
+    }
+}
+
 #[proc_macro_attribute]
 pub fn zk_object(
     args: proc_macro::TokenStream,
@@ -161,6 +229,18 @@ pub fn zk_object(
 
     let (s1, s2, fields, zk_names, alloc, _fcond, _eq) =
         derive_userdata_and_zk(&ast.data, field_type.clone());
+    let var_type = match noalloc {
+        Some(ref t) => quote! { #t },
+        None => quote! { #zk_var_name },
+    };
+    let field_refs = derive_field_refs(
+        &ast.data,
+        &field_type,
+        &name,
+        &var_type,
+        &ast.vis,
+        &generics,
+    );
     let tok = match noalloc {
         Some(t) => {
             quote! {
@@ -183,6 +263,8 @@ pub fn zk_object(
                         Ok(buf)
                     }
                 }
+
+                #field_refs
             }
         }
         None => {
@@ -231,6 +313,8 @@ pub fn zk_object(
                         Ok(buf)
                     }
                 }
+
+                #field_refs
             }
         }
     };
@@ -274,6 +358,18 @@ pub fn scannable_zk_object(
 
     let (s1, s2, fields, zk_names, alloc, fp_cond, eqg) =
         derive_userdata_and_zk(&ast.data, field_type.clone());
+    let var_type = match noalloc {
+        Some(ref t) => quote! { #t },
+        None => quote! { #zk_var_name },
+    };
+    let field_refs = derive_field_refs(
+        &ast.data,
+        &field_type,
+        &name,
+        &var_type,
+        &ast.vis,
+        &generics,
+    );
     let tok = match noalloc {
         Some(t) => {
             quote! {
@@ -296,6 +392,8 @@ pub fn scannable_zk_object(
                         Ok(buf)
                     }
                 }
+
+                #field_refs
             }
         }
         None => {
@@ -366,6 +464,8 @@ pub fn scannable_zk_object(
                         Ok(buf)
                     }
                 }
+
+                #field_refs
             }
         }
     };